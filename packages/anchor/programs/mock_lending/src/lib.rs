@@ -0,0 +1,258 @@
+//! Stand-in lending program for local tests of pledge yield mode
+//! Implements the lending adapter interface the pledge program calls
+//! (`deposit(amount)` / `withdraw()`, see pledge::utils::lending) and lets tests
+//! accrue interest on a position by donating tokens to the reserve
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("9YjvvzGaLp6edfWbTpKSsD1C2rdBKQZmm9uzm3wbCgHv");
+
+pub const RESERVE_SEED: &[u8] = b"reserve";
+pub const RESERVE_VAULT_SEED: &[u8] = b"reserve_vault";
+pub const POSITION_SEED: &[u8] = b"position";
+
+#[program]
+pub mod mock_lending {
+    use super::*;
+
+    /// Create the reserve holding deposits of one mint (permissionless)
+    pub fn init_reserve(ctx: Context<InitReserve>) -> Result<()> {
+        let reserve = &mut ctx.accounts.reserve;
+        reserve.mint = ctx.accounts.mint.key();
+        reserve.vault = ctx.accounts.reserve_vault.key();
+        reserve.bump = ctx.bumps.reserve;
+        Ok(())
+    }
+
+    /// Adapter interface: lend `amount` from the owner's token account
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source.to_account_info(),
+                to: ctx.accounts.reserve_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.reserve = ctx.accounts.reserve.key();
+        position.balance = amount;
+        position.bump = ctx.bumps.position;
+        Ok(())
+    }
+
+    /// Adapter interface: return the whole position (principal plus interest)
+    /// and close it
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        let mint = ctx.accounts.reserve.mint;
+        let reserve_seeds = &[RESERVE_SEED, mint.as_ref(), &[ctx.accounts.reserve.bump]];
+        let signer_seeds = &[&reserve_seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reserve_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.reserve.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, ctx.accounts.position.balance)?;
+        Ok(())
+    }
+
+    /// Test helper: donate `amount` to the reserve as interest on a position
+    pub fn accrue(ctx: Context<Accrue>, amount: u64) -> Result<()> {
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.donor_token_account.to_account_info(),
+                to: ctx.accounts.reserve_vault.to_account_info(),
+                authority: ctx.accounts.donor.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_add(amount)
+            .ok_or(MockLendingError::Overflow)?;
+        Ok(())
+    }
+
+    /// Test helper: lose `amount` of a position (bad debt), sent to the donor
+    pub fn slash(ctx: Context<Slash>, amount: u64) -> Result<()> {
+        let mint = ctx.accounts.reserve.mint;
+        let reserve_seeds = &[RESERVE_SEED, mint.as_ref(), &[ctx.accounts.reserve.bump]];
+        let signer_seeds = &[&reserve_seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reserve_vault.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.reserve.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, amount)?;
+
+        let position = &mut ctx.accounts.position;
+        position.balance = position
+            .balance
+            .checked_sub(amount)
+            .ok_or(MockLendingError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct Reserve {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub bump: u8,
+}
+
+impl Reserve {
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 1;
+}
+
+#[account]
+pub struct Position {
+    pub owner: Pubkey,
+    pub reserve: Pubkey,
+    pub balance: u64, // Principal plus accrued interest
+    pub bump: u8,
+}
+
+impl Position {
+    pub const INIT_SPACE: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitReserve<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Reserve::INIT_SPACE,
+        seeds = [RESERVE_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = reserve,
+        seeds = [RESERVE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut, token::authority = owner)]
+    pub source: Account<'info, TokenAccount>,
+
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(mut, address = reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Position::INIT_SPACE,
+        seeds = [POSITION_SEED, reserve.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: Receives the position's rent
+    #[account(mut)]
+    pub rent_receiver: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = reserve.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(mut, address = reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = rent_receiver,
+        seeds = [POSITION_SEED, reserve.key().as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Accrue<'info> {
+    pub donor: Signer<'info>,
+
+    #[account(mut, token::mint = reserve.mint, token::authority = donor)]
+    pub donor_token_account: Account<'info, TokenAccount>,
+
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(mut, address = reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = reserve)]
+    pub position: Account<'info, Position>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Slash<'info> {
+    #[account(mut, token::mint = reserve.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub reserve: Account<'info, Reserve>,
+
+    #[account(mut, address = reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = reserve)]
+    pub position: Account<'info, Position>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum MockLendingError {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
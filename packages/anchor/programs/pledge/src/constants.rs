@@ -3,10 +3,46 @@ pub const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1
 pub const USDC_MINT_DEVNET: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU";
 
 // Default config values
-pub const DEFAULT_TREASURY_SPLIT_BPS: u16 = 7000; // 70%
 pub const DEFAULT_PARTIAL_FEE_BPS: u16 = 100; // 1%
 pub const DEFAULT_EDIT_PENALTY_BPS: u16 = 1000; // 10%
 pub const DEFAULT_GRACE_PERIOD: i64 = 86400; // 1 day in seconds
+pub const DEFAULT_MAX_BENEFICIARY_BPS: u16 = 5000; // 50% of the charity share
+pub const DEFAULT_CRANK_REWARD_BPS: u16 = 50; // 0.5% of fees + forfeitures
+pub const DEFAULT_CHALLENGE_CUT_BPS: u16 = 1000; // 10% of challenge forfeitures
+pub const DEFAULT_MAX_DEFAULT_PERCENTAGE: u8 = 50; // Highest fixed default outcome users may pick
+
+// Crank reward cap
+pub const MAX_CRANK_REWARD_BPS: u16 = 500; // 5%
+
+// Revenue split
+pub const MAX_REVENUE_SHARES: usize = 5;
+
+// Deadline index
+pub const SECONDS_PER_BUCKET: i64 = 86400; // One bucket per day
+pub const DEADLINE_BUCKET_SHARDS: u8 = 16; // Buckets per day - clients spread pledges across them
+pub const MAX_BUCKET_ENTRIES: usize = 128;
+
+// Self-imposed user limits
+pub const LIMITS_WINDOW_SECONDS: i64 = 30 * 86400; // Monthly caps reset every 30 days
+pub const LIMIT_RAISE_DELAY_SECONDS: i64 = 7 * 86400; // Raising a cap waits a week
+
+// Session keys
+pub const MAX_SESSION_SECONDS: i64 = 30 * 86400; // Longest a session key stays valid
+
+// Wallet recovery
+pub const RECOVERY_INACTIVITY_SECONDS: i64 = 180 * 86400; // Owner silence before a recovery can start
+pub const RECOVERY_CHALLENGE_SECONDS: i64 = 14 * 86400; // Owner's window to cancel a started recovery
+
+// Batch settlement
+// pledge, vault, user, user_token_account, deadline_bucket, user_counter, recovery, rent_payer
+pub const BATCH_ACCOUNTS_PER_PLEDGE: usize = 8;
+
+// Account layout versions (new fields are carved out of the reserved bytes,
+// the account grows on migration once they run out)
+pub const PLEDGE_VERSION: u8 = 1;
+pub const CONFIG_VERSION: u8 = 1;
+pub const PLEDGE_RESERVED_BYTES: usize = 60;
+pub const CONFIG_RESERVED_BYTES: usize = 19;
 
 // Basis points
 pub const BPS_DENOMINATOR: u64 = 10000;
@@ -15,3 +51,17 @@ pub const BPS_DENOMINATOR: u64 = 10000;
 pub const CONFIG_SEED: &[u8] = b"config";
 pub const PLEDGE_SEED: &[u8] = b"pledge";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const FEE_LEDGER_SEED: &[u8] = b"fee_ledger";
+pub const CHARITY_SEED: &[u8] = b"charity";
+pub const CHARITY_LEDGER_SEED: &[u8] = b"charity_ledger";
+pub const DEADLINE_BUCKET_SEED: &[u8] = b"deadline_bucket";
+pub const USER_COUNTER_SEED: &[u8] = b"user_counter";
+pub const USER_LIMITS_SEED: &[u8] = b"user_limits";
+pub const CHALLENGE_SEED: &[u8] = b"challenge";
+pub const CHALLENGE_VAULT_SEED: &[u8] = b"challenge_vault";
+pub const CONTRIBUTION_SEED: &[u8] = b"contribution";
+pub const SPONSORSHIP_SEED: &[u8] = b"sponsorship";
+pub const SPONSOR_VAULT_SEED: &[u8] = b"sponsor_vault";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const RECOVERY_SEED: &[u8] = b"recovery";
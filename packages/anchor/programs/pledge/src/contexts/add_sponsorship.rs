@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+use crate::constants::{CONFIG_SEED, PLEDGE_SEED, SPONSORSHIP_SEED, SPONSOR_VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeStatus, ProgramConfig, Sponsorship, SponsorshipAdded};
+
+#[derive(Accounts)]
+pub struct AddSponsorship<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    /// Pledge's sponsorship record (created by the first deposit)
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = Sponsorship::INIT_SPACE,
+        seeds = [SPONSORSHIP_SEED, pledge.key().as_ref()],
+        bump
+    )]
+    pub sponsorship: Account<'info, Sponsorship>,
+
+    /// Bonus vault, released by the pledge PDA at settlement
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        token::mint = mint,
+        token::authority = pledge,
+        seeds = [SPONSOR_VAULT_SEED, pledge.key().as_ref()],
+        bump
+    )]
+    pub sponsor_vault: Account<'info, TokenAccount>,
+
+    #[account(address = pledge.mint @ ErrorCode::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = sponsor
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddSponsorship<'info> {
+    pub fn add_sponsorship(&mut self, amount: u64, bumps: &AddSponsorshipBumps) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(
+            clock.unix_timestamp < self.pledge.deadline,
+            ErrorCode::DeadlineAlreadyPassed
+        );
+        require!(
+            self.sponsor.key() != self.pledge.user,
+            ErrorCode::InvalidSponsor
+        );
+
+        // Challenge pledges can end as a cancelled duel, which refunds only the stake
+        require!(
+            self.pledge.challenge.is_none(),
+            ErrorCode::SponsorshipUnavailable
+        );
+
+        // One sponsor per pledge - later deposits top up the same bonus
+        if self.sponsorship.sponsor == Pubkey::default() {
+            self.sponsorship.pledge = self.pledge.key();
+            self.sponsorship.sponsor = self.sponsor.key();
+            self.sponsorship.vault = self.sponsor_vault.key();
+            self.sponsorship.bump = bumps.sponsorship;
+            self.sponsorship.vault_bump = bumps.sponsor_vault;
+        }
+        require!(
+            self.sponsorship.sponsor == self.sponsor.key(),
+            ErrorCode::InvalidSponsor
+        );
+
+        let transfer_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.sponsor_token_account.to_account_info(),
+                to: self.sponsor_vault.to_account_info(),
+                authority: self.sponsor.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount)?;
+
+        self.sponsorship.amount = self
+            .sponsorship
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.pledge.sponsorship = self.sponsorship.amount;
+
+        emit!(SponsorshipAdded {
+            pledge: self.pledge.key(),
+            sponsor: self.sponsor.key(),
+            amount,
+            total: self.sponsorship.amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::RECOVERY_SEED;
+use crate::state::{Recovery, RecoveryCancelled};
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, user.key().as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+}
+
+impl<'info> CancelRecovery<'info> {
+    pub fn cancel_recovery(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.recovery.cancel(now)?;
+
+        emit!(RecoveryCancelled {
+            user: self.user.key(),
+            recovery_address: self.recovery.recovery_address,
+        });
+
+        Ok(())
+    }
+}
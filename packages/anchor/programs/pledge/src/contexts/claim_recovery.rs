@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{PLEDGE_SEED, RECOVERY_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeRecovered, PledgeStatus, Recovery};
+
+#[derive(Accounts)]
+pub struct ClaimRecovery<'info> {
+    /// Recovery address taking the pledge over
+    pub recovery_address: Signer<'info>,
+
+    #[account(
+        seeds = [RECOVERY_SEED, pledge.user.as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = matches!(pledge.status, PledgeStatus::Active | PledgeStatus::Reported)
+            @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+}
+
+impl<'info> ClaimRecovery<'info> {
+    pub fn claim_recovery(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.recovery.check_claimant(
+            self.recovery_address.key(),
+            self.pledge.user,
+            self.pledge.created_at,
+            now,
+        )?;
+
+        // The owner keeps the pledge until its deadline
+        require!(now >= self.pledge.deadline, ErrorCode::DeadlineNotPassed);
+
+        // Refunds and reporting move to the recovery address; the recorded refund
+        // account belongs to the lost wallet
+        self.pledge.recovery = Some(self.recovery_address.key());
+        self.pledge.refund_account = None;
+
+        emit!(PledgeRecovered {
+            pledge: self.pledge.key(),
+            user: self.pledge.user,
+            recovery_address: self.recovery_address.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::constants::{CONTRIBUTION_SEED, PLEDGE_SEED, USER_COUNTER_SEED, VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Contribution, Pledge, PledgeStatus, TeamRefundClaimed, UserCounter};
+use crate::utils::settlement::rent_recipient;
+
+#[derive(Accounts)]
+pub struct ClaimTeamRefund<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = matches!(pledge.status, PledgeStatus::Completed | PledgeStatus::Forfeited)
+            @ ErrorCode::PledgeNotSettled
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Pledge owner - receives the vault rent once the last contributor claims
+    #[account(mut, address = pledge.user)]
+    pub user: AccountInfo<'info>,
+
+    /// CHECK: Sponsor that paid the pledge's rent (required when pledge.rent_payer is set)
+    #[account(mut)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+
+    /// Contribution record - closed to the contributor on claim
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [CONTRIBUTION_SEED, pledge.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    /// Contributor's pledge counter - the contribution stops counting against their limits
+    #[account(
+        mut,
+        seeds = [USER_COUNTER_SEED, contributor.key().as_ref()],
+        bump = contributor_counter.bump
+    )]
+    pub contributor_counter: Account<'info, UserCounter>,
+
+    #[account(
+        mut,
+        token::mint = pledge.mint,
+        token::authority = contributor
+    )]
+    pub contributor_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimTeamRefund<'info> {
+    pub fn claim_team_refund(&mut self) -> Result<()> {
+        let stake_amount = self.pledge.stake_amount;
+        let Some(team) = self.pledge.team.as_mut() else {
+            return err!(ErrorCode::InvalidTeam);
+        };
+
+        // Share of the refund pool proportional to the contribution
+        let amount = team.next_refund(self.contribution.amount, stake_amount, self.vault.amount)?;
+        team.claimed = team.claimed.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        let last_claim = team.claimed == team.contributors;
+
+        let lost = self
+            .contribution
+            .amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        self.contributor_counter.release(
+            self.contribution.amount,
+            lost,
+            true,
+            Clock::get()?.unix_timestamp,
+        );
+
+        // Create PDA signer seeds for pledge (which is the vault authority)
+        let user_key = self.pledge.user;
+        let pledge_seed = self.pledge.seed();
+        let pledge_seeds = &[
+            PLEDGE_SEED,
+            user_key.as_ref(),
+            pledge_seed.as_ref(),
+            &[self.pledge.bump],
+        ];
+        let signer_seeds = &[&pledge_seeds[..]];
+
+        if amount > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: self.contributor_token_account.to_account_info(),
+                    authority: self.pledge.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_ctx, amount)?;
+        }
+
+        // Last claim empties the vault - return its rent to whoever paid it
+        if last_claim {
+            let rent_recipient = rent_recipient(
+                &self.pledge,
+                &self.user,
+                self.rent_payer.as_ref().map(|account| account.to_account_info()),
+            )?;
+            let close_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                CloseAccount {
+                    account: self.vault.to_account_info(),
+                    destination: rent_recipient,
+                    authority: self.pledge.to_account_info(),
+                },
+                signer_seeds,
+            );
+            close_account(close_ctx)?;
+        }
+
+        emit!(TeamRefundClaimed {
+            pledge: self.pledge.key(),
+            contributor: self.contributor.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
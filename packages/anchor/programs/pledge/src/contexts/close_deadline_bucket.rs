@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::DEADLINE_BUCKET_SEED;
+use crate::errors::ErrorCode;
+use crate::state::{DeadlineBucket, DeadlineBucketClosed};
+
+#[derive(Accounts)]
+pub struct CloseDeadlineBucket<'info> {
+    /// Any signer can close an empty bucket - rent only goes back to its payer
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &deadline_bucket.day.to_le_bytes(),
+            &[deadline_bucket.shard]
+        ],
+        bump = deadline_bucket.bump,
+        constraint = deadline_bucket.pledges.is_empty() @ ErrorCode::DeadlineBucketNotEmpty,
+        close = rent_payer
+    )]
+    pub deadline_bucket: Account<'info, DeadlineBucket>,
+
+    /// CHECK: Account that paid the bucket's rent, receives it back
+    #[account(mut, address = deadline_bucket.rent_payer)]
+    pub rent_payer: AccountInfo<'info>,
+}
+
+impl<'info> CloseDeadlineBucket<'info> {
+    pub fn close_deadline_bucket(&mut self) -> Result<()> {
+        emit!(DeadlineBucketClosed {
+            day: self.deadline_bucket.day,
+            shard: self.deadline_bucket.shard,
+            rent_payer: self.rent_payer.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::{CHALLENGE_SEED, CHALLENGE_VAULT_SEED, CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Challenge, ChallengeCreated, DuelTerms, ProgramConfig};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: u64)]
+pub struct CreateChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Challenge::INIT_SPACE,
+        seeds = [CHALLENGE_SEED, creator.key().as_ref(), &challenge_id.to_le_bytes()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Pool vault that collects forfeitures for the challenge's completers
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = challenge,
+        seeds = [CHALLENGE_VAULT_SEED, challenge.key().as_ref()],
+        bump
+    )]
+    pub challenge_vault: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateChallenge<'info> {
+    pub fn create_challenge(
+        &mut self,
+        challenge_id: u64,
+        stake_amount: u64,
+        deadline: i64,
+        join_by: i64,
+        duel: Option<DuelTerms>,
+        bumps: &CreateChallengeBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(stake_amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(deadline > now, ErrorCode::InvalidDeadline);
+        Challenge::validate_join_by(now, join_by, deadline)?;
+        self.config.limits.check_stake(stake_amount)?;
+        if let Some(duel) = duel {
+            duel.validate(self.creator.key(), now, join_by)?;
+        }
+
+        self.challenge.set_inner(Challenge {
+            creator: self.creator.key(),
+            challenge_id,
+            mint: self.mint.key(),
+            stake_amount,
+            deadline,
+            join_by,
+            vault: self.challenge_vault.key(),
+            participants: 0,
+            settled: 0,
+            completers: 0,
+            paid_completers: 0,
+            pool: 0,
+            distributed: false,
+            bump: bumps.challenge,
+            vault_bump: bumps.challenge_vault,
+            duel,
+        });
+
+        emit!(ChallengeCreated {
+            challenge: self.challenge.key(),
+            creator: self.creator.key(),
+            mint: self.mint.key(),
+            stake_amount,
+            deadline,
+            join_by,
+            duel,
+        });
+
+        Ok(())
+    }
+}
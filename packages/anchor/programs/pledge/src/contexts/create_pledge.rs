@@ -1,22 +1,37 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{transfer, Mint, Token, TokenAccount, Transfer},
-};
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
-use crate::constants::{CONFIG_SEED, PLEDGE_SEED, VAULT_SEED};
+use crate::constants::{
+    CONFIG_SEED, DEADLINE_BUCKET_SEED, DEADLINE_BUCKET_SHARDS, PLEDGE_RESERVED_BYTES, PLEDGE_SEED,
+    PLEDGE_VERSION, RECOVERY_SEED, USER_COUNTER_SEED, USER_LIMITS_SEED, VAULT_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::state::{Pledge, PledgeCreated, PledgeStatus, ProgramConfig};
-
-// Maximum allowed clock drift (5 minutes in seconds)
-const MAX_CLOCK_DRIFT: i64 = 300;
+use crate::state::{
+    BeneficiaryTerms, Challenge, CharityLedger, CharityRecord, DeadlineBucket, DefaultOutcome, GoalMetadata, Pledge,
+    PledgeCreated, PledgeStatus, ProgramConfig, Recovery, TeamReporting, TeamState,
+    UserCounter, UserLimits,
+};
 
 #[derive(Accounts)]
-#[instruction(stake_amount: u64, deadline: i64, created_at: i64)]
+#[instruction(
+    stake_amount: u64,
+    deadline: i64,
+    beneficiary: Option<BeneficiaryTerms>,
+    metadata: GoalMetadata,
+    early_report: bool,
+    grace_period_seconds: Option<i64>,
+    default_outcome: DefaultOutcome,
+    refund_to_user: bool,
+    team: Option<TeamReporting>,
+    deadline_shard: u8
+)]
 pub struct CreatePledge<'info> {
-    #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Pays rent for the new accounts - the user, or a backend sponsoring a user without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
         seeds = [CONFIG_SEED],
         bump = config.bump,
@@ -24,18 +39,44 @@ pub struct CreatePledge<'info> {
     )]
     pub config: Account<'info, ProgramConfig>,
 
+    /// Per-user pledge counter (created on the user's first pledge)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UserCounter::INIT_SPACE,
+        seeds = [USER_COUNTER_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_counter: Account<'info, UserCounter>,
+
+    /// CHECK: User's self-imposed limits - may not exist if the user never set any
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_limits: UncheckedAccount<'info>,
+
+    /// CHECK: User's recovery profile - may not exist; the owner signing counts as activity
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub recovery: UncheckedAccount<'info>,
+
     #[account(
         init,
-        payer = user,
+        payer = payer,
         space = Pledge::INIT_SPACE,
-        seeds = [PLEDGE_SEED, user.key().as_ref(), &created_at.to_le_bytes()],
+        seeds = [PLEDGE_SEED, user.key().as_ref(), &user_counter.next_index.to_le_bytes()],
         bump
     )]
     pub pledge: Account<'info, Pledge>,
 
     #[account(
         init,
-        payer = user,
+        payer = payer,
         token::mint = mint,
         token::authority = pledge,
         seeds = [VAULT_SEED, pledge.key().as_ref()],
@@ -43,50 +84,203 @@ pub struct CreatePledge<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    /// CHECK: Deadline index bucket for the pledge's deadline day and chosen shard - created on
+    /// first use and grown by one entry per pledge
     #[account(
         mut,
-        associated_token::mint = mint,
-        associated_token::authority = user
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &DeadlineBucket::day_for(deadline).to_le_bytes(),
+            &[deadline_shard]
+        ],
+        bump
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub deadline_bucket: UncheckedAccount<'info>,
+
+    /// Any of the user's token accounts for the mint, associated or not (required without a funder)
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account every refund must be paid into (omit to accept any of the recipient's)
+    #[account(
+        token::mint = mint,
+        constraint = refund_account.owner == user.key() @ ErrorCode::InvalidRefundAccount
+    )]
+    pub refund_account: Option<Account<'info, TokenAccount>>,
+
+    /// Third party staking on the user's behalf (omit when the user funds the pledge)
+    pub funder: Option<Signer<'info>>,
+
+    /// Funder's token account the stake is paid from (required with a funder)
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub funder_token_account: Option<Account<'info, TokenAccount>>,
 
     pub mint: Account<'info, Mint>,
 
+    /// Optional registered charity that receives this pledge's charity share
+    #[account(
+        constraint = charity.active @ ErrorCode::CharityNotActive
+    )]
+    pub charity: Option<Account<'info, CharityRecord>>,
+
+    /// Charity's fee ledger for this mint (required with a charity)
+    #[account(
+        constraint = charity_ledger.mint == mint.key() @ ErrorCode::InvalidCharityLedger
+    )]
+    pub charity_ledger: Option<Account<'info, CharityLedger>>,
+
+    /// Group challenge to join (the pledge must match its mint, stake and deadline)
+    #[account(mut)]
+    pub challenge: Option<Account<'info, Challenge>>,
+
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> CreatePledge<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pledge(
         &mut self,
         stake_amount: u64,
         deadline: i64,
-        created_at: i64,
+        beneficiary: Option<BeneficiaryTerms>,
+        metadata: GoalMetadata,
+        early_report: bool,
+        grace_period_seconds: Option<i64>,
+        default_outcome: DefaultOutcome,
+        refund_to_user: bool,
+        team: Option<TeamReporting>,
+        deadline_shard: u8,
         bumps: &CreatePledgeBumps,
     ) -> Result<()> {
-        let clock = Clock::get()?;
-        let current_time = clock.unix_timestamp;
-
-        // Validate created_at is within acceptable range of current time
-        let drift = (created_at - current_time).abs();
-        require!(drift <= MAX_CLOCK_DRIFT, ErrorCode::InvalidTimestamp);
+        let created_at = Clock::get()?.unix_timestamp;
 
         // Validate inputs
         require!(stake_amount > 0, ErrorCode::InvalidStakeAmount);
         require!(deadline > created_at, ErrorCode::InvalidDeadline);
+        require!(
+            deadline_shard < DEADLINE_BUCKET_SHARDS,
+            ErrorCode::InvalidDeadlineShard
+        );
+        if let Some(terms) = beneficiary {
+            require!(terms.wallet != self.user.key(), ErrorCode::InvalidBeneficiary);
+            self.config.check_beneficiary_bps(terms.bps)?;
+        }
+        let beneficiary_bps = beneficiary.map_or(0, |terms| terms.bps);
+        let beneficiary = beneficiary.map(|terms| terms.wallet);
+
+        // A chosen charity must already have a ledger for this mint to accrue into
+        if let Some(charity) = self.charity.as_ref() {
+            require!(
+                self.charity_ledger
+                    .as_ref()
+                    .is_some_and(|ledger| ledger.charity == charity.key()),
+                ErrorCode::InvalidCharityLedger
+            );
+        }
+
+        // User-chosen grace period must lie within the admin's bounds
+        if let Some(grace_period_seconds) = grace_period_seconds {
+            self.config.grace_bounds.check(grace_period_seconds)?;
+        }
+        default_outcome.validate(
+            self.config.max_default_percentage,
+            self.challenge.is_some(),
+        )?;
+
+        // Team pledges grow as contributors join, so they can't match a challenge's stake
+        if let Some(reporting) = team {
+            reporting.validate()?;
+            require!(self.challenge.is_none(), ErrorCode::InvalidTeam);
+        }
+
+        // Enforce exposure limits
+        let limits = self.config.limits;
+        limits.check_stake(stake_amount)?;
+        limits.check_duration(created_at, deadline)?;
 
-        // Transfer tokens from user to vault
+        // Challenge participants all stake the same terms - duels admit just their two parties
+        if let Some(challenge) = self.challenge.as_mut() {
+            require!(
+                challenge.mint == self.mint.key()
+                    && challenge.stake_amount == stake_amount
+                    && challenge.deadline == deadline,
+                ErrorCode::ChallengeTermsMismatch
+            );
+            if let Some(duel) = challenge.duel {
+                duel.check_join(
+                    challenge.creator,
+                    challenge.participants,
+                    self.user.key(),
+                    created_at,
+                )?;
+            }
+            challenge.join(created_at)?;
+        }
+        let challenge = self.challenge.as_ref().map(|challenge| challenge.key());
+
+        // Stake comes from the funder when one signs, otherwise from the user
+        let (source, authority) = match (&self.funder, &self.funder_token_account) {
+            (Some(funder), Some(funder_token_account)) => {
+                require!(
+                    funder.key() != self.user.key() && funder_token_account.owner == funder.key(),
+                    ErrorCode::InvalidFunder
+                );
+                (funder_token_account.to_account_info(), funder.to_account_info())
+            }
+            (None, None) => {
+                let user_token_account = self
+                    .user_token_account
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidTokenAccountOwner)?;
+                require_keys_eq!(
+                    user_token_account.owner,
+                    self.user.key(),
+                    ErrorCode::InvalidTokenAccountOwner
+                );
+                (user_token_account.to_account_info(), self.user.to_account_info())
+            }
+            _ => return err!(ErrorCode::InvalidFunder),
+        };
+        let funder = self.funder.as_ref().map(|funder| funder.key());
+        let refund_to_user = funder.is_some() && refund_to_user;
+        let rent_payer = (self.payer.key() != self.user.key()).then_some(self.payer.key());
+
+        // A recorded refund account only makes sense when refunds go to the user
+        let refund_account = self.refund_account.as_ref().map(|account| account.key());
+        require!(
+            refund_account.is_none() || funder.is_none() || refund_to_user,
+            ErrorCode::InvalidRefundAccount
+        );
+
+        // Transfer tokens to vault
         let transfer_ctx = CpiContext::new(
             self.token_program.to_account_info(),
             Transfer {
-                from: self.user_token_account.to_account_info(),
+                from: source,
                 to: self.vault.to_account_info(),
-                authority: self.user.to_account_info(),
+                authority,
             },
         );
         transfer(transfer_ctx, stake_amount)?;
 
+        // Claim the index seeding this pledge's PDA
+        if self.user_counter.user == Pubkey::default() {
+            self.user_counter.user = self.user.key();
+            self.user_counter.bump = bumps.user_counter;
+        }
+        let index = self.user_counter.claim_index()?;
+        self.user_counter.roll_window(created_at);
+        UserLimits::enforce_on(&self.user_limits, &self.user_counter, stake_amount, created_at)?;
+        self.user_counter.open_pledge(stake_amount, &limits)?;
+        Recovery::record_activity_on(&self.recovery, created_at)?;
+
         // Initialize pledge account
         self.pledge.set_inner(Pledge {
             user: self.user.key(),
@@ -97,15 +291,61 @@ impl<'info> CreatePledge<'info> {
             completion_percentage: None,
             reported_at: None,
             created_at,
+            charity: self.charity.as_ref().map(|charity| charity.key()),
+            beneficiary,
             bump: bumps.pledge,
             vault_bump: bumps.vault,
+            version: PLEDGE_VERSION,
+            index: Some(index),
+            metadata,
+            metadata_version: 0,
+            early_report,
+            late_penalty_bps: 0,
+            grace_period_seconds,
+            default_outcome,
+            funder,
+            refund_to_user,
+            challenge,
+            challenge_paid: false,
+            team: team.map(|reporting| TeamState::new(reporting, stake_amount)),
+            sponsorship: 0,
+            yield_position: None,
+            rent_payer,
+            refund_account,
+            recovery: None,
+            deadline_shard,
+            beneficiary_bps,
+            reserved: [0; PLEDGE_RESERVED_BYTES],
         });
 
+        // Index the pledge under its deadline day
+        DeadlineBucket::add_pledge_to(
+            &self.deadline_bucket,
+            &self.payer,
+            &self.system_program,
+            self.pledge.key(),
+            DeadlineBucket::day_for(deadline),
+            deadline_shard,
+            bumps.deadline_bucket,
+        )?;
+
         emit!(PledgeCreated {
             pledge: self.pledge.key(),
             user: self.user.key(),
             stake_amount,
             deadline,
+            charity: self.charity.as_ref().map(|charity| charity.key()),
+            beneficiary,
+            beneficiary_bps,
+            metadata,
+            grace_period_seconds,
+            default_outcome,
+            funder,
+            refund_to_user,
+            challenge,
+            team,
+            rent_payer,
+            refund_account,
         });
 
         Ok(())
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_SESSION_SECONDS, SESSION_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{SessionAuthority, SessionCreated};
+
+#[derive(Accounts)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// User's session - registering again replaces the previous key
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = SessionAuthority::INIT_SPACE,
+        seeds = [SESSION_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionAuthority>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSession<'info> {
+    pub fn create_session(
+        &mut self,
+        session_key: Pubkey,
+        expires_at: i64,
+        pledge: Option<Pubkey>,
+        bumps: &CreateSessionBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(session_key != self.user.key(), ErrorCode::InvalidSession);
+        require!(
+            expires_at > now
+                && expires_at
+                    <= now
+                        .checked_add(MAX_SESSION_SECONDS)
+                        .ok_or(ErrorCode::Overflow)?,
+            ErrorCode::InvalidSession
+        );
+
+        self.session.set_inner(SessionAuthority {
+            user: self.user.key(),
+            session_key,
+            pledge,
+            expires_at,
+            bump: bumps.session,
+        });
+
+        emit!(SessionCreated {
+            user: self.user.key(),
+            session_key,
+            pledge,
+            expires_at,
+        });
+
+        Ok(())
+    }
+}
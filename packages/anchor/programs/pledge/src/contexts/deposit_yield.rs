@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::{CONFIG_SEED, PLEDGE_SEED, VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeStatus, ProgramConfig, YieldDeposited, YieldPosition};
+use crate::utils::lending::{lending_deposit, LendingAccounts};
+
+#[derive(Accounts)]
+pub struct DepositYield<'info> {
+    /// Pledge owner - pays the adapter position's rent
+    #[account(
+        mut,
+        constraint = user.key() == pledge.user @ ErrorCode::NotPledgeOwner
+    )]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Must be the configured lending adapter
+    #[account(
+        executable,
+        constraint = Some(adapter_program.key()) == config.yield_policy.adapter @ ErrorCode::InvalidYieldAdapter
+    )]
+    pub adapter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Adapter reserve - validated by the adapter
+    pub reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Adapter reserve token account - validated by the adapter
+    #[account(mut)]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pledge's adapter position - created by the adapter
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositYield<'info> {
+    pub fn deposit_yield(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp < self.pledge.deadline,
+            ErrorCode::DeadlineAlreadyPassed
+        );
+
+        // Team and challenge pledges move vault funds outside settlement
+        require!(
+            self.pledge.team.is_none()
+                && self.pledge.challenge.is_none()
+                && self.pledge.yield_position.is_none(),
+            ErrorCode::YieldUnavailable
+        );
+
+        let principal = self.pledge.stake_amount;
+        let user_key = self.pledge.user;
+        let pledge_seed = self.pledge.seed();
+        let pledge_seeds = &[
+            PLEDGE_SEED,
+            user_key.as_ref(),
+            pledge_seed.as_ref(),
+            &[self.pledge.bump],
+        ];
+        let signer_seeds = &[&pledge_seeds[..]];
+
+        let balance_before = self.vault.amount;
+        lending_deposit(
+            &LendingAccounts {
+                adapter_program: self.adapter_program.to_account_info(),
+                reserve: self.reserve.to_account_info(),
+                reserve_vault: self.reserve_vault.to_account_info(),
+                position: self.position.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+            &self.pledge.to_account_info(),
+            &self.user.to_account_info(),
+            &self.vault.to_account_info(),
+            &self.system_program.to_account_info(),
+            principal,
+            signer_seeds,
+        )?;
+
+        // The adapter must have taken exactly the principal
+        self.vault.reload()?;
+        require!(
+            balance_before.checked_sub(self.vault.amount) == Some(principal),
+            ErrorCode::InvalidYieldAdapter
+        );
+
+        self.pledge.yield_position = Some(YieldPosition {
+            adapter: self.adapter_program.key(),
+            principal,
+            user_bps: self.config.yield_policy.user_bps,
+            withdrawn: false,
+            yield_amount: 0,
+        });
+
+        emit!(YieldDeposited {
+            pledge: self.pledge.key(),
+            adapter: self.adapter_program.key(),
+            principal,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::constants::{CHALLENGE_SEED, CONFIG_SEED, FEE_LEDGER_SEED, FEE_VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{
+    Challenge, ChallengeDistributed, ChallengePayout, FeeLedger, Pledge, PledgeStatus,
+    ProgramConfig,
+};
+use crate::utils::account_groups;
+
+#[derive(Accounts)]
+pub struct DistributeChallenge<'info> {
+    /// Any signer can trigger distribution - funds only go to completers' refund recipients
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            CHALLENGE_SEED,
+            challenge.creator.as_ref(),
+            &challenge.challenge_id.to_le_bytes()
+        ],
+        bump = challenge.bump,
+        constraint = !challenge.distributed @ ErrorCode::ChallengeAlreadyDistributed
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(
+        mut,
+        address = challenge.vault
+    )]
+    pub challenge_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Challenge creator - paid the challenge's rent and gets it back once the pool is paid out
+    #[account(mut, address = challenge.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Receives the pool when nobody completed
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, challenge.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, challenge.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DistributeChallenge<'info> {
+    pub fn distribute_challenge(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // Pool is final once joining has closed and every participant settled
+        require!(
+            now > self.challenge.deadline && self.challenge.is_settled(),
+            ErrorCode::ChallengeNotSettled
+        );
+        let pairs =
+            account_groups(remaining_accounts, 2).ok_or(ErrorCode::InvalidChallengePayout)?;
+
+        // Challenge PDA is the pool vault authority
+        let creator = self.challenge.creator;
+        let challenge_id = self.challenge.challenge_id.to_le_bytes();
+        let challenge_seeds = &[
+            CHALLENGE_SEED,
+            creator.as_ref(),
+            challenge_id.as_ref(),
+            &[self.challenge.bump],
+        ];
+        let signer_seeds = &[&challenge_seeds[..]];
+
+        // Nobody completed - the pool becomes protocol revenue
+        if self.challenge.completers == 0 {
+            let pool = self.challenge_vault.amount;
+            if pool > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.challenge_vault.to_account_info(),
+                        to: self.fee_vault.to_account_info(),
+                        authority: self.challenge.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                transfer(transfer_ctx, pool)?;
+            }
+            let revenue_amounts = self.config.split_revenue(pool)?;
+            self.config.accrue_revenue(&mut self.fee_ledger, &revenue_amounts)?;
+            self.challenge.distributed = true;
+
+            emit!(ChallengeDistributed {
+                challenge: self.challenge.key(),
+                completers: 0,
+                pool,
+            });
+            return self.close_challenge(signer_seeds);
+        }
+
+        let mut vault_balance = self.challenge_vault.amount;
+
+        for pair in pairs {
+            let (pledge_info, recipient_info) = (&pair[0], &pair[1]);
+
+            // Pledge must be an unpaid 100% completer of this challenge
+            let mut pledge = Account::<Pledge>::try_from(pledge_info)?;
+            require!(
+                pledge.challenge == Some(self.challenge.key())
+                    && pledge.status == PledgeStatus::Completed
+                    && pledge.completion_percentage == Some(100)
+                    && !pledge.challenge_paid,
+                ErrorCode::InvalidChallengePayout
+            );
+
+            // Payout goes where the pledge's refund went
+            let recipient_token_account = Account::<TokenAccount>::try_from(recipient_info)?;
+            require!(
+                recipient_token_account.owner == pledge.refund_recipient()
+                    && recipient_token_account.mint == self.challenge.mint,
+                ErrorCode::InvalidRecipientAccount
+            );
+
+            let amount = self.challenge.next_payout(vault_balance)?;
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.challenge_vault.to_account_info(),
+                        to: recipient_info.clone(),
+                        authority: self.challenge.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                transfer(transfer_ctx, amount)?;
+            }
+            vault_balance = vault_balance
+                .checked_sub(amount)
+                .ok_or(ErrorCode::Underflow)?;
+
+            // Remaining accounts aren't written back automatically
+            pledge.challenge_paid = true;
+            pledge.exit(&crate::ID)?;
+            self.challenge.paid_completers += 1;
+
+            emit!(ChallengePayout {
+                challenge: self.challenge.key(),
+                pledge: pledge.key(),
+                recipient: recipient_token_account.owner,
+                amount,
+            });
+        }
+
+        if self.challenge.paid_completers == self.challenge.completers {
+            self.challenge.distributed = true;
+
+            emit!(ChallengeDistributed {
+                challenge: self.challenge.key(),
+                completers: self.challenge.completers,
+                pool: self.challenge.pool,
+            });
+            self.close_challenge(signer_seeds)?;
+        }
+
+        Ok(())
+    }
+
+    /// Close the emptied pool vault and the challenge - every participant has settled, so
+    /// nothing references them any more - returning their rent to the creator
+    fn close_challenge(&mut self, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.challenge_vault.to_account_info(),
+                destination: self.creator.to_account_info(),
+                authority: self.challenge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_ctx)?;
+
+        self.challenge.close(self.creator.to_account_info())
+    }
+}
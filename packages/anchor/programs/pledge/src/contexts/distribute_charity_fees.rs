@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::constants::{CHARITY_LEDGER_SEED, CHARITY_SEED, CONFIG_SEED, FEE_VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{CharityFeesDistributed, CharityLedger, CharityRecord, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct DistributeCharityFees<'info> {
+    /// Any signer can trigger distribution - funds only go to the charity's payout wallet
+    pub caller: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [CHARITY_SEED, charity.name_hash.as_ref()],
+        bump = charity.bump
+    )]
+    pub charity: Account<'info, CharityRecord>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// Charity's undistributed fees in the fee vault's mint
+    #[account(
+        mut,
+        seeds = [
+            CHARITY_LEDGER_SEED,
+            charity_ledger.charity.as_ref(),
+            charity_ledger.mint.as_ref()
+        ],
+        bump = charity_ledger.bump,
+        constraint = charity_ledger.charity == charity.key()
+            && charity_ledger.mint == fee_vault.mint @ ErrorCode::InvalidCharityLedger
+    )]
+    pub charity_ledger: Account<'info, CharityLedger>,
+
+    #[account(
+        mut,
+        token::mint = fee_vault.mint,
+        token::authority = charity.payout
+    )]
+    pub payout_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DistributeCharityFees<'info> {
+    pub fn distribute_charity_fees(&mut self) -> Result<()> {
+        let amount = self.charity_ledger.accrued_fees;
+        require!(amount > 0, ErrorCode::NoFeesToDistribute);
+
+        // Config PDA is the fee vault authority
+        let config_seeds = &[CONFIG_SEED, &[self.config.bump]];
+        let signer_seeds = &[&config_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.fee_vault.to_account_info(),
+                to: self.payout_token_account.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, amount)?;
+
+        // Clear the ledger
+        self.charity_ledger.accrued_fees = 0;
+
+        emit!(CharityFeesDistributed {
+            charity: self.charity.key(),
+            mint: self.fee_vault.mint,
+            amount,
+        });
+
+        Ok(())
+    }
+}
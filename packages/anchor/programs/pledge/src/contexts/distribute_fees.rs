@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::constants::{CONFIG_SEED, FEE_LEDGER_SEED, FEE_VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{FeeLedger, FeesDistributed, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct DistributeFees<'info> {
+    /// Any signer can trigger distribution - funds only go to configured recipients
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// Undistributed balances for the fee vault's mint
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fee_ledger.mint.as_ref()],
+        bump = fee_ledger.bump,
+        constraint = fee_ledger.mint == fee_vault.mint @ ErrorCode::InvalidFeeLedger
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> DistributeFees<'info> {
+    pub fn distribute_fees(&mut self, recipient_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let share_count = self.config.revenue_shares.len();
+        require!(
+            self.fee_ledger.accrued[..share_count]
+                .iter()
+                .any(|accrued| *accrued > 0),
+            ErrorCode::NoFeesToDistribute
+        );
+        require!(
+            recipient_accounts.len() == self.config.revenue_shares.len(),
+            ErrorCode::InvalidRecipientAccount
+        );
+
+        // Config PDA is the fee vault authority
+        let config_bump = self.config.bump;
+        let config_seeds = &[CONFIG_SEED, &[config_bump]];
+        let signer_seeds = &[&config_seeds[..]];
+
+        let mut amounts = Vec::with_capacity(recipient_accounts.len());
+
+        for (index, recipient_info) in recipient_accounts.iter().enumerate() {
+            let share = self.config.revenue_shares[index];
+            let accrued = self.fee_ledger.accrued[index];
+
+            // Recipient account must be the share's token account for this mint
+            let recipient_token_account = Account::<TokenAccount>::try_from(recipient_info)?;
+            require_keys_eq!(
+                recipient_token_account.owner,
+                share.recipient,
+                ErrorCode::InvalidRecipientAccount
+            );
+            require_keys_eq!(
+                recipient_token_account.mint,
+                self.fee_vault.mint,
+                ErrorCode::InvalidRecipientAccount
+            );
+
+            // Transfer accrued share (if any)
+            if accrued > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.fee_vault.to_account_info(),
+                        to: recipient_info.clone(),
+                        authority: self.config.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                transfer(transfer_ctx, accrued)?;
+            }
+
+            amounts.push(accrued);
+
+            // Clear this mint's ledger and take it off the cross-mint total
+            self.fee_ledger.accrued[index] = 0;
+            self.config.revenue_shares[index].accrued = share
+                .accrued
+                .checked_sub(accrued)
+                .ok_or(ErrorCode::Underflow)?;
+        }
+
+        emit!(FeesDistributed {
+            mint: self.fee_vault.mint,
+            amounts,
+        });
+
+        Ok(())
+    }
+}
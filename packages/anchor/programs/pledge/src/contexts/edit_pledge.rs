@@ -1,19 +1,31 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
 
-use crate::constants::{CONFIG_SEED, PLEDGE_SEED, VAULT_SEED};
+use crate::constants::{
+    CONFIG_SEED, DEADLINE_BUCKET_SEED, FEE_LEDGER_SEED, FEE_VAULT_SEED, PLEDGE_SEED,
+    RECOVERY_SEED, USER_COUNTER_SEED, VAULT_SEED,
+};
 use crate::errors::ErrorCode;
-use crate::state::{Pledge, PledgeEdited, PledgeStatus, ProgramConfig};
-use crate::utils::fees::{calculate_edit_penalty, calculate_split};
+use crate::state::{
+    CharityLedger, CharityRecord, DeadlineBucket, FeeLedger, GoalMetadata, Pledge, PledgeEdited,
+    PledgeStatus, ProgramConfig, Recovery, UserCounter,
+};
+use crate::utils::fees::calculate_edit_penalty;
 
 #[derive(Accounts)]
+#[instruction(new_deadline: Option<i64>)]
 pub struct EditPledge<'info> {
     #[account(
         constraint = user.key() == pledge.user @ ErrorCode::NotPledgeOwner
     )]
     pub user: Signer<'info>,
 
+    /// Pays rent for a new deadline bucket - the user, or a backend sponsoring a user without SOL
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
+        mut,
         seeds = [CONFIG_SEED],
         bump = config.bump,
         constraint = !config.paused @ ErrorCode::ProgramPaused
@@ -22,7 +34,7 @@ pub struct EditPledge<'info> {
 
     #[account(
         mut,
-        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.created_at.to_le_bytes()],
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
         bump = pledge.bump,
         constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
     )]
@@ -37,25 +49,98 @@ pub struct EditPledge<'info> {
 
     #[account(
         mut,
-        token::mint = pledge.mint,
-        token::authority = config.treasury
+        seeds = [FEE_VAULT_SEED, pledge.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, pledge.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// CHECK: Deadline index bucket for the current deadline day - may not exist
+    /// for pledges created before deadline indexing
+    #[account(
+        mut,
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &DeadlineBucket::day_for(pledge.deadline).to_le_bytes(),
+            &[pledge.deadline_shard]
+        ],
+        bump
+    )]
+    pub deadline_bucket: UncheckedAccount<'info>,
+
+    /// CHECK: Deadline index bucket for the new deadline day, same shard - created on first use
+    /// and grown by one entry per pledge
+    #[account(
+        mut,
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &DeadlineBucket::day_for(new_deadline.unwrap_or(pledge.deadline)).to_le_bytes(),
+            &[pledge.deadline_shard]
+        ],
+        bump
+    )]
+    pub new_deadline_bucket: UncheckedAccount<'info>,
+
+    /// CHECK: User's pledge counter - may not exist for pledges created before it
+    #[account(
+        mut,
+        seeds = [USER_COUNTER_SEED, pledge.user.as_ref()],
+        bump
+    )]
+    pub user_counter: UncheckedAccount<'info>,
+
+    /// CHECK: User's recovery profile - may not exist; the owner signing counts as activity
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, pledge.user.as_ref()],
+        bump
     )]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub recovery: UncheckedAccount<'info>,
 
+    /// Registered charity chosen at creation (required when pledge.charity is set)
+    #[account(
+        constraint = Some(charity.key()) == pledge.charity @ ErrorCode::InvalidCharity
+    )]
+    pub charity: Option<Account<'info, CharityRecord>>,
+
+    /// Charity's fee ledger for the pledge's mint (required while the charity is active)
     #[account(
         mut,
-        token::mint = pledge.mint,
-        token::authority = config.charity
+        constraint = Some(charity_ledger.charity) == pledge.charity
+            && charity_ledger.mint == pledge.mint @ ErrorCode::InvalidCharityLedger
     )]
-    pub charity_token_account: Account<'info, TokenAccount>,
+    pub charity_ledger: Option<Account<'info, CharityLedger>>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> EditPledge<'info> {
-    pub fn edit_pledge(&mut self, new_deadline: Option<i64>) -> Result<()> {
+    pub fn edit_pledge(
+        &mut self,
+        new_deadline: Option<i64>,
+        new_metadata: Option<GoalMetadata>,
+        bumps: &EditPledgeBumps,
+    ) -> Result<()> {
         let clock = Clock::get()?;
 
+        // Challenge participants are held to the shared terms
+        require!(
+            self.pledge.challenge.is_none(),
+            ErrorCode::ChallengePledgeLocked
+        );
+        require!(self.pledge.team.is_none(), ErrorCode::TeamPledgeLocked);
+        require!(
+            self.pledge.yield_position.is_none(),
+            ErrorCode::YieldPositionOpen
+        );
+
         // Validate deadline hasn't passed
         require!(
             clock.unix_timestamp < self.pledge.deadline,
@@ -65,48 +150,40 @@ impl<'info> EditPledge<'info> {
         // Calculate 10% penalty from remaining stake
         let penalty = calculate_edit_penalty(self.pledge.stake_amount, self.config.edit_penalty_bps)?;
 
-        // Calculate treasury/charity split
-        let (treasury_amount, charity_amount) =
-            calculate_split(penalty, self.config.treasury_split_bps)?;
+        // Calculate revenue split
+        let revenue_amounts = self.config.split_revenue(penalty)?;
 
         // Create PDA signer seeds for pledge (which is the vault authority)
         let user_key = self.pledge.user;
-        let created_at_bytes = self.pledge.created_at.to_le_bytes();
+        let pledge_seed = self.pledge.seed();
         let pledge_seeds = &[
             PLEDGE_SEED,
             user_key.as_ref(),
-            created_at_bytes.as_ref(),
+            pledge_seed.as_ref(),
             &[self.pledge.bump],
         ];
         let signer_seeds = &[&pledge_seeds[..]];
 
-        // Transfer penalty to treasury
-        if treasury_amount > 0 {
-            let transfer_ctx = CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
-                    from: self.vault.to_account_info(),
-                    to: self.treasury_token_account.to_account_info(),
-                    authority: self.pledge.to_account_info(),
-                },
-                signer_seeds,
-            );
-            transfer(transfer_ctx, treasury_amount)?;
-        }
-
-        // Transfer penalty to charity
-        if charity_amount > 0 {
+        // Move penalty into the fee vault
+        if penalty > 0 {
             let transfer_ctx = CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 Transfer {
                     from: self.vault.to_account_info(),
-                    to: self.charity_token_account.to_account_info(),
+                    to: self.fee_vault.to_account_info(),
                     authority: self.pledge.to_account_info(),
                 },
                 signer_seeds,
             );
-            transfer(transfer_ctx, charity_amount)?;
+            transfer(transfer_ctx, penalty)?;
         }
+        let charity = self.config.accrue_pledge_fees(
+            &self.pledge,
+            &mut self.fee_ledger,
+            self.charity.as_ref(),
+            self.charity_ledger.as_mut(),
+            &revenue_amounts,
+        )?;
 
         // Update stake amount
         self.pledge.stake_amount = self
@@ -114,16 +191,49 @@ impl<'info> EditPledge<'info> {
             .stake_amount
             .checked_sub(penalty)
             .ok_or(ErrorCode::Underflow)?;
+        UserCounter::release_from(&self.user_counter, &self.pledge, penalty, penalty, false)?;
 
         // Update deadline if provided
         if let Some(deadline) = new_deadline {
             require!(deadline > clock.unix_timestamp, ErrorCode::InvalidDeadline);
+            self.config
+                .limits
+                .check_duration(self.pledge.created_at, deadline)?;
             self.pledge.deadline = deadline;
         }
 
+        // Update goal metadata if provided
+        if let Some(metadata) = new_metadata {
+            self.pledge.metadata = metadata;
+            self.pledge.metadata_version = self
+                .pledge
+                .metadata_version
+                .checked_add(1)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        // Move the pledge to its new deadline day's bucket
+        let pledge_key = self.pledge.key();
+        if self.deadline_bucket.key() != self.new_deadline_bucket.key() {
+            DeadlineBucket::remove_pledge_from(&self.deadline_bucket, &pledge_key)?;
+        }
+        DeadlineBucket::add_pledge_to(
+            &self.new_deadline_bucket,
+            &self.payer,
+            &self.system_program,
+            pledge_key,
+            DeadlineBucket::day_for(self.pledge.deadline),
+            self.pledge.deadline_shard,
+            bumps.new_deadline_bucket,
+        )?;
+        Recovery::record_activity_on(&self.recovery, clock.unix_timestamp)?;
+
         emit!(PledgeEdited {
             pledge: self.pledge.key(),
             penalty_paid: penalty,
+            charity,
+            metadata: self.pledge.metadata,
+            metadata_version: self.pledge.metadata_version,
         });
 
         Ok(())
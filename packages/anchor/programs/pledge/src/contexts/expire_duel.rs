@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::constants::{
+    CHALLENGE_SEED, DEADLINE_BUCKET_SEED, PLEDGE_SEED, USER_COUNTER_SEED, VAULT_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::state::{Challenge, DeadlineBucket, DuelExpired, Pledge, PledgeStatus, UserCounter};
+use crate::utils::settlement::rent_recipient;
+
+#[derive(Accounts)]
+pub struct ExpireDuel<'info> {
+    /// Any signer can expire an unaccepted duel - funds only go back to the creator's pledge
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            CHALLENGE_SEED,
+            challenge.creator.as_ref(),
+            &challenge.challenge_id.to_le_bytes()
+        ],
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// Creator's pledge - the duel's only participant
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive,
+        constraint = pledge.challenge == Some(challenge.key()) @ ErrorCode::InvalidChallenge
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the user who created the pledge, used for rent return
+    #[account(mut, address = pledge.user)]
+    pub user: AccountInfo<'info>,
+
+    /// CHECK: Sponsor that paid the pledge's rent (required when pledge.rent_payer is set)
+    #[account(mut)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+
+    /// Refund destination - the funder's account for funded pledges unless it opted to refund the user
+    #[account(
+        mut,
+        token::mint = pledge.mint,
+        token::authority = pledge.refund_recipient(),
+        constraint = pledge.accepts_refund_account(user_token_account.key())
+            @ ErrorCode::InvalidRefundAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Deadline index bucket for the pledge's deadline day
+    #[account(
+        mut,
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &DeadlineBucket::day_for(pledge.deadline).to_le_bytes(),
+            &[pledge.deadline_shard]
+        ],
+        bump
+    )]
+    pub deadline_bucket: UncheckedAccount<'info>,
+
+    /// CHECK: User's pledge counter
+    #[account(
+        mut,
+        seeds = [USER_COUNTER_SEED, pledge.user.as_ref()],
+        bump
+    )]
+    pub user_counter: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ExpireDuel<'info> {
+    pub fn expire_duel(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.challenge.is_unaccepted_duel(now),
+            ErrorCode::DuelNotExpired
+        );
+
+        // Create PDA signer seeds for pledge (which is the vault authority)
+        let user_key = self.pledge.user;
+        let pledge_seed = self.pledge.seed();
+        let pledge_seeds = &[
+            PLEDGE_SEED,
+            user_key.as_ref(),
+            pledge_seed.as_ref(),
+            &[self.pledge.bump],
+        ];
+        let signer_seeds = &[&pledge_seeds[..]];
+
+        // Full refund - nothing was wagered
+        let refund_amount = self.vault.amount;
+        if refund_amount > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                Transfer {
+                    from: self.vault.to_account_info(),
+                    to: self.user_token_account.to_account_info(),
+                    authority: self.pledge.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_ctx, refund_amount)?;
+        }
+
+        let rent_recipient = rent_recipient(
+            &self.pledge,
+            &self.user,
+            self.rent_payer.as_ref().map(|account| account.to_account_info()),
+        )?;
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: rent_recipient,
+                authority: self.pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_ctx)?;
+
+        DeadlineBucket::remove_pledge_from(&self.deadline_bucket, &self.pledge.key())?;
+        UserCounter::release_from(
+            &self.user_counter,
+            &self.pledge,
+            self.pledge.stake_amount,
+            0,
+            true,
+        )?;
+
+        self.pledge.status = PledgeStatus::Cancelled;
+
+        // Nothing left to settle or pay out
+        self.challenge.record_settlement(0, 0)?;
+        self.challenge.distributed = true;
+
+        emit!(DuelExpired {
+            challenge: self.challenge.key(),
+            pledge: self.pledge.key(),
+            refund_amount,
+        });
+
+        Ok(())
+    }
+}
@@ -1,8 +1,14 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::CONFIG_SEED;
+use crate::constants::{
+    CONFIG_RESERVED_BYTES, CONFIG_SEED, CONFIG_VERSION, DEFAULT_CHALLENGE_CUT_BPS,
+    DEFAULT_CRANK_REWARD_BPS, DEFAULT_MAX_BENEFICIARY_BPS, DEFAULT_MAX_DEFAULT_PERCENTAGE,
+};
 use crate::errors::ErrorCode;
-use crate::state::{ConfigInitialized, ProgramConfig};
+use crate::state::{
+    ConfigInitialized, GracePeriodBounds, LateReportPolicy, PledgeLimits, ProgramConfig,
+    RevenueShareParams, YieldPolicy,
+};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -27,39 +33,42 @@ pub struct Initialize<'info> {
 impl<'info> Initialize<'info> {
     pub fn initialize(
         &mut self,
-        treasury: Pubkey,
-        charity: Pubkey,
-        treasury_split_bps: u16,
+        revenue_shares: Vec<RevenueShareParams>,
         partial_fee_bps: u16,
         edit_penalty_bps: u16,
         grace_period_seconds: i64,
         bumps: &InitializeBumps,
     ) -> Result<()> {
         // Validate parameters
-        require!(
-            treasury_split_bps <= 10000,
-            ErrorCode::InvalidTreasurySplit
-        );
         require!(partial_fee_bps <= 1000, ErrorCode::InvalidFee);
         require!(edit_penalty_bps <= 1000, ErrorCode::InvalidFee);
 
         // Initialize config
         self.config.set_inner(ProgramConfig {
             admin: self.admin.key(),
-            treasury,
-            charity,
-            treasury_split_bps,
+            revenue_shares: Vec::new(),
             partial_fee_bps,
             edit_penalty_bps,
             grace_period_seconds,
             paused: false,
+            max_beneficiary_bps: DEFAULT_MAX_BENEFICIARY_BPS,
+            crank_reward_bps: DEFAULT_CRANK_REWARD_BPS,
             bump: bumps.config,
+            version: CONFIG_VERSION,
+            limits: PledgeLimits::default(),
+            allow_early_report: false,
+            late_report: LateReportPolicy::default(),
+            grace_bounds: GracePeriodBounds::default(),
+            challenge_cut_bps: DEFAULT_CHALLENGE_CUT_BPS,
+            yield_policy: YieldPolicy::default(),
+            max_default_percentage: DEFAULT_MAX_DEFAULT_PERCENTAGE,
+            reserved: [0; CONFIG_RESERVED_BYTES],
         });
+        self.config.set_revenue_shares(&revenue_shares)?;
 
         emit!(ConfigInitialized {
             admin: self.admin.key(),
-            treasury,
-            charity,
+            revenue_shares: self.config.revenue_shares.clone(),
         });
 
         Ok(())
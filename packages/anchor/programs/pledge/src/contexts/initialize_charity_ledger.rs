@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::constants::{CHARITY_LEDGER_SEED, CHARITY_SEED, FEE_LEDGER_SEED};
+use crate::state::{CharityLedger, CharityLedgerInitialized, CharityRecord, FeeLedger};
+
+#[derive(Accounts)]
+pub struct InitializeCharityLedger<'info> {
+    /// Anyone can open a charity's ledger for a mint - they pay its rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [CHARITY_SEED, charity.name_hash.as_ref()],
+        bump = charity.bump
+    )]
+    pub charity: Account<'info, CharityRecord>,
+
+    /// Mint's fee ledger - only mints with a fee vault can hold charity fees
+    #[account(
+        seeds = [FEE_LEDGER_SEED, mint.key().as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = CharityLedger::INIT_SPACE,
+        seeds = [CHARITY_LEDGER_SEED, charity.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub charity_ledger: Account<'info, CharityLedger>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeCharityLedger<'info> {
+    pub fn initialize_charity_ledger(
+        &mut self,
+        bumps: &InitializeCharityLedgerBumps,
+    ) -> Result<()> {
+        self.charity_ledger.set_inner(CharityLedger {
+            charity: self.charity.key(),
+            mint: self.mint.key(),
+            accrued_fees: 0,
+            bump: bumps.charity_ledger,
+        });
+
+        emit!(CharityLedgerInitialized {
+            charity: self.charity.key(),
+            mint: self.mint.key(),
+            charity_ledger: self.charity_ledger.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::constants::{CONFIG_SEED, FEE_LEDGER_SEED, FEE_VAULT_SEED, MAX_REVENUE_SHARES};
+use crate::errors::ErrorCode;
+use crate::state::{FeeLedger, FeeVaultInitialized, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == config.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Program-owned vault that collects fees and forfeitures for this mint
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = config,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// Undistributed balances of the fees held in this mint's vault
+    #[account(
+        init,
+        payer = admin,
+        space = FeeLedger::INIT_SPACE,
+        seeds = [FEE_LEDGER_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeFeeVault<'info> {
+    pub fn initialize_fee_vault(&mut self, bumps: &InitializeFeeVaultBumps) -> Result<()> {
+        self.fee_ledger.set_inner(FeeLedger {
+            mint: self.mint.key(),
+            accrued: [0; MAX_REVENUE_SHARES],
+            bump: bumps.fee_ledger,
+        });
+
+        emit!(FeeVaultInitialized {
+            mint: self.mint.key(),
+            fee_vault: self.fee_vault.key(),
+            fee_ledger: self.fee_ledger.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::RECOVERY_SEED;
+use crate::state::{Recovery, RecoveryInitiated};
+
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    /// Recovery address registered by the user
+    pub recovery_address: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, recovery.user.as_ref()],
+        bump = recovery.bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+}
+
+impl<'info> InitiateRecovery<'info> {
+    pub fn initiate_recovery(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let finalizes_at = self.recovery.initiate(self.recovery_address.key(), now)?;
+
+        emit!(RecoveryInitiated {
+            user: self.recovery.user,
+            recovery_address: self.recovery_address.key(),
+            finalizes_at,
+        });
+
+        Ok(())
+    }
+}
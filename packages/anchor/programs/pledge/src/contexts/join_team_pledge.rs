@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::constants::{
+    CONFIG_SEED, CONTRIBUTION_SEED, PLEDGE_SEED, USER_COUNTER_SEED, USER_LIMITS_SEED, VAULT_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::state::{
+    Contribution, Pledge, PledgeStatus, ProgramConfig, TeamContributed, UserCounter, UserLimits,
+};
+
+#[derive(Accounts)]
+pub struct JoinTeamPledge<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Contributor's record - its amount sets their share of the refund
+    #[account(
+        init,
+        payer = contributor,
+        space = Contribution::INIT_SPACE,
+        seeds = [CONTRIBUTION_SEED, pledge.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    /// Contributor's pledge counter - the contribution counts against their exposure limits
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = UserCounter::INIT_SPACE,
+        seeds = [USER_COUNTER_SEED, contributor.key().as_ref()],
+        bump
+    )]
+    pub contributor_counter: Account<'info, UserCounter>,
+
+    /// CHECK: Contributor's self-imposed limits - may not exist if they never set any
+    #[account(
+        mut,
+        seeds = [USER_LIMITS_SEED, contributor.key().as_ref()],
+        bump
+    )]
+    pub contributor_limits: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = pledge.mint,
+        token::authority = contributor
+    )]
+    pub contributor_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> JoinTeamPledge<'info> {
+    pub fn join_team_pledge(&mut self, amount: u64, bumps: &JoinTeamPledgeBumps) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(amount > 0, ErrorCode::InvalidStakeAmount);
+        require!(
+            clock.unix_timestamp < self.pledge.deadline,
+            ErrorCode::DeadlineAlreadyPassed
+        );
+        require!(
+            self.contributor.key() != self.pledge.user,
+            ErrorCode::InvalidContributor
+        );
+        let Some(team) = self.pledge.team.as_mut() else {
+            return err!(ErrorCode::InvalidTeam);
+        };
+
+        // Contributions count against the contributor's limits like a pledge of their own
+        let limits = self.config.limits;
+        limits.check_stake(amount)?;
+        if self.contributor_counter.user == Pubkey::default() {
+            self.contributor_counter.user = self.contributor.key();
+            self.contributor_counter.bump = bumps.contributor_counter;
+        }
+        self.contributor_counter.roll_window(clock.unix_timestamp);
+        UserLimits::enforce_on(
+            &self.contributor_limits,
+            &self.contributor_counter,
+            amount,
+            clock.unix_timestamp,
+        )?;
+        self.contributor_counter.open_pledge(amount, &limits)?;
+
+        // Transfer the contribution into the pledge vault
+        let transfer_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.contributor_token_account.to_account_info(),
+                to: self.vault.to_account_info(),
+                authority: self.contributor.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount)?;
+
+        team.contributors = team
+            .contributors
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        self.pledge.stake_amount = self
+            .pledge
+            .stake_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.contribution.set_inner(Contribution {
+            pledge: self.pledge.key(),
+            contributor: self.contributor.key(),
+            amount,
+            voted: false,
+            bump: bumps.contribution,
+        });
+
+        emit!(TeamContributed {
+            pledge: self.pledge.key(),
+            contributor: self.contributor.key(),
+            amount,
+            stake_amount: self.pledge.stake_amount,
+        });
+
+        Ok(())
+    }
+}
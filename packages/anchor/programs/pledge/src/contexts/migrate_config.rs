@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, CONFIG_VERSION};
+use crate::errors::ErrorCode;
+use crate::state::{ConfigMigrated, ProgramConfig};
+use crate::utils::migration::{grow_account, ProgramConfigV0};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Admin pays rent for the larger layout
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Config in the baseline layout - grown to the current size, then decoded
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfig<'info> {
+    pub fn migrate_config(&mut self) -> Result<()> {
+        // Only the baseline layout predates versioning - it is decoded field by field
+        require!(
+            self.config.data_len() == ProgramConfigV0::INIT_SPACE,
+            ErrorCode::AlreadyMigrated
+        );
+        grow_account(
+            &self.config,
+            &self.admin,
+            &self.system_program,
+            ProgramConfig::INIT_SPACE,
+        )?;
+
+        let mut data = self.config.try_borrow_mut_data()?;
+        let config = ProgramConfigV0::try_from_account_data(&data)?.upgrade()?;
+        require_keys_eq!(self.admin.key(), config.admin, ErrorCode::Unauthorized);
+        config.try_serialize(&mut &mut data[..])?;
+
+        emit!(ConfigMigrated {
+            from_version: 0,
+            to_version: CONFIG_VERSION,
+        });
+
+        Ok(())
+    }
+}
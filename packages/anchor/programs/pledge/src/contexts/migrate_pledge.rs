@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::PLEDGE_VERSION;
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeMigrated};
+use crate::utils::migration::{grow_account, PledgeV0};
+
+#[derive(Accounts)]
+pub struct MigratePledge<'info> {
+    /// Pays rent for the larger layout - anyone can migrate a pledge
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Pledge in the baseline layout - grown to the current size, then decoded
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub pledge: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigratePledge<'info> {
+    pub fn migrate_pledge(&mut self) -> Result<()> {
+        // Only the baseline layout predates versioning - it is decoded field by field
+        require!(
+            self.pledge.data_len() == PledgeV0::INIT_SPACE,
+            ErrorCode::AlreadyMigrated
+        );
+        grow_account(
+            &self.pledge,
+            &self.payer,
+            &self.system_program,
+            Pledge::INIT_SPACE,
+        )?;
+
+        let mut data = self.pledge.try_borrow_mut_data()?;
+        let pledge = PledgeV0::try_from_account_data(&data)?.upgrade();
+        pledge.try_serialize(&mut &mut data[..])?;
+
+        emit!(PledgeMigrated {
+            pledge: self.pledge.key(),
+            from_version: 0,
+            to_version: PLEDGE_VERSION,
+        });
+
+        Ok(())
+    }
+}
@@ -5,6 +5,34 @@ pub mod edit_pledge;
 pub mod report_completion;
 pub mod process_completion;
 pub mod process_expired;
+pub mod process_batch;
+pub mod initialize_fee_vault;
+pub mod distribute_fees;
+pub mod register_charity;
+pub mod update_charity;
+pub mod initialize_charity_ledger;
+pub mod distribute_charity_fees;
+pub mod close_deadline_bucket;
+pub mod migrate_pledge;
+pub mod migrate_config;
+pub mod set_user_limits;
+pub mod report_early_completion;
+pub mod create_challenge;
+pub mod distribute_challenge;
+pub mod join_team_pledge;
+pub mod vote_team_completion;
+pub mod claim_team_refund;
+pub mod expire_duel;
+pub mod add_sponsorship;
+pub mod deposit_yield;
+pub mod withdraw_yield;
+pub mod create_session;
+pub mod revoke_session;
+pub mod set_recovery;
+pub mod initiate_recovery;
+pub mod cancel_recovery;
+pub mod claim_recovery;
+pub mod settle_pledge;
 
 pub use initialize::*;
 pub use update_config::*;
@@ -13,3 +41,31 @@ pub use edit_pledge::*;
 pub use report_completion::*;
 pub use process_completion::*;
 pub use process_expired::*;
+pub use process_batch::*;
+pub use initialize_fee_vault::*;
+pub use distribute_fees::*;
+pub use register_charity::*;
+pub use update_charity::*;
+pub use initialize_charity_ledger::*;
+pub use distribute_charity_fees::*;
+pub use close_deadline_bucket::*;
+pub use migrate_pledge::*;
+pub use migrate_config::*;
+pub use set_user_limits::*;
+pub use report_early_completion::*;
+pub use create_challenge::*;
+pub use distribute_challenge::*;
+pub use join_team_pledge::*;
+pub use vote_team_completion::*;
+pub use claim_team_refund::*;
+pub use expire_duel::*;
+pub use add_sponsorship::*;
+pub use deposit_yield::*;
+pub use withdraw_yield::*;
+pub use create_session::*;
+pub use revoke_session::*;
+pub use set_recovery::*;
+pub use initiate_recovery::*;
+pub use cancel_recovery::*;
+pub use claim_recovery::*;
+pub use settle_pledge::*;
@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::{
+    BATCH_ACCOUNTS_PER_PLEDGE, CONFIG_SEED, DEADLINE_BUCKET_SEED, FEE_LEDGER_SEED,
+    FEE_VAULT_SEED, RECOVERY_SEED, USER_COUNTER_SEED, VAULT_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::state::{
+    BatchProcessed, DeadlineBucket, FeeLedger, Pledge, PledgeStatus, ProgramConfig, Recovery,
+};
+use crate::utils::account_groups;
+use crate::utils::settlement::{settle_pledge, Settlement, SettlementAccounts};
+
+#[derive(Accounts)]
+pub struct ProcessBatch<'info> {
+    /// Any signer can be the crank - permissionless
+    pub crank: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Fee vault for the batch's mint - pledges in other mints are skipped
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, fee_vault.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, fee_vault.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// Crank's token account for settlement rewards (omit to forgo the rewards)
+    #[account(
+        mut,
+        token::mint = fee_vault.mint,
+        token::authority = crank
+    )]
+    pub crank_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ProcessBatch<'info> {
+    pub fn process_batch(
+        &mut self,
+        pledge_accounts: &'info [AccountInfo<'info>],
+        completion_percentages: Vec<u8>,
+    ) -> Result<()> {
+        let groups = account_groups(pledge_accounts, BATCH_ACCOUNTS_PER_PLEDGE)
+            .ok_or(ErrorCode::InvalidBatch)?;
+        require!(
+            groups.len() == completion_percentages.len(),
+            ErrorCode::InvalidBatch
+        );
+
+        let clock = Clock::get()?;
+
+        let mut processed: u32 = 0;
+        let mut skipped = Vec::new();
+        let mut total_refunded: u64 = 0;
+        let mut total_to_fee_vault: u64 = 0;
+        let mut total_crank_rewards: u64 = 0;
+
+        for (group, completion_percentage) in groups.zip(completion_percentages) {
+            match self.settle_group(group, completion_percentage, clock.unix_timestamp)? {
+                Some(settlement) => {
+                    processed = processed.checked_add(1).ok_or(ErrorCode::Overflow)?;
+                    total_refunded = total_refunded
+                        .checked_add(settlement.refund_amount)
+                        .ok_or(ErrorCode::Overflow)?;
+                    total_to_fee_vault = total_to_fee_vault
+                        .checked_add(settlement.fee_vault_amount)
+                        .ok_or(ErrorCode::Overflow)?;
+                    total_crank_rewards = total_crank_rewards
+                        .checked_add(settlement.crank_reward)
+                        .ok_or(ErrorCode::Overflow)?;
+                }
+                None => skipped.push(group[0].key()),
+            }
+        }
+
+        emit!(BatchProcessed {
+            mint: self.fee_vault.mint,
+            processed,
+            skipped,
+            total_refunded,
+            total_to_fee_vault,
+            total_crank_rewards,
+        });
+
+        Ok(())
+    }
+
+    /// Settle one (pledge, vault, user, user_token_account, deadline_bucket, user_counter,
+    /// recovery, rent_payer) group
+    /// Returns None when the pledge is not eligible for batch settlement
+    fn settle_group(
+        &mut self,
+        group: &'info [AccountInfo<'info>],
+        completion_percentage: u8,
+        now: i64,
+    ) -> Result<Option<Settlement>> {
+        let [pledge_info, vault_info, user_info, user_token_info, bucket_info, counter_info, recovery_info, rent_payer_info] =
+            group
+        else {
+            return Ok(None);
+        };
+
+        if !group.iter().all(|account| account.is_writable) {
+            return Ok(None);
+        }
+
+        let Ok(mut pledge) = Account::<Pledge>::try_from(pledge_info) else {
+            return Ok(None);
+        };
+
+        // Pledges routing fees to a registered charity or custom beneficiary, pooling into a
+        // challenge or paying a sponsor bonus need extra accounts, team pledges keep their
+        // vault open for contributor claims and lent stakes must be withdrawn first - leave
+        // them for process_completion / process_expired
+        if pledge.mint != self.fee_vault.mint
+            || pledge.charity.is_some()
+            || pledge.beneficiary.is_some()
+            || pledge.challenge.is_some()
+            || pledge.team.is_some()
+            || pledge.sponsorship > 0
+            || pledge.yield_position.is_some_and(|position| !position.withdrawn)
+        {
+            return Ok(None);
+        }
+
+        // Reported pledges settle at the reported percentage, expired ones by their default outcome
+        let completion_percentage = match pledge.status {
+            PledgeStatus::Reported => match pledge.completion_percentage {
+                Some(reported) => reported,
+                None => return Ok(None),
+            },
+            PledgeStatus::Active => {
+                if now <= self.config.report_cutoff(&pledge)? || completion_percentage > 100 {
+                    return Ok(None);
+                }
+                pledge.default_outcome.resolve(completion_percentage)
+            }
+            _ => return Ok(None),
+        };
+
+        // Vault must be the pledge's vault PDA
+        let Ok(vault_key) = Pubkey::create_program_address(
+            &[VAULT_SEED, pledge.key().as_ref(), &[pledge.vault_bump]],
+            &crate::ID,
+        ) else {
+            return Ok(None);
+        };
+        if vault_info.key() != vault_key || user_info.key() != pledge.user {
+            return Ok(None);
+        }
+
+        // Vault rent goes back to whoever paid it - the user's slot is repeated when nobody sponsored it
+        if rent_payer_info.key() != pledge.rent_payer.unwrap_or(pledge.user) {
+            return Ok(None);
+        }
+        let rent_payer = pledge.rent_payer.map(|_| rent_payer_info.clone());
+
+        // Bucket must be the deadline index PDA for the pledge's deadline day and shard
+        let (bucket_key, _) = Pubkey::find_program_address(
+            &[
+                DEADLINE_BUCKET_SEED,
+                &DeadlineBucket::day_for(pledge.deadline).to_le_bytes(),
+                &[pledge.deadline_shard],
+            ],
+            &crate::ID,
+        );
+        if bucket_info.key() != bucket_key {
+            return Ok(None);
+        }
+
+        // Counter must be the pledge owner's counter PDA
+        let (counter_key, _) =
+            Pubkey::find_program_address(&[USER_COUNTER_SEED, pledge.user.as_ref()], &crate::ID);
+        if counter_info.key() != counter_key {
+            return Ok(None);
+        }
+
+        // Recovery must be the pledge owner's recovery profile PDA, and a pending recovery
+        // holds the settlement until it resolves
+        let (recovery_key, _) =
+            Pubkey::find_program_address(&[RECOVERY_SEED, pledge.user.as_ref()], &crate::ID);
+        if recovery_info.key() != recovery_key || Recovery::holds_settlement_on(recovery_info, &pledge)? {
+            return Ok(None);
+        }
+
+        // Refund must go to the refund recipient's token account for this mint (the recorded one, if any)
+        let Ok(user_token_account) = Account::<TokenAccount>::try_from(user_token_info) else {
+            return Ok(None);
+        };
+        if user_token_account.mint != pledge.mint
+            || user_token_account.owner != pledge.refund_recipient()
+            || !pledge.accepts_refund_account(user_token_info.key())
+        {
+            return Ok(None);
+        }
+
+        let settlement = settle_pledge(
+            &mut self.config,
+            &mut pledge,
+            completion_percentage,
+            SettlementAccounts {
+                vault: vault_info.clone(),
+                user: user_info.clone(),
+                rent_payer,
+                user_token_account: user_token_info.clone(),
+                fee_vault: self.fee_vault.to_account_info(),
+                fee_ledger: &mut self.fee_ledger,
+                deadline_bucket: bucket_info.clone(),
+                user_counter: counter_info.clone(),
+                recovery: recovery_info.clone(),
+                charity: None,
+                charity_ledger: None,
+                beneficiary_token_account: None,
+                crank_token_account: self
+                    .crank_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                token_program: self.token_program.to_account_info(),
+                challenge: None,
+                challenge_vault: None,
+                sponsorship: None,
+                sponsor_vault: None,
+                sponsor: None,
+                sponsor_token_account: None,
+                bonus_token_account: None,
+            },
+        )?;
+
+        // Remaining accounts aren't written back automatically
+        pledge.exit(&crate::ID)?;
+
+        Ok(Some(settlement))
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CHARITY_SEED, CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{CharityRecord, CharityRegistered, ProgramConfig};
+
+#[derive(Accounts)]
+#[instruction(name_hash: [u8; 32])]
+pub struct RegisterCharity<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == config.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = CharityRecord::INIT_SPACE,
+        seeds = [CHARITY_SEED, name_hash.as_ref()],
+        bump
+    )]
+    pub charity: Account<'info, CharityRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterCharity<'info> {
+    pub fn register_charity(
+        &mut self,
+        name_hash: [u8; 32],
+        payout: Pubkey,
+        bumps: &RegisterCharityBumps,
+    ) -> Result<()> {
+        self.charity.set_inner(CharityRecord {
+            name_hash,
+            payout,
+            active: true,
+            bump: bumps.charity,
+        });
+
+        emit!(CharityRegistered {
+            charity: self.charity.key(),
+            name_hash,
+            payout,
+        });
+
+        Ok(())
+    }
+}
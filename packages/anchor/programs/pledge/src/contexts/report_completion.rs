@@ -1,14 +1,15 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{CONFIG_SEED, PLEDGE_SEED};
+use crate::constants::{CONFIG_SEED, PLEDGE_SEED, RECOVERY_SEED, SESSION_SEED};
 use crate::errors::ErrorCode;
-use crate::state::{CompletionReported, Pledge, PledgeStatus, ProgramConfig};
+use crate::state::{
+    CompletionReported, Pledge, PledgeStatus, ProgramConfig, Recovery, SessionAuthority,
+};
 
 #[derive(Accounts)]
 pub struct ReportCompletion<'info> {
-    #[account(
-        constraint = user.key() == pledge.user @ ErrorCode::NotPledgeOwner
-    )]
+    /// Pledge owner (or the recovery address that took the pledge over), or its session key
+    /// when `session` is passed
     pub user: Signer<'info>,
 
     #[account(
@@ -19,48 +20,85 @@ pub struct ReportCompletion<'info> {
 
     #[account(
         mut,
-        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.created_at.to_le_bytes()],
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
         bump = pledge.bump,
         constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
     )]
     pub pledge: Account<'info, Pledge>,
+
+    /// Owner's session (required when a session key signs)
+    #[account(
+        seeds = [SESSION_SEED, pledge.user.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Option<Account<'info, SessionAuthority>>,
+
+    /// CHECK: Owner's recovery profile - may not exist; the owner signing counts as activity
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, pledge.user.as_ref()],
+        bump
+    )]
+    pub recovery: UncheckedAccount<'info>,
 }
 
 impl<'info> ReportCompletion<'info> {
     pub fn report_completion(&mut self, completion_percentage: u8) -> Result<()> {
         let clock = Clock::get()?;
 
+        // Owner's wallet, its recovery address or a live session key it registered
+        if self.user.key() != self.pledge.user && self.pledge.recovery != Some(self.user.key()) {
+            let session = self.session.as_ref().ok_or(ErrorCode::NotPledgeOwner)?;
+            session.check(
+                self.user.key(),
+                self.pledge.user,
+                self.pledge.key(),
+                clock.unix_timestamp,
+            )?;
+        }
+
         // Validate completion percentage
         require!(
             completion_percentage <= 100,
             ErrorCode::InvalidCompletionPercentage
         );
 
+        // Quorum teams report through vote_team_completion
+        require!(
+            self.pledge.captain_reports(),
+            ErrorCode::InvalidTeamReport
+        );
+
         // Validate current_time >= deadline (can only report after deadline)
         require!(
             clock.unix_timestamp >= self.pledge.deadline,
             ErrorCode::DeadlineNotPassed
         );
 
-        // Validate current_time <= deadline + grace_period
-        let grace_period_end = self
-            .pledge
-            .deadline
-            .checked_add(self.config.grace_period_seconds)
-            .ok_or(ErrorCode::Overflow)?;
+        // Validate current_time <= deadline + grace_period + late window
         require!(
-            clock.unix_timestamp <= grace_period_end,
+            clock.unix_timestamp <= self.config.report_cutoff(&self.pledge)?,
             ErrorCode::GracePeriodEnded
         );
 
+        // Reports after the grace period lose a growing share of the refund
+        let late_penalty_bps = self
+            .config
+            .late_penalty_bps(&self.pledge, clock.unix_timestamp)?;
+
         // Update pledge
         self.pledge.completion_percentage = Some(completion_percentage);
         self.pledge.reported_at = Some(clock.unix_timestamp);
+        self.pledge.late_penalty_bps = late_penalty_bps;
         self.pledge.status = PledgeStatus::Reported;
+        if self.user.key() == self.pledge.user {
+            Recovery::record_activity_on(&self.recovery, clock.unix_timestamp)?;
+        }
 
         emit!(CompletionReported {
             pledge: self.pledge.key(),
             completion_percentage,
+            late_penalty_bps,
         });
 
         Ok(())
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::contexts::settle_pledge::*;
+use crate::errors::ErrorCode;
+use crate::state::{CompletionReported, PledgeStatus, Recovery};
+
+#[derive(Accounts)]
+pub struct ReportEarlyCompletion<'info> {
+    pub settlement: SettlePledge<'info>,
+
+    #[account(
+        mut,
+        constraint = user.key() == settlement.pledge.user @ ErrorCode::NotPledgeOwner
+    )]
+    pub user: Signer<'info>,
+}
+
+impl<'info> ReportEarlyCompletion<'info> {
+    pub fn report_early_completion(&mut self) -> Result<()> {
+        require!(
+            self.settlement.pledge.status == PledgeStatus::Active,
+            ErrorCode::PledgeNotActive
+        );
+        let clock = Clock::get()?;
+
+        // Opt-in per pledge, or enabled for everyone in config
+        require!(
+            self.settlement.pledge.early_report || self.settlement.config.allow_early_report,
+            ErrorCode::EarlyReportNotAllowed
+        );
+
+        // Quorum teams report through vote_team_completion
+        require!(
+            self.settlement.pledge.captain_reports(),
+            ErrorCode::InvalidTeamReport
+        );
+
+        // After the deadline the regular report_completion flow applies
+        require!(
+            clock.unix_timestamp < self.settlement.pledge.deadline,
+            ErrorCode::DeadlineAlreadyPassed
+        );
+
+        // Owner-signed - counts as activity, cancelling a recovery that isn't final yet
+        Recovery::record_activity_on(&self.settlement.recovery, clock.unix_timestamp)?;
+
+        self.settlement.pledge.reported_at = Some(clock.unix_timestamp);
+
+        emit!(CompletionReported {
+            pledge: self.settlement.pledge.key(),
+            completion_percentage: 100,
+            late_penalty_bps: 0,
+        });
+
+        // Settle now - the pledge leaves Active, so it can no longer be edited
+        self.settlement.settle(100, None)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SESSION_SEED;
+use crate::state::{SessionAuthority, SessionRevoked};
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [SESSION_SEED, user.key().as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionAuthority>,
+}
+
+impl<'info> RevokeSession<'info> {
+    pub fn revoke_session(&mut self) -> Result<()> {
+        emit!(SessionRevoked {
+            user: self.user.key(),
+            session_key: self.session.session_key,
+        });
+
+        Ok(())
+    }
+}
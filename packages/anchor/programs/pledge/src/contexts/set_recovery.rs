@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::RECOVERY_SEED;
+use crate::errors::ErrorCode;
+use crate::state::{Recovery, RecoveryAddressSet};
+
+#[derive(Accounts)]
+pub struct SetRecovery<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// User's recovery profile - setting it again replaces the address and resets a
+    /// pending or final recovery
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Recovery::INIT_SPACE,
+        seeds = [RECOVERY_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub recovery: Account<'info, Recovery>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetRecovery<'info> {
+    pub fn set_recovery(
+        &mut self,
+        recovery_address: Pubkey,
+        bumps: &SetRecoveryBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(recovery_address != self.user.key(), ErrorCode::InvalidRecovery);
+
+        self.recovery.set_inner(Recovery {
+            user: self.user.key(),
+            recovery_address,
+            last_active: now,
+            initiated_at: None,
+            bump: bumps.recovery,
+        });
+
+        emit!(RecoveryAddressSet {
+            user: self.user.key(),
+            recovery_address,
+        });
+
+        Ok(())
+    }
+}
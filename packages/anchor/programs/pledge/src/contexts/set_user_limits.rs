@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::USER_LIMITS_SEED;
+use crate::state::{UserLimits, UserLimitsUpdated};
+
+#[derive(Accounts)]
+pub struct SetUserLimits<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserLimits::INIT_SPACE,
+        seeds = [USER_LIMITS_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_limits: Account<'info, UserLimits>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetUserLimits<'info> {
+    pub fn set_user_limits(
+        &mut self,
+        max_monthly_stake: Option<u64>,
+        max_single_stake: Option<u64>,
+        max_monthly_forfeiture: Option<u64>,
+        exclude_for_seconds: Option<i64>,
+        bumps: &SetUserLimitsBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        if self.user_limits.user == Pubkey::default() {
+            self.user_limits.user = self.user.key();
+            self.user_limits.bump = bumps.user_limits;
+        }
+
+        self.user_limits.request_caps(
+            max_monthly_stake,
+            max_single_stake,
+            max_monthly_forfeiture,
+            now,
+        )?;
+
+        if let Some(seconds) = exclude_for_seconds {
+            self.user_limits.exclude_for(seconds, now)?;
+        }
+
+        let pending = self.user_limits.pending_effective_at != 0;
+        emit!(UserLimitsUpdated {
+            user: self.user.key(),
+            caps: self.user_limits.caps,
+            pending_caps: pending.then_some(self.user_limits.pending_caps),
+            pending_effective_at: pending.then_some(self.user_limits.pending_effective_at),
+            excluded_until: self.user_limits.excluded_until,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::{
+    CONFIG_SEED, DEADLINE_BUCKET_SEED, FEE_LEDGER_SEED, FEE_VAULT_SEED, PLEDGE_SEED,
+    RECOVERY_SEED, SPONSORSHIP_SEED, USER_COUNTER_SEED, VAULT_SEED,
+};
+use crate::errors::ErrorCode;
+use crate::state::{
+    Challenge, CharityLedger, CharityRecord, DeadlineBucket, FeeLedger, Pledge, ProgramConfig,
+    Sponsorship,
+};
+use crate::utils::settlement::{settle_pledge, Settlement, SettlementAccounts};
+
+/// Accounts shared by every instruction that settles a single pledge - the status the
+/// pledge must be in is checked by the instruction itself
+#[derive(Accounts)]
+pub struct SettlePledge<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: This is the user who created the pledge, used for rent return
+    #[account(mut, address = pledge.user)]
+    pub user: AccountInfo<'info>,
+
+    /// CHECK: Sponsor that paid the pledge's rent (required when pledge.rent_payer is set)
+    #[account(mut)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+
+    /// Refund destination - the funder's account for funded pledges unless it opted to refund the user
+    #[account(
+        mut,
+        token::mint = pledge.mint,
+        token::authority = pledge.refund_recipient(),
+        constraint = pledge.accepts_refund_account(user_token_account.key())
+            @ ErrorCode::InvalidRefundAccount
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, pledge.mint.as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [FEE_LEDGER_SEED, pledge.mint.as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+
+    /// CHECK: Deadline index bucket for the pledge's deadline day - may not exist
+    /// for pledges created before deadline indexing
+    #[account(
+        mut,
+        seeds = [
+            DEADLINE_BUCKET_SEED,
+            &DeadlineBucket::day_for(pledge.deadline).to_le_bytes(),
+            &[pledge.deadline_shard]
+        ],
+        bump
+    )]
+    pub deadline_bucket: UncheckedAccount<'info>,
+
+    /// CHECK: User's pledge counter - may not exist for pledges created before it
+    #[account(
+        mut,
+        seeds = [USER_COUNTER_SEED, pledge.user.as_ref()],
+        bump
+    )]
+    pub user_counter: UncheckedAccount<'info>,
+
+    /// CHECK: Owner's recovery profile - may not exist. A pending recovery holds the
+    /// settlement until the owner cancels it or the recovery address claims the pledge
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, pledge.user.as_ref()],
+        bump
+    )]
+    pub recovery: UncheckedAccount<'info>,
+
+    /// Registered charity chosen at creation (required when pledge.charity is set)
+    #[account(
+        constraint = Some(charity.key()) == pledge.charity @ ErrorCode::InvalidCharity
+    )]
+    pub charity: Option<Account<'info, CharityRecord>>,
+
+    /// Charity's fee ledger for the pledge's mint (required while the charity is active)
+    #[account(
+        mut,
+        constraint = Some(charity_ledger.charity) == pledge.charity
+            && charity_ledger.mint == pledge.mint @ ErrorCode::InvalidCharityLedger
+    )]
+    pub charity_ledger: Option<Account<'info, CharityLedger>>,
+
+    /// Forfeiture beneficiary's token account (required when pledge.beneficiary is set)
+    #[account(
+        mut,
+        token::mint = pledge.mint,
+        constraint = Some(beneficiary_token_account.owner) == pledge.beneficiary @ ErrorCode::InvalidBeneficiary
+    )]
+    pub beneficiary_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Group challenge the pledge joined (required when pledge.challenge is set)
+    #[account(
+        mut,
+        constraint = Some(challenge.key()) == pledge.challenge @ ErrorCode::InvalidChallenge
+    )]
+    pub challenge: Option<Account<'info, Challenge>>,
+
+    /// Challenge pool vault receiving the pooled forfeiture
+    #[account(mut)]
+    pub challenge_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Pledge's sponsorship (required when pledge.sponsorship is set)
+    #[account(
+        mut,
+        seeds = [SPONSORSHIP_SEED, pledge.key().as_ref()],
+        bump = sponsorship.bump
+    )]
+    pub sponsorship: Option<Account<'info, Sponsorship>>,
+
+    /// Sponsor bonus vault
+    #[account(mut)]
+    pub sponsor_vault: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Sponsor wallet, verified against the sponsorship - receives the sponsor accounts' rent
+    #[account(mut)]
+    pub sponsor: Option<UncheckedAccount<'info>>,
+
+    /// Sponsor's token account for the unearned part of the bonus
+    #[account(mut)]
+    pub sponsor_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Goal owner's token account for the earned bonus (required when refunds go to a funder)
+    #[account(mut)]
+    pub bonus_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SettlePledge<'info> {
+    /// Settle the pledge at the given completion percentage, paying the settlement
+    /// reward to `crank_token_account` when one is given
+    pub fn settle(
+        &mut self,
+        completion_percentage: u8,
+        crank_token_account: Option<AccountInfo<'info>>,
+    ) -> Result<Settlement> {
+        settle_pledge(
+            &mut self.config,
+            &mut self.pledge,
+            completion_percentage,
+            SettlementAccounts {
+                vault: self.vault.to_account_info(),
+                user: self.user.to_account_info(),
+                rent_payer: self.rent_payer.as_ref().map(|account| account.to_account_info()),
+                user_token_account: self.user_token_account.to_account_info(),
+                fee_vault: self.fee_vault.to_account_info(),
+                fee_ledger: &mut self.fee_ledger,
+                deadline_bucket: self.deadline_bucket.to_account_info(),
+                user_counter: self.user_counter.to_account_info(),
+                recovery: self.recovery.to_account_info(),
+                charity: self.charity.as_ref(),
+                charity_ledger: self.charity_ledger.as_mut(),
+                beneficiary_token_account: self
+                    .beneficiary_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                crank_token_account,
+                token_program: self.token_program.to_account_info(),
+                challenge: self.challenge.as_mut(),
+                challenge_vault: self
+                    .challenge_vault
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                sponsorship: self.sponsorship.as_mut(),
+                sponsor_vault: self
+                    .sponsor_vault
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                sponsor: self.sponsor.as_ref().map(|account| account.to_account_info()),
+                sponsor_token_account: self
+                    .sponsor_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                bonus_token_account: self
+                    .bonus_token_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+            },
+        )
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CHARITY_SEED, CONFIG_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{CharityRecord, CharityUpdated, ProgramConfig};
+
+#[derive(Accounts)]
+pub struct UpdateCharity<'info> {
+    #[account(
+        constraint = admin.key() == config.admin @ ErrorCode::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [CHARITY_SEED, charity.name_hash.as_ref()],
+        bump = charity.bump
+    )]
+    pub charity: Account<'info, CharityRecord>,
+}
+
+impl<'info> UpdateCharity<'info> {
+    pub fn update_charity(&mut self, new_payout: Option<Pubkey>, active: Option<bool>) -> Result<()> {
+        if let Some(payout) = new_payout {
+            self.charity.payout = payout;
+        }
+
+        if let Some(active_state) = active {
+            self.charity.active = active_state;
+        }
+
+        emit!(CharityUpdated {
+            charity: self.charity.key(),
+            payout: self.charity.payout,
+            active: self.charity.active,
+        });
+
+        Ok(())
+    }
+}
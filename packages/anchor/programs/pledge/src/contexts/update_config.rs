@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::CONFIG_SEED;
+use crate::constants::{BPS_DENOMINATOR, CONFIG_SEED, MAX_CRANK_REWARD_BPS};
 use crate::errors::ErrorCode;
-use crate::state::{ConfigUpdated, ProgramConfig};
+use crate::state::{
+    ConfigUpdated, GracePeriodBounds, LateReportPolicy, PledgeLimits, ProgramConfig, RevenueShare,
+    RevenueShareParams, YieldPolicy,
+};
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
@@ -20,42 +23,32 @@ pub struct UpdateConfig<'info> {
 }
 
 impl<'info> UpdateConfig<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         &mut self,
-        new_treasury: Option<Pubkey>,
-        new_charity: Option<Pubkey>,
-        new_treasury_split_bps: Option<u16>,
+        new_revenue_shares: Option<Vec<RevenueShareParams>>,
         new_partial_fee_bps: Option<u16>,
         new_edit_penalty_bps: Option<u16>,
         new_grace_period_seconds: Option<i64>,
         paused: Option<bool>,
+        new_max_beneficiary_bps: Option<u16>,
+        new_crank_reward_bps: Option<u16>,
+        new_limits: Option<PledgeLimits>,
+        allow_early_report: Option<bool>,
+        new_late_report: Option<LateReportPolicy>,
+        new_grace_bounds: Option<GracePeriodBounds>,
+        new_challenge_cut_bps: Option<u16>,
+        new_yield_policy: Option<YieldPolicy>,
+        new_max_default_percentage: Option<u8>,
     ) -> Result<()> {
-        if let Some(treasury) = new_treasury {
+        if let Some(shares) = new_revenue_shares {
+            let old_value = format_revenue_shares(&self.config.revenue_shares);
+            self.config.set_revenue_shares(&shares)?;
             emit!(ConfigUpdated {
-                field: "treasury".to_string(),
-                old_value: self.config.treasury.to_string(),
-                new_value: treasury.to_string(),
+                field: "revenue_shares".to_string(),
+                old_value,
+                new_value: format_revenue_shares(&self.config.revenue_shares),
             });
-            self.config.treasury = treasury;
-        }
-
-        if let Some(charity) = new_charity {
-            emit!(ConfigUpdated {
-                field: "charity".to_string(),
-                old_value: self.config.charity.to_string(),
-                new_value: charity.to_string(),
-            });
-            self.config.charity = charity;
-        }
-
-        if let Some(split_bps) = new_treasury_split_bps {
-            require!(split_bps <= 10000, ErrorCode::InvalidTreasurySplit);
-            emit!(ConfigUpdated {
-                field: "treasury_split_bps".to_string(),
-                old_value: self.config.treasury_split_bps.to_string(),
-                new_value: split_bps.to_string(),
-            });
-            self.config.treasury_split_bps = split_bps;
         }
 
         if let Some(fee_bps) = new_partial_fee_bps {
@@ -96,6 +89,144 @@ impl<'info> UpdateConfig<'info> {
             self.config.paused = pause_state;
         }
 
+        if let Some(beneficiary_bps) = new_max_beneficiary_bps {
+            require!(beneficiary_bps <= 10000, ErrorCode::InvalidBeneficiaryCap);
+            emit!(ConfigUpdated {
+                field: "max_beneficiary_bps".to_string(),
+                old_value: self.config.max_beneficiary_bps.to_string(),
+                new_value: beneficiary_bps.to_string(),
+            });
+            self.config.max_beneficiary_bps = beneficiary_bps;
+        }
+
+        if let Some(reward_bps) = new_crank_reward_bps {
+            require!(reward_bps <= MAX_CRANK_REWARD_BPS, ErrorCode::InvalidCrankReward);
+            emit!(ConfigUpdated {
+                field: "crank_reward_bps".to_string(),
+                old_value: self.config.crank_reward_bps.to_string(),
+                new_value: reward_bps.to_string(),
+            });
+            self.config.crank_reward_bps = reward_bps;
+        }
+
+        if let Some(limits) = new_limits {
+            limits.validate()?;
+            emit!(ConfigUpdated {
+                field: "limits".to_string(),
+                old_value: format_limits(&self.config.limits),
+                new_value: format_limits(&limits),
+            });
+            self.config.limits = limits;
+        }
+
+        if let Some(allow) = allow_early_report {
+            emit!(ConfigUpdated {
+                field: "allow_early_report".to_string(),
+                old_value: self.config.allow_early_report.to_string(),
+                new_value: allow.to_string(),
+            });
+            self.config.allow_early_report = allow;
+        }
+
+        if let Some(late_report) = new_late_report {
+            late_report.validate()?;
+            emit!(ConfigUpdated {
+                field: "late_report".to_string(),
+                old_value: format_late_report(&self.config.late_report),
+                new_value: format_late_report(&late_report),
+            });
+            self.config.late_report = late_report;
+        }
+
+        if let Some(grace_bounds) = new_grace_bounds {
+            grace_bounds.validate()?;
+            emit!(ConfigUpdated {
+                field: "grace_bounds".to_string(),
+                old_value: format_grace_bounds(&self.config.grace_bounds),
+                new_value: format_grace_bounds(&grace_bounds),
+            });
+            self.config.grace_bounds = grace_bounds;
+        }
+
+        if let Some(cut_bps) = new_challenge_cut_bps {
+            require!(
+                cut_bps as u64 <= BPS_DENOMINATOR,
+                ErrorCode::InvalidChallengeCut
+            );
+            emit!(ConfigUpdated {
+                field: "challenge_cut_bps".to_string(),
+                old_value: self.config.challenge_cut_bps.to_string(),
+                new_value: cut_bps.to_string(),
+            });
+            self.config.challenge_cut_bps = cut_bps;
+        }
+
+        if let Some(yield_policy) = new_yield_policy {
+            yield_policy.validate()?;
+            emit!(ConfigUpdated {
+                field: "yield_policy".to_string(),
+                old_value: format_yield_policy(&self.config.yield_policy),
+                new_value: format_yield_policy(&yield_policy),
+            });
+            self.config.yield_policy = yield_policy;
+        }
+
+        if let Some(max_percentage) = new_max_default_percentage {
+            require!(max_percentage <= 100, ErrorCode::InvalidCompletionPercentage);
+            emit!(ConfigUpdated {
+                field: "max_default_percentage".to_string(),
+                old_value: self.config.max_default_percentage.to_string(),
+                new_value: max_percentage.to_string(),
+            });
+            self.config.max_default_percentage = max_percentage;
+        }
+
         Ok(())
     }
 }
+
+/// Render revenue shares as "recipient:bps" pairs for ConfigUpdated events
+fn format_revenue_shares(shares: &[RevenueShare]) -> String {
+    shares
+        .iter()
+        .map(|share| format!("{}:{}", share.recipient, share.bps))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Render limits as "name=value" pairs for ConfigUpdated events
+fn format_limits(limits: &PledgeLimits) -> String {
+    format!(
+        "min_stake={},max_stake={},min_duration_seconds={},max_duration_seconds={},max_active_pledges={},max_active_stake={}",
+        limits.min_stake,
+        limits.max_stake,
+        limits.min_duration_seconds,
+        limits.max_duration_seconds,
+        limits.max_active_pledges,
+        limits.max_active_stake
+    )
+}
+
+/// Render the late report policy as "name=value" pairs for ConfigUpdated events
+fn format_late_report(late_report: &LateReportPolicy) -> String {
+    format!(
+        "window_seconds={},max_penalty_bps={}",
+        late_report.window_seconds, late_report.max_penalty_bps
+    )
+}
+
+/// Render the grace period bounds as "name=value" pairs for ConfigUpdated events
+fn format_grace_bounds(grace_bounds: &GracePeriodBounds) -> String {
+    format!(
+        "min_seconds={},max_seconds={}",
+        grace_bounds.min_seconds, grace_bounds.max_seconds
+    )
+}
+
+/// Render the yield policy as "name=value" pairs for ConfigUpdated events (adapter "none" when off)
+fn format_yield_policy(yield_policy: &YieldPolicy) -> String {
+    let adapter = yield_policy
+        .adapter
+        .map_or_else(|| "none".to_string(), |adapter| adapter.to_string());
+    format!("adapter={},user_bps={}", adapter, yield_policy.user_bps)
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, CONTRIBUTION_SEED, PLEDGE_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{
+    CompletionReported, Contribution, Pledge, PledgeStatus, ProgramConfig, TeamVoteCast,
+};
+
+#[derive(Accounts)]
+pub struct VoteTeamCompletion<'info> {
+    pub contributor: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = pledge.status == PledgeStatus::Active @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [CONTRIBUTION_SEED, pledge.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        constraint = !contribution.voted @ ErrorCode::AlreadyVoted
+    )]
+    pub contribution: Account<'info, Contribution>,
+}
+
+impl<'info> VoteTeamCompletion<'info> {
+    pub fn vote_team_completion(&mut self, completion_percentage: u8) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            completion_percentage <= 100,
+            ErrorCode::InvalidCompletionPercentage
+        );
+
+        // Votes share report_completion's window
+        require!(
+            clock.unix_timestamp >= self.pledge.deadline,
+            ErrorCode::DeadlineNotPassed
+        );
+        require!(
+            clock.unix_timestamp <= self.config.report_cutoff(&self.pledge)?,
+            ErrorCode::GracePeriodEnded
+        );
+
+        let Some(team) = self.pledge.team.as_mut() else {
+            return err!(ErrorCode::InvalidTeamReport);
+        };
+        let reported = team.record_vote(completion_percentage)?;
+        let votes = team.votes;
+        self.contribution.voted = true;
+
+        emit!(TeamVoteCast {
+            pledge: self.pledge.key(),
+            contributor: self.contributor.key(),
+            completion_percentage,
+            votes,
+        });
+
+        // Quorum reached - report the lowest voted percentage
+        if let Some(completion_percentage) = reported {
+            let late_penalty_bps = self
+                .config
+                .late_penalty_bps(&self.pledge, clock.unix_timestamp)?;
+
+            self.pledge.completion_percentage = Some(completion_percentage);
+            self.pledge.reported_at = Some(clock.unix_timestamp);
+            self.pledge.late_penalty_bps = late_penalty_bps;
+            self.pledge.status = PledgeStatus::Reported;
+
+            emit!(CompletionReported {
+                pledge: self.pledge.key(),
+                completion_percentage,
+                late_penalty_bps,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::constants::{PLEDGE_SEED, VAULT_SEED};
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeStatus, YieldWithdrawn};
+use crate::utils::lending::{lending_withdraw, LendingAccounts};
+
+#[derive(Accounts)]
+pub struct WithdrawYield<'info> {
+    /// Pledge owner at any time, anyone once the deadline has passed
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PLEDGE_SEED, pledge.user.as_ref(), &pledge.seed()],
+        bump = pledge.bump,
+        constraint = matches!(pledge.status, PledgeStatus::Active | PledgeStatus::Reported)
+            @ ErrorCode::PledgeNotActive
+    )]
+    pub pledge: Account<'info, Pledge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, pledge.key().as_ref()],
+        bump = pledge.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Pledge owner - gets back the position rent it paid
+    #[account(mut, address = pledge.user)]
+    pub user: UncheckedAccount<'info>,
+
+    /// CHECK: Must be the adapter the stake was deposited with
+    #[account(
+        executable,
+        constraint = pledge.yield_position.map(|position| position.adapter)
+            == Some(adapter_program.key()) @ ErrorCode::YieldNotDeposited
+    )]
+    pub adapter_program: UncheckedAccount<'info>,
+
+    /// CHECK: Adapter reserve - validated by the adapter
+    pub reserve: UncheckedAccount<'info>,
+
+    /// CHECK: Adapter reserve token account - validated by the adapter
+    #[account(mut)]
+    pub reserve_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Pledge's adapter position - validated and closed by the adapter
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawYield<'info> {
+    pub fn withdraw_yield(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+
+        require!(
+            self.caller.key() == self.pledge.user || clock.unix_timestamp >= self.pledge.deadline,
+            ErrorCode::NotPledgeOwner
+        );
+        let mut position = self
+            .pledge
+            .yield_position
+            .ok_or(ErrorCode::YieldNotDeposited)?;
+        require!(!position.withdrawn, ErrorCode::YieldNotDeposited);
+
+        let user_key = self.pledge.user;
+        let pledge_seed = self.pledge.seed();
+        let pledge_seeds = &[
+            PLEDGE_SEED,
+            user_key.as_ref(),
+            pledge_seed.as_ref(),
+            &[self.pledge.bump],
+        ];
+        let signer_seeds = &[&pledge_seeds[..]];
+
+        let balance_before = self.vault.amount;
+        lending_withdraw(
+            &LendingAccounts {
+                adapter_program: self.adapter_program.to_account_info(),
+                reserve: self.reserve.to_account_info(),
+                reserve_vault: self.reserve_vault.to_account_info(),
+                position: self.position.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+            &self.pledge.to_account_info(),
+            &self.user.to_account_info(),
+            &self.vault.to_account_info(),
+            signer_seeds,
+        )?;
+
+        // Yield is whatever came back above the principal - a shortfall reverts
+        self.vault.reload()?;
+        let returned = self
+            .vault
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::Underflow)?;
+        position.record_withdrawal(returned)?;
+        self.pledge.yield_position = Some(position);
+
+        let (user_yield, _) = position.split_yield()?;
+        emit!(YieldWithdrawn {
+            pledge: self.pledge.key(),
+            adapter: position.adapter,
+            principal: position.principal,
+            yield_amount: position.yield_amount,
+            user_yield,
+        });
+
+        Ok(())
+    }
+}
@@ -13,14 +13,14 @@ pub enum ErrorCode {
     #[msg("Program is paused")]
     ProgramPaused,
 
-    #[msg("Invalid treasury split - must be <= 10000 bps")]
-    InvalidTreasurySplit,
+    #[msg("Invalid revenue shares - 1 to 5 shares summing to 10000 bps, at most one charity slot")]
+    InvalidRevenueShares,
 
     #[msg("Invalid fee - must be <= 1000 bps (10%)")]
     InvalidFee,
 
     // Pledge errors
-    #[msg("Invalid timestamp - exceeds clock drift tolerance")]
+    #[msg("Invalid timestamp")]
     InvalidTimestamp,
 
     #[msg("Invalid deadline - must be in the future")]
@@ -41,10 +41,10 @@ pub enum ErrorCode {
     #[msg("Deadline has already passed")]
     DeadlineAlreadyPassed,
 
-    #[msg("Grace period has not ended")]
+    #[msg("Grace period and late report window have not ended")]
     GracePeriodNotEnded,
 
-    #[msg("Grace period has ended - cannot report")]
+    #[msg("Late report window has ended - cannot report")]
     GracePeriodEnded,
 
     #[msg("Invalid completion percentage - must be 0-100")]
@@ -63,4 +63,239 @@ pub enum ErrorCode {
 
     #[msg("Invalid token account owner")]
     InvalidTokenAccountOwner,
+
+    // Fee vault errors
+    #[msg("No accrued fees to distribute")]
+    NoFeesToDistribute,
+
+    #[msg("Accrued fees must be distributed before changing revenue shares")]
+    UndistributedFees,
+
+    #[msg("Recipient token account does not match revenue share")]
+    InvalidRecipientAccount,
+
+    #[msg("Fee ledger does not match the fee vault's mint")]
+    InvalidFeeLedger,
+
+    // Charity errors
+    #[msg("Charity is not active")]
+    CharityNotActive,
+
+    #[msg("Charity account does not match pledge")]
+    InvalidCharity,
+
+    #[msg("Charity ledger does not match the charity or the fee vault's mint")]
+    InvalidCharityLedger,
+
+    // Beneficiary errors
+    #[msg("Invalid beneficiary - must not be the pledge owner and must match the pledge")]
+    InvalidBeneficiary,
+
+    #[msg("Invalid beneficiary cap - must be <= 10000 bps")]
+    InvalidBeneficiaryCap,
+
+    #[msg("Invalid beneficiary share - must be above 0 and within the admin cap")]
+    InvalidBeneficiaryShare,
+
+    #[msg("Custom beneficiaries need a charity revenue share to take their cut from")]
+    BeneficiaryUnavailable,
+
+    // Crank errors
+    #[msg("Invalid crank reward - must be <= 500 bps (5%)")]
+    InvalidCrankReward,
+
+    // Deadline index errors
+    #[msg("Deadline bucket shard out of range")]
+    InvalidDeadlineShard,
+
+    #[msg("Deadline bucket still holds pledges")]
+    DeadlineBucketNotEmpty,
+
+    // Migration errors
+    #[msg("Account is already on the current layout version")]
+    AlreadyMigrated,
+
+    // Batch errors
+    #[msg("Invalid batch - remaining accounts must be (pledge, vault, user, user_token_account, deadline_bucket, user_counter) groups with one completion percentage each")]
+    InvalidBatch,
+
+    // Exposure limit errors
+    #[msg("Invalid limits - minimums must not exceed maximums")]
+    InvalidLimits,
+
+    #[msg("Stake is below the minimum per pledge")]
+    StakeBelowMinimum,
+
+    #[msg("Stake is above the maximum per pledge")]
+    StakeAboveMaximum,
+
+    #[msg("Pledge duration is below the minimum")]
+    DurationTooShort,
+
+    #[msg("Pledge duration is above the maximum")]
+    DurationTooLong,
+
+    #[msg("User has reached the maximum number of active pledges")]
+    TooManyActivePledges,
+
+    #[msg("User's total active stake would exceed the maximum")]
+    ActiveStakeLimitExceeded,
+
+    // Self-imposed limit errors
+    #[msg("User is self-excluded from creating pledges")]
+    SelfExcluded,
+
+    #[msg("Stake exceeds the user's own single-pledge limit")]
+    SingleStakeLimitExceeded,
+
+    #[msg("Stake exceeds the user's own monthly stake limit")]
+    MonthlyStakeLimitExceeded,
+
+    #[msg("Stake at risk exceeds the user's own monthly forfeiture limit")]
+    ForfeitureLimitExceeded,
+
+    // Early report errors
+    #[msg("Early reporting is not enabled for this pledge")]
+    EarlyReportNotAllowed,
+
+    // Late report errors
+    #[msg("Invalid late report policy - window must be >= 0 and penalty <= 10000 bps")]
+    InvalidLateReportPolicy,
+
+    // Grace period / default outcome errors
+    #[msg("Invalid grace period bounds - must be >= 0 with min <= max")]
+    InvalidGracePeriodBounds,
+
+    #[msg("Grace period is outside the allowed bounds")]
+    InvalidGracePeriod,
+
+    #[msg("Default outcome above the admin cap, or not Forfeit on a challenge pledge")]
+    InvalidDefaultOutcome,
+
+    // Funder errors
+    #[msg("Invalid funder - must not be the pledge owner and must own the funding token account")]
+    InvalidFunder,
+
+    // Challenge errors
+    #[msg("Invalid challenge cut - must be <= 10000 bps")]
+    InvalidChallengeCut,
+
+    #[msg("Challenge account does not match pledge")]
+    InvalidChallenge,
+
+    #[msg("Pledge must match the challenge's mint, stake and deadline")]
+    ChallengeTermsMismatch,
+
+    #[msg("Challenge pledges cannot be edited")]
+    ChallengePledgeLocked,
+
+    #[msg("Invalid join cutoff - joining must close after creation and no later than halfway to the deadline")]
+    InvalidJoinCutoff,
+
+    #[msg("Joining this challenge has closed")]
+    ChallengeJoinClosed,
+
+    #[msg("Challenge still has unsettled participants")]
+    ChallengeNotSettled,
+
+    #[msg("Challenge pool has already been distributed")]
+    ChallengeAlreadyDistributed,
+
+    #[msg("Invalid challenge payout - remaining accounts must be (pledge, recipient token account) pairs of unpaid completers")]
+    InvalidChallengePayout,
+
+    // Team errors
+    #[msg("Invalid team - quorum must be at least 1 and team pledges cannot join a challenge")]
+    InvalidTeam,
+
+    #[msg("Invalid contributor - must not be the captain and must match the contribution")]
+    InvalidContributor,
+
+    #[msg("Team pledge must be reported the way it was created - by its captain or a contributor quorum")]
+    InvalidTeamReport,
+
+    #[msg("Contributor has already voted")]
+    AlreadyVoted,
+
+    #[msg("Team pledges cannot be edited")]
+    TeamPledgeLocked,
+
+    #[msg("Pledge has not been settled")]
+    PledgeNotSettled,
+
+    // Duel errors
+    #[msg("Invalid duel - opponent must not be the creator and acceptance must close by the join cutoff")]
+    InvalidDuel,
+
+    #[msg("Only the duel creator, then the invited opponent before acceptance closes, may join")]
+    DuelNotJoinable,
+
+    #[msg("Duel was accepted or can still be accepted")]
+    DuelNotExpired,
+
+    // Sponsorship errors
+    #[msg("Invalid sponsor - must not be the pledge owner and must match the pledge's sponsorship")]
+    InvalidSponsor,
+
+    #[msg("Challenge and duel pledges can't be sponsored")]
+    SponsorshipUnavailable,
+
+    #[msg("Sponsor bonus must be paid into a token account of the pledge's owner or its recovery address")]
+    InvalidBonusAccount,
+
+    // Yield errors
+    #[msg("Invalid yield policy - user share must be at most 10000 bps")]
+    InvalidYieldPolicy,
+
+    #[msg("Yield mode is off or the adapter is not the configured one")]
+    InvalidYieldAdapter,
+
+    #[msg("Only solo, unchallenged pledges can lend their stake, once")]
+    YieldUnavailable,
+
+    #[msg("Pledge has no funds with the lending adapter")]
+    YieldNotDeposited,
+
+    #[msg("Pledge funds are still with the lending adapter - withdraw them first")]
+    YieldPositionOpen,
+
+    #[msg("Lending adapter returned less than the deposited principal")]
+    YieldShortfall,
+
+    // Session errors
+    #[msg("Invalid session - key, user or pledge doesn't match, or expiry is out of range")]
+    InvalidSession,
+
+    #[msg("Session key has expired")]
+    SessionExpired,
+
+    // Rent payer errors
+    #[msg("Rent payer account must match the wallet that paid the pledge's rent")]
+    InvalidRentPayer,
+
+    // Refund account errors
+    #[msg("Refund account must belong to the user, and settlements must pay into the recorded one")]
+    InvalidRefundAccount,
+
+    // Recovery errors
+    #[msg("Invalid recovery - signer isn't the registered recovery address, or it is the user's own wallet")]
+    InvalidRecovery,
+
+    #[msg("Owner was active too recently to start a recovery")]
+    RecoveryNotDue,
+
+    #[msg("A recovery is pending for this user")]
+    RecoveryPending,
+
+    #[msg("No recovery is pending")]
+    RecoveryNotPending,
+
+    #[msg("Recovery challenge window is still open")]
+    RecoveryNotFinal,
+
+    #[msg("Recovery is final - the challenge window has closed")]
+    RecoveryFinalized,
+
+    #[msg("Pledge was created after the recovery became final")]
+    PledgeAfterRecovery,
 }
@@ -18,17 +18,13 @@ pub mod pledge {
     /// Initialize the program config (admin only, one-time setup)
     pub fn initialize(
         ctx: Context<Initialize>,
-        treasury: Pubkey,
-        charity: Pubkey,
-        treasury_split_bps: u16,
+        revenue_shares: Vec<RevenueShareParams>,
         partial_fee_bps: u16,
         edit_penalty_bps: u16,
         grace_period_seconds: i64,
     ) -> Result<()> {
         ctx.accounts.initialize(
-            treasury,
-            charity,
-            treasury_split_bps,
+            revenue_shares,
             partial_fee_bps,
             edit_penalty_bps,
             grace_period_seconds,
@@ -37,44 +33,88 @@ pub mod pledge {
     }
 
     /// Update program config parameters (admin only)
+    #[allow(clippy::too_many_arguments)]
     pub fn update_config(
         ctx: Context<UpdateConfig>,
-        new_treasury: Option<Pubkey>,
-        new_charity: Option<Pubkey>,
-        new_treasury_split_bps: Option<u16>,
+        new_revenue_shares: Option<Vec<RevenueShareParams>>,
         new_partial_fee_bps: Option<u16>,
         new_edit_penalty_bps: Option<u16>,
         new_grace_period_seconds: Option<i64>,
         paused: Option<bool>,
+        new_max_beneficiary_bps: Option<u16>,
+        new_crank_reward_bps: Option<u16>,
+        new_limits: Option<PledgeLimits>,
+        allow_early_report: Option<bool>,
+        new_late_report: Option<LateReportPolicy>,
+        new_grace_bounds: Option<GracePeriodBounds>,
+        new_challenge_cut_bps: Option<u16>,
+        new_yield_policy: Option<YieldPolicy>,
+        new_max_default_percentage: Option<u8>,
     ) -> Result<()> {
         ctx.accounts.update_config(
-            new_treasury,
-            new_charity,
-            new_treasury_split_bps,
+            new_revenue_shares,
             new_partial_fee_bps,
             new_edit_penalty_bps,
             new_grace_period_seconds,
             paused,
+            new_max_beneficiary_bps,
+            new_crank_reward_bps,
+            new_limits,
+            allow_early_report,
+            new_late_report,
+            new_grace_bounds,
+            new_challenge_cut_bps,
+            new_yield_policy,
+            new_max_default_percentage,
         )
     }
 
     /// Create a new pledge and stake tokens
+    /// Optionally picks its own grace period (within config bounds) and the outcome
+    /// applied if it is never reported; a co-signing funder may pay the stake and
+    /// receives refunds unless `refund_to_user` is set; `team` opens it to contributors;
+    /// a `payer` other than the user sponsors the rent and gets it back at settlement;
+    /// `deadline_shard` picks which of the deadline day's index buckets lists it
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pledge(
         ctx: Context<CreatePledge>,
         stake_amount: u64,
         deadline: i64,
-        created_at: i64,
+        beneficiary: Option<BeneficiaryTerms>,
+        metadata: GoalMetadata,
+        early_report: bool,
+        grace_period_seconds: Option<i64>,
+        default_outcome: DefaultOutcome,
+        refund_to_user: bool,
+        team: Option<TeamReporting>,
+        deadline_shard: u8,
     ) -> Result<()> {
-        ctx.accounts
-            .create_pledge(stake_amount, deadline, created_at, &ctx.bumps)
+        ctx.accounts.create_pledge(
+            stake_amount,
+            deadline,
+            beneficiary,
+            metadata,
+            early_report,
+            grace_period_seconds,
+            default_outcome,
+            refund_to_user,
+            team,
+            deadline_shard,
+            &ctx.bumps,
+        )
     }
 
-    /// Edit an existing pledge (10% penalty)
-    pub fn edit_pledge(ctx: Context<EditPledge>, new_deadline: Option<i64>) -> Result<()> {
-        ctx.accounts.edit_pledge(new_deadline)
+    /// Edit an existing pledge's deadline and/or goal metadata (10% penalty)
+    pub fn edit_pledge(
+        ctx: Context<EditPledge>,
+        new_deadline: Option<i64>,
+        new_metadata: Option<GoalMetadata>,
+    ) -> Result<()> {
+        ctx.accounts.edit_pledge(new_deadline, new_metadata, &ctx.bumps)
     }
 
-    /// Report completion percentage (user calls within grace period)
+    /// Report completion percentage (user calls within grace period; later reports
+    /// until the late window closes pay an escalating penalty)
     pub fn report_completion(ctx: Context<ReportCompletion>, completion_percentage: u8) -> Result<()> {
         ctx.accounts.report_completion(completion_percentage)
     }
@@ -85,7 +125,204 @@ pub mod pledge {
     }
 
     /// Process an expired pledge that wasn't reported (permissionless crank)
+    /// Settles by the pledge's default outcome; the crank's percentage is used for Attested
     pub fn process_expired(ctx: Context<ProcessExpired>, completion_percentage: u8) -> Result<()> {
         ctx.accounts.process_expired(completion_percentage)
     }
+
+    /// Settle many reported or expired pledges of one mint (permissionless crank)
+    /// Remaining accounts: (pledge, vault, user, user_token_account, deadline_bucket, user_counter) per pledge;
+    /// ineligible pledges are skipped and listed in the BatchProcessed event
+    pub fn process_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessBatch<'info>>,
+        completion_percentages: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts
+            .process_batch(ctx.remaining_accounts, completion_percentages)
+    }
+
+    /// Create the fee vault and its fee ledger for a mint (admin only, once per mint)
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        ctx.accounts.initialize_fee_vault(&ctx.bumps)
+    }
+
+    /// Pay accrued fees from the fee vault to each revenue share (permissionless)
+    /// Remaining accounts: one recipient token account per revenue share, in config order
+    pub fn distribute_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeFees<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.distribute_fees(ctx.remaining_accounts)
+    }
+
+    /// Add a charity to the registry (admin only)
+    pub fn register_charity(
+        ctx: Context<RegisterCharity>,
+        name_hash: [u8; 32],
+        payout: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.register_charity(name_hash, payout, &ctx.bumps)
+    }
+
+    /// Update a registered charity's payout wallet or active flag (admin only)
+    pub fn update_charity(
+        ctx: Context<UpdateCharity>,
+        new_payout: Option<Pubkey>,
+        active: Option<bool>,
+    ) -> Result<()> {
+        ctx.accounts.update_charity(new_payout, active)
+    }
+
+    /// Open a charity's fee ledger for a mint with a fee vault (permissionless, once per pair)
+    pub fn initialize_charity_ledger(ctx: Context<InitializeCharityLedger>) -> Result<()> {
+        ctx.accounts.initialize_charity_ledger(&ctx.bumps)
+    }
+
+    /// Pay a charity's fees accrued in one mint to its payout wallet (permissionless)
+    pub fn distribute_charity_fees(ctx: Context<DistributeCharityFees>) -> Result<()> {
+        ctx.accounts.distribute_charity_fees()
+    }
+
+    /// Reclaim an empty deadline bucket's rent for its payer (permissionless)
+    pub fn close_deadline_bucket(ctx: Context<CloseDeadlineBucket>) -> Result<()> {
+        ctx.accounts.close_deadline_bucket()
+    }
+
+    /// Grow a pledge to the current layout and stamp its version (permissionless)
+    pub fn migrate_pledge(ctx: Context<MigratePledge>) -> Result<()> {
+        ctx.accounts.migrate_pledge()
+    }
+
+    /// Grow the config to the current layout and stamp its version (admin only)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        ctx.accounts.migrate_config()
+    }
+
+    /// Set the caller's own stake/forfeiture caps and self-exclusion
+    /// Lowering a cap applies immediately, raising one waits LIMIT_RAISE_DELAY_SECONDS
+    pub fn set_user_limits(
+        ctx: Context<SetUserLimits>,
+        max_monthly_stake: Option<u64>,
+        max_single_stake: Option<u64>,
+        max_monthly_forfeiture: Option<u64>,
+        exclude_for_seconds: Option<i64>,
+    ) -> Result<()> {
+        ctx.accounts.set_user_limits(
+            max_monthly_stake,
+            max_single_stake,
+            max_monthly_forfeiture,
+            exclude_for_seconds,
+            &ctx.bumps,
+        )
+    }
+
+    /// Report 100% before the deadline and settle immediately (pledge or config opt-in)
+    pub fn report_early_completion(ctx: Context<ReportEarlyCompletion>) -> Result<()> {
+        ctx.accounts.report_early_completion()
+    }
+
+    /// Open a group challenge - participants stake the same terms and completers
+    /// split the forfeitures of those who miss, less the config's challenge cut
+    /// Joining closes at `join_by`, no later than halfway to the deadline
+    /// With `duel` set only the creator and the invited opponent may join
+    pub fn create_challenge(
+        ctx: Context<CreateChallenge>,
+        challenge_id: u64,
+        stake_amount: u64,
+        deadline: i64,
+        join_by: i64,
+        duel: Option<DuelTerms>,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_challenge(challenge_id, stake_amount, deadline, join_by, duel, &ctx.bumps)
+    }
+
+    /// Pay a settled challenge's pool to its completers (permissionless)
+    /// Remaining accounts: (pledge, recipient token account) per completer; the pool
+    /// goes to the revenue shares when nobody completed
+    pub fn distribute_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeChallenge<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.distribute_challenge(ctx.remaining_accounts)
+    }
+
+    /// Contribute to a team pledge's vault before its deadline
+    pub fn join_team_pledge(ctx: Context<JoinTeamPledge>, amount: u64) -> Result<()> {
+        ctx.accounts.join_team_pledge(amount, &ctx.bumps)
+    }
+
+    /// Cast a contributor's completion vote on a quorum-reported team pledge
+    /// Reaching the quorum reports the lowest voted percentage
+    pub fn vote_team_completion(
+        ctx: Context<VoteTeamCompletion>,
+        completion_percentage: u8,
+    ) -> Result<()> {
+        ctx.accounts.vote_team_completion(completion_percentage)
+    }
+
+    /// Claim a contributor's share of a settled team pledge's refund
+    pub fn claim_team_refund(ctx: Context<ClaimTeamRefund>) -> Result<()> {
+        ctx.accounts.claim_team_refund()
+    }
+
+    /// Refund and cancel the creator's pledge in a duel nobody accepted (permissionless)
+    pub fn expire_duel(ctx: Context<ExpireDuel>) -> Result<()> {
+        ctx.accounts.expire_duel()
+    }
+
+    /// Deposit a bonus paid with the refund on completion (pro-rata on partial completion)
+    /// and returned to the sponsor otherwise (not for challenge or duel pledges)
+    pub fn add_sponsorship(ctx: Context<AddSponsorship>, amount: u64) -> Result<()> {
+        ctx.accounts.add_sponsorship(amount, &ctx.bumps)
+    }
+
+    /// Lend the pledge's stake through the configured lending adapter (owner only,
+    /// before the deadline); the yield is split per config.yield_policy at settlement
+    pub fn deposit_yield(ctx: Context<DepositYield>) -> Result<()> {
+        ctx.accounts.deposit_yield()
+    }
+
+    /// Return a lent stake plus its yield to the vault - the owner at any time,
+    /// anyone after the deadline; settlement requires it
+    pub fn withdraw_yield(ctx: Context<WithdrawYield>) -> Result<()> {
+        ctx.accounts.withdraw_yield()
+    }
+
+    /// Register an expiring session key that may report completion for the caller's
+    /// pledges (or just `pledge`) without the wallet - it can never move funds or edit stakes
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expires_at: i64,
+        pledge: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.create_session(session_key, expires_at, pledge, &ctx.bumps)
+    }
+
+    /// Revoke the caller's session key before it expires
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        ctx.accounts.revoke_session()
+    }
+
+    /// Register (or replace) the caller's recovery address - calling it again also
+    /// checks in, restarting the inactivity window and resetting any pending or final recovery
+    pub fn set_recovery(ctx: Context<SetRecovery>, recovery_address: Pubkey) -> Result<()> {
+        ctx.accounts.set_recovery(recovery_address, &ctx.bumps)
+    }
+
+    /// Start a recovery once the owner has been inactive for the inactivity window
+    /// (recovery address only)
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>) -> Result<()> {
+        ctx.accounts.initiate_recovery()
+    }
+
+    /// Cancel a pending recovery before its challenge window closes (owner only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        ctx.accounts.cancel_recovery()
+    }
+
+    /// Take over an unsettled pledge, past its deadline and created before an unchallenged
+    /// recovery became final - refunds go to the recovery address and it may report completion
+    pub fn claim_recovery(ctx: Context<ClaimRecovery>) -> Result<()> {
+        ctx.accounts.claim_recovery()
+    }
 }
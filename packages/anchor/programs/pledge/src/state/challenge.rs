@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct Challenge {
+    pub creator: Pubkey,        // Wallet that opened the challenge
+    pub challenge_id: u64,      // Creator-chosen id seeding the PDA
+    pub mint: Pubkey,           // Token every participant stakes
+    pub stake_amount: u64,      // Stake each participant must put up
+    pub deadline: i64,          // Shared deadline
+    pub join_by: i64,           // Joining closes here - at or before the challenge's midpoint
+    pub vault: Pubkey,          // Pool vault holding forfeitures for the completers
+    pub participants: u32,      // Pledges that joined
+    pub settled: u32,           // Pledges settled so far
+    pub completers: u32,        // Settled pledges that reached 100%
+    pub paid_completers: u32,   // Completers paid out by distribute_challenge
+    pub pool: u64,              // Forfeitures pooled after the revenue cut
+    pub distributed: bool,      // Pool fully paid out
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub duel: Option<DuelTerms>, // Head-to-head wager: creator vs one invited opponent (None = open)
+}
+
+/// A duel admits the creator's pledge, then the invited opponent's until accept_by
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct DuelTerms {
+    pub opponent: Pubkey, // Wallet invited to accept
+    pub accept_by: i64,   // Acceptance closes here - the duel can then be expired if unaccepted
+}
+
+impl DuelTerms {
+    /// Check the opponent is someone else and acceptance closes by the join cutoff
+    pub fn validate(&self, creator: Pubkey, now: i64, join_by: i64) -> Result<()> {
+        require!(
+            self.opponent != creator && self.accept_by > now && self.accept_by <= join_by,
+            ErrorCode::InvalidDuel
+        );
+        Ok(())
+    }
+
+    /// Check a user may join as the duel's next participant
+    pub fn check_join(
+        &self,
+        creator: Pubkey,
+        participants: u32,
+        user: Pubkey,
+        now: i64,
+    ) -> Result<()> {
+        let allowed = match participants {
+            0 => user == creator,
+            1 => user == self.opponent && now <= self.accept_by,
+            _ => false,
+        };
+        require!(allowed, ErrorCode::DuelNotJoinable);
+        Ok(())
+    }
+}
+
+impl Challenge {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // creator
+        8 +     // challenge_id
+        32 +    // mint
+        8 +     // stake_amount
+        8 +     // deadline
+        8 +     // join_by
+        32 +    // vault
+        4 +     // participants
+        4 +     // settled
+        4 +     // completers
+        4 +     // paid_completers
+        8 +     // pool
+        1 +     // distributed
+        1 +     // bump
+        1 +     // vault_bump
+        1 + DuelTerms::INIT_SPACE; // duel (Option<DuelTerms>)
+
+    /// Duel whose opponent never accepted and can no longer accept
+    pub fn is_unaccepted_duel(&self, now: i64) -> bool {
+        self.duel
+            .is_some_and(|duel| self.participants == 1 && now > duel.accept_by)
+    }
+
+    /// Check joining closes after `now` and no later than halfway to the deadline, so every
+    /// participant is committed for at least as long as joining stayed open
+    pub fn validate_join_by(now: i64, join_by: i64, deadline: i64) -> Result<()> {
+        let join_window = join_by.checked_sub(now).ok_or(ErrorCode::Underflow)?;
+        let committed = deadline.checked_sub(join_by).ok_or(ErrorCode::Underflow)?;
+        require!(
+            join_window > 0 && committed >= join_window,
+            ErrorCode::InvalidJoinCutoff
+        );
+        Ok(())
+    }
+
+    /// Count a new participant, until joining closes
+    pub fn join(&mut self, now: i64) -> Result<()> {
+        require!(now <= self.join_by, ErrorCode::ChallengeJoinClosed);
+        self.participants = self
+            .participants
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Record a participant's settlement and the forfeitures it pooled
+    pub fn record_settlement(&mut self, completion_percentage: u8, pooled_amount: u64) -> Result<()> {
+        self.settled = self.settled.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        if completion_percentage == 100 {
+            self.completers = self.completers.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        }
+        self.pool = self
+            .pool
+            .checked_add(pooled_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Every participant has settled - the pool is final
+    pub fn is_settled(&self) -> bool {
+        self.settled == self.participants
+    }
+
+    /// Next completer's payout - the last one also takes the rounding dust
+    pub fn next_payout(&self, vault_balance: u64) -> Result<u64> {
+        require!(
+            self.paid_completers < self.completers,
+            ErrorCode::InvalidChallengePayout
+        );
+        if self.paid_completers + 1 == self.completers {
+            return Ok(vault_balance);
+        }
+        Ok(self
+            .pool
+            .checked_div(self.completers as u64)
+            .ok_or(ErrorCode::Overflow)?)
+    }
+}
+
+#[event]
+pub struct ChallengeCreated {
+    pub challenge: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub stake_amount: u64,
+    pub deadline: i64,
+    pub join_by: i64,
+    pub duel: Option<DuelTerms>,
+}
+
+#[event]
+pub struct ChallengePayout {
+    pub challenge: Pubkey,
+    pub pledge: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengeDistributed {
+    pub challenge: Pubkey,
+    pub completers: u32,
+    pub pool: u64,
+}
+
+#[event]
+pub struct DuelExpired {
+    pub challenge: Pubkey,
+    pub pledge: Pubkey,
+    pub refund_amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge() -> Challenge {
+        Challenge {
+            creator: Pubkey::new_unique(),
+            challenge_id: 1,
+            mint: Pubkey::new_unique(),
+            stake_amount: 10_000_000,
+            deadline: 1_700_086_400,
+            join_by: 1_700_043_200,
+            vault: Pubkey::new_unique(),
+            participants: 0,
+            settled: 0,
+            completers: 0,
+            paid_completers: 0,
+            pool: 0,
+            distributed: false,
+            bump: 255,
+            vault_bump: 254,
+            duel: None,
+        }
+    }
+
+    #[test]
+    fn test_settlements_fill_pool_and_count_completers() {
+        let mut challenge = challenge();
+        for _ in 0..3 {
+            challenge.join(1_700_000_000).unwrap();
+        }
+
+        challenge.record_settlement(100, 0).unwrap();
+        challenge.record_settlement(0, 9_000_000).unwrap();
+        assert!(!challenge.is_settled());
+        challenge.record_settlement(100, 0).unwrap();
+
+        assert!(challenge.is_settled());
+        assert_eq!(challenge.completers, 2);
+        assert_eq!(challenge.pool, 9_000_000);
+    }
+
+    #[test]
+    fn test_joining_closes_by_the_midpoint() {
+        let mut challenge = challenge();
+        let now = 1_700_000_000;
+
+        assert!(Challenge::validate_join_by(now, now, challenge.deadline).is_err());
+        assert!(Challenge::validate_join_by(now, challenge.join_by, challenge.deadline).is_ok());
+        assert!(Challenge::validate_join_by(now, challenge.join_by + 1, challenge.deadline).is_err());
+
+        challenge.join(challenge.join_by).unwrap();
+        assert!(challenge.join(challenge.join_by + 1).is_err());
+        assert_eq!(challenge.participants, 1);
+    }
+
+    #[test]
+    fn test_last_completer_takes_rounding_dust() {
+        let mut challenge = challenge();
+        challenge.completers = 3;
+        challenge.pool = 10_000_000;
+
+        assert_eq!(challenge.next_payout(10_000_000).unwrap(), 3_333_333);
+        challenge.paid_completers = 2;
+        assert_eq!(challenge.next_payout(3_333_334).unwrap(), 3_333_334);
+        challenge.paid_completers = 3;
+        assert!(challenge.next_payout(0).is_err());
+    }
+
+    #[test]
+    fn test_duel_admits_creator_then_opponent() {
+        let mut challenge = challenge();
+        let duel = DuelTerms {
+            opponent: Pubkey::new_unique(),
+            accept_by: 1_700_050_000,
+        };
+        challenge.duel = Some(duel);
+        let stranger = Pubkey::new_unique();
+
+        assert!(duel.check_join(challenge.creator, 0, duel.opponent, 1_700_000_000).is_err());
+        assert!(duel.check_join(challenge.creator, 0, challenge.creator, 1_700_000_000).is_ok());
+        challenge.join(1_700_000_000).unwrap();
+
+        assert!(duel.check_join(challenge.creator, 1, stranger, 1_700_000_000).is_err());
+        assert!(duel.check_join(challenge.creator, 1, duel.opponent, 1_700_050_001).is_err());
+        assert!(duel.check_join(challenge.creator, 1, duel.opponent, 1_700_050_000).is_ok());
+
+        // Unaccepted once acceptance closes with only the creator in
+        assert!(!challenge.is_unaccepted_duel(1_700_050_000));
+        assert!(challenge.is_unaccepted_duel(1_700_050_001));
+        challenge.join(1_700_000_000).unwrap();
+        assert!(!challenge.is_unaccepted_duel(1_700_050_001));
+        assert!(duel.check_join(challenge.creator, 2, duel.opponent, 1_700_000_000).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct CharityRecord {
+    pub name_hash: [u8; 32], // Hash of the charity's display name (also the PDA seed)
+    pub payout: Pubkey,      // Wallet that receives this charity's distributions
+    pub active: bool,        // Inactive charities can't be picked for new pledges
+    pub bump: u8,
+}
+
+impl CharityRecord {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // name_hash
+        32 +    // payout
+        1 +     // active
+        1; // bump
+}
+
+/// A charity's undistributed share of the fees held in one mint's fee vault
+#[account]
+pub struct CharityLedger {
+    pub charity: Pubkey,   // Charity record (PDA seed)
+    pub mint: Pubkey,      // Mint of the fee vault holding the fees (PDA seed)
+    pub accrued_fees: u64, // Charity share held in the fee vault, not yet distributed
+    pub bump: u8,
+}
+
+impl CharityLedger {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // charity
+        32 +    // mint
+        8 +     // accrued_fees
+        1; // bump
+
+    /// Credit this charity's share to its undistributed fee ledger
+    pub fn accrue_fees(&mut self, amount: u64) -> Result<()> {
+        self.accrued_fees = self
+            .accrued_fees
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+#[event]
+pub struct CharityRegistered {
+    pub charity: Pubkey,
+    pub name_hash: [u8; 32],
+    pub payout: Pubkey,
+}
+
+#[event]
+pub struct CharityUpdated {
+    pub charity: Pubkey,
+    pub payout: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct CharityLedgerInitialized {
+    pub charity: Pubkey,
+    pub mint: Pubkey,
+    pub charity_ledger: Pubkey,
+}
+
+#[event]
+pub struct CharityFeesDistributed {
+    pub charity: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
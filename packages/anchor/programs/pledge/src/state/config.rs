@@ -1,36 +1,392 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{BPS_DENOMINATOR, CONFIG_RESERVED_BYTES, MAX_REVENUE_SHARES};
+use crate::errors::ErrorCode;
+use crate::state::{CharityLedger, CharityRecord, FeeLedger, Pledge};
+use crate::utils::fees::{calculate_late_penalty_bps, calculate_revenue_split, calculate_split};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct RevenueShare {
+    pub recipient: Pubkey, // Wallet that receives this share on distribution
+    pub bps: u16,          // Share of fees/forfeitures (all shares sum to 10000)
+    pub is_charity: bool,  // Charity slot - may be redirected per pledge
+    pub accrued: u64,      // Undistributed across all mints' fee ledgers
+}
+
+/// Revenue share as supplied by the admin (accrued balance starts at zero)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RevenueShareParams {
+    pub recipient: Pubkey,
+    pub bps: u16,
+    pub is_charity: bool,
+}
+
+impl From<RevenueShareParams> for RevenueShare {
+    fn from(params: RevenueShareParams) -> Self {
+        RevenueShare {
+            recipient: params.recipient,
+            bps: params.bps,
+            is_charity: params.is_charity,
+            accrued: 0,
+        }
+    }
+}
+
+/// Exposure guardrails (0 = no limit)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct PledgeLimits {
+    pub min_stake: u64,               // Minimum stake per pledge
+    pub max_stake: u64,               // Maximum stake per pledge
+    pub min_duration_seconds: i64,    // Minimum deadline - created_at
+    pub max_duration_seconds: i64,    // Maximum deadline - created_at
+    pub max_active_pledges: u32,      // Maximum unsettled pledges per user
+    pub max_active_stake: u64,        // Maximum total unsettled stake per user
+}
+
+impl PledgeLimits {
+    /// Check minimums don't exceed their maximums
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_duration_seconds >= 0 && self.max_duration_seconds >= 0,
+            ErrorCode::InvalidLimits
+        );
+        require!(
+            self.max_stake == 0 || self.min_stake <= self.max_stake,
+            ErrorCode::InvalidLimits
+        );
+        require!(
+            self.max_duration_seconds == 0 || self.min_duration_seconds <= self.max_duration_seconds,
+            ErrorCode::InvalidLimits
+        );
+        Ok(())
+    }
+
+    /// Check a single pledge's stake against the per-pledge bounds
+    pub fn check_stake(&self, stake_amount: u64) -> Result<()> {
+        require!(stake_amount >= self.min_stake, ErrorCode::StakeBelowMinimum);
+        require!(
+            self.max_stake == 0 || stake_amount <= self.max_stake,
+            ErrorCode::StakeAboveMaximum
+        );
+        Ok(())
+    }
+
+    /// Check a pledge's duration (deadline - created_at) against the bounds
+    pub fn check_duration(&self, created_at: i64, deadline: i64) -> Result<()> {
+        let duration = deadline.checked_sub(created_at).ok_or(ErrorCode::Underflow)?;
+        require!(duration >= self.min_duration_seconds, ErrorCode::DurationTooShort);
+        require!(
+            self.max_duration_seconds == 0 || duration <= self.max_duration_seconds,
+            ErrorCode::DurationTooLong
+        );
+        Ok(())
+    }
+}
+
+/// Late report window after the grace period (window_seconds = 0 disables it)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct LateReportPolicy {
+    pub window_seconds: i64,   // How long after the grace period a late report is still accepted
+    pub max_penalty_bps: u16,  // Share of the refund lost by a report at the very end of the window
+}
+
+impl LateReportPolicy {
+    /// Check the window isn't negative and the penalty is at most 100%
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.window_seconds >= 0 && self.max_penalty_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidLateReportPolicy
+        );
+        Ok(())
+    }
+}
+
+/// Range users may pick their own grace period from (max_seconds = 0 = global grace only)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct GracePeriodBounds {
+    pub min_seconds: i64,
+    pub max_seconds: i64,
+}
+
+impl GracePeriodBounds {
+    /// Check the bounds aren't negative and min doesn't exceed max
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.min_seconds >= 0 && self.min_seconds <= self.max_seconds,
+            ErrorCode::InvalidGracePeriodBounds
+        );
+        Ok(())
+    }
+
+    /// Check a user-chosen grace period lies within the bounds
+    pub fn check(&self, grace_period_seconds: i64) -> Result<()> {
+        require!(
+            self.max_seconds > 0
+                && grace_period_seconds >= self.min_seconds
+                && grace_period_seconds <= self.max_seconds,
+            ErrorCode::InvalidGracePeriod
+        );
+        Ok(())
+    }
+}
+
+/// Optional yield mode - pledge vaults may lend their stake through the adapter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct YieldPolicy {
+    pub adapter: Option<Pubkey>, // Lending adapter program (None = yield mode off)
+    pub user_bps: u16,           // User's share of the yield (the rest is split like revenue)
+}
+
+impl YieldPolicy {
+    /// Check the user share is at most 100%
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.user_bps as u64 <= BPS_DENOMINATOR,
+            ErrorCode::InvalidYieldPolicy
+        );
+        Ok(())
+    }
+}
+
 #[account]
 pub struct ProgramConfig {
-    pub admin: Pubkey,           // Program admin (can update config)
-    pub treasury: Pubkey,        // Treasury wallet (receives forfeitures)
-    pub charity: Pubkey,         // Charity wallet (receives forfeitures)
-    pub treasury_split_bps: u16, // Treasury % of forfeitures (7000 = 70%)
-    pub partial_fee_bps: u16,    // Fee on partial completions (100 = 1%)
-    pub edit_penalty_bps: u16,   // Penalty for editing (1000 = 10%)
-    pub grace_period_seconds: i64, // Grace period after deadline (86400 = 1 day)
-    pub paused: bool,            // Emergency pause flag
+    pub admin: Pubkey,                     // Program admin (can update config)
+    pub revenue_shares: Vec<RevenueShare>, // Recipients of fees/forfeitures (max MAX_REVENUE_SHARES)
+    pub partial_fee_bps: u16,              // Fee on partial completions (100 = 1%)
+    pub edit_penalty_bps: u16,             // Penalty for editing (1000 = 10%)
+    pub grace_period_seconds: i64,         // Grace period after deadline (86400 = 1 day)
+    pub paused: bool,                      // Emergency pause flag
+    pub max_beneficiary_bps: u16,          // Max % of the charity share a custom beneficiary receives
+    pub crank_reward_bps: u16,             // Settler's cut of fees + forfeitures (max MAX_CRANK_REWARD_BPS)
     pub bump: u8,
+    pub version: u8,                       // Layout version (0 = created before versioning)
+    pub limits: PledgeLimits,              // Exposure guardrails
+    pub allow_early_report: bool,          // Every pledge may report 100% before its deadline
+    pub late_report: LateReportPolicy,     // Penalized reporting after the grace period
+    pub grace_bounds: GracePeriodBounds,   // Allowed per-pledge grace periods
+    pub challenge_cut_bps: u16,            // Revenue share cut of challenge forfeitures (rest is pooled)
+    pub yield_policy: YieldPolicy,         // Lending adapter and yield split
+    pub max_default_percentage: u8,        // Highest DefaultOutcome::Percentage a pledge may pick
+    pub reserved: [u8; CONFIG_RESERVED_BYTES], // Zeroed space for future fields
 }
 
 impl ProgramConfig {
     pub const INIT_SPACE: usize = 8 +  // discriminator
         32 +    // admin
-        32 +    // treasury
-        32 +    // charity
-        2 +     // treasury_split_bps
+        4 + MAX_REVENUE_SHARES * RevenueShare::INIT_SPACE + // revenue_shares
         2 +     // partial_fee_bps
         2 +     // edit_penalty_bps
         8 +     // grace_period_seconds
         1 +     // paused
-        1; // bump
+        2 +     // max_beneficiary_bps
+        2 +     // crank_reward_bps
+        1 +     // bump
+        1 +     // version
+        PledgeLimits::INIT_SPACE + // limits
+        1 +     // allow_early_report
+        LateReportPolicy::INIT_SPACE + // late_report
+        GracePeriodBounds::INIT_SPACE + // grace_bounds
+        2 +     // challenge_cut_bps
+        YieldPolicy::INIT_SPACE + // yield_policy
+        1 +     // max_default_percentage
+        CONFIG_RESERVED_BYTES; // reserved
+
+    /// Check shares are non-empty, bounded, sum to 10000 and have at most one charity slot
+    pub fn validate_revenue_shares(shares: &[RevenueShareParams]) -> Result<()> {
+        require!(
+            !shares.is_empty() && shares.len() <= MAX_REVENUE_SHARES,
+            ErrorCode::InvalidRevenueShares
+        );
+
+        let total_bps = shares
+            .iter()
+            .try_fold(0u16, |total, share| total.checked_add(share.bps))
+            .ok_or(ErrorCode::InvalidRevenueShares)?;
+        require!(total_bps == 10000, ErrorCode::InvalidRevenueShares);
+
+        let charity_slots = shares.iter().filter(|share| share.is_charity).count();
+        require!(charity_slots <= 1, ErrorCode::InvalidRevenueShares);
+
+        Ok(())
+    }
+
+    /// Replace the revenue shares (all accrued fees must be distributed first)
+    pub fn set_revenue_shares(&mut self, shares: &[RevenueShareParams]) -> Result<()> {
+        Self::validate_revenue_shares(shares)?;
+        require!(
+            self.revenue_shares.iter().all(|share| share.accrued == 0),
+            ErrorCode::UndistributedFees
+        );
+
+        self.revenue_shares = shares.iter().copied().map(RevenueShare::from).collect();
+        Ok(())
+    }
+
+    /// End of the penalty-free reporting period for a pledge (its own grace or the global one)
+    pub fn grace_period_end(&self, pledge: &Pledge) -> Result<i64> {
+        let grace_period_seconds = pledge
+            .grace_period_seconds
+            .unwrap_or(self.grace_period_seconds);
+        Ok(pledge
+            .deadline
+            .checked_add(grace_period_seconds)
+            .ok_or(ErrorCode::Overflow)?)
+    }
+
+    /// Last moment a report is accepted - cranks may settle as expired after this
+    pub fn report_cutoff(&self, pledge: &Pledge) -> Result<i64> {
+        Ok(self
+            .grace_period_end(pledge)?
+            .checked_add(self.late_report.window_seconds)
+            .ok_or(ErrorCode::Overflow)?)
+    }
+
+    /// Late penalty for a report made at `now` (0 within the grace period)
+    pub fn late_penalty_bps(&self, pledge: &Pledge, now: i64) -> Result<u16> {
+        let seconds_late = now
+            .checked_sub(self.grace_period_end(pledge)?)
+            .ok_or(ErrorCode::Underflow)?;
+        calculate_late_penalty_bps(
+            seconds_late,
+            self.late_report.window_seconds,
+            self.late_report.max_penalty_bps,
+        )
+    }
+
+    /// Split an amount across the revenue shares, in config order
+    pub fn split_revenue(&self, amount: u64) -> Result<Vec<u64>> {
+        let shares_bps: Vec<u16> = self.revenue_shares.iter().map(|share| share.bps).collect();
+        calculate_revenue_split(amount, &shares_bps)
+    }
+
+    /// Credit a revenue share in the mint's fee ledger and the cross-mint total
+    fn credit_share(&mut self, fee_ledger: &mut FeeLedger, index: usize, amount: u64) -> Result<()> {
+        fee_ledger.credit(index, amount)?;
+        let share = &mut self.revenue_shares[index];
+        share.accrued = share
+            .accrued
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Credit amounts split by split_revenue straight to the revenue shares
+    pub fn accrue_revenue(
+        &mut self,
+        fee_ledger: &mut FeeLedger,
+        revenue_amounts: &[u64],
+    ) -> Result<()> {
+        for (index, amount) in revenue_amounts
+            .iter()
+            .enumerate()
+            .take(self.revenue_shares.len())
+        {
+            self.credit_share(fee_ledger, index, *amount)?;
+        }
+        Ok(())
+    }
+
+    /// Index of the charity slot, if one is configured
+    pub fn charity_share_index(&self) -> Option<usize> {
+        self.revenue_shares.iter().position(|share| share.is_charity)
+    }
+
+    /// Check a beneficiary share chosen at creation - it is carved out of the charity slot,
+    /// so one must be configured
+    pub fn check_beneficiary_bps(&self, beneficiary_bps: u16) -> Result<()> {
+        require!(
+            self.charity_share_index().is_some(),
+            ErrorCode::BeneficiaryUnavailable
+        );
+        require!(
+            beneficiary_bps > 0 && beneficiary_bps <= self.max_beneficiary_bps,
+            ErrorCode::InvalidBeneficiaryShare
+        );
+        Ok(())
+    }
+
+    /// Take a custom beneficiary's chosen cut out of the charity slot's amount
+    pub fn take_beneficiary_cut(&self, beneficiary_bps: u16, revenue_amounts: &mut [u64]) -> Result<u64> {
+        let Some(index) = self.charity_share_index() else {
+            return Ok(0);
+        };
+
+        let (beneficiary_amount, charity_amount) =
+            calculate_split(revenue_amounts[index], beneficiary_bps)?;
+        revenue_amounts[index] = charity_amount;
+
+        Ok(beneficiary_amount)
+    }
+
+    /// Credit a pledge's revenue split to its mint's fee ledger, routing the
+    /// charity slot to the pledge's registered charity (in its ledger for the
+    /// pledge's mint) while it is active. Returns the charity credited.
+    pub fn accrue_pledge_fees(
+        &mut self,
+        pledge: &Pledge,
+        fee_ledger: &mut FeeLedger,
+        charity: Option<&Account<CharityRecord>>,
+        charity_ledger: Option<&mut Account<CharityLedger>>,
+        revenue_amounts: &[u64],
+    ) -> Result<Option<Pubkey>> {
+        require_keys_eq!(fee_ledger.mint, pledge.mint, ErrorCode::InvalidFeeLedger);
+        let charity_index = self.charity_share_index();
+
+        let mut credited_ledger = None;
+        if let (Some(charity_key), Some(_)) = (pledge.charity, charity_index) {
+            let record = charity.ok_or(ErrorCode::InvalidCharity)?;
+            require_keys_eq!(record.key(), charity_key, ErrorCode::InvalidCharity);
+
+            // Deactivated charities fall back to the configured charity slot
+            if record.active {
+                let ledger = charity_ledger.ok_or(ErrorCode::InvalidCharityLedger)?;
+                require!(
+                    ledger.charity == charity_key && ledger.mint == pledge.mint,
+                    ErrorCode::InvalidCharityLedger
+                );
+                credited_ledger = Some(ledger);
+            }
+        }
+        let credited_charity = credited_ledger.as_ref().map(|ledger| ledger.charity);
+
+        for (index, amount) in revenue_amounts
+            .iter()
+            .enumerate()
+            .take(self.revenue_shares.len())
+        {
+            match credited_ledger.as_mut() {
+                Some(ledger) if Some(index) == charity_index => ledger.accrue_fees(*amount)?,
+                _ => self.credit_share(fee_ledger, index, *amount)?,
+            }
+        }
+
+        Ok(credited_charity)
+    }
 }
 
 #[event]
 pub struct ConfigInitialized {
     pub admin: Pubkey,
-    pub treasury: Pubkey,
-    pub charity: Pubkey,
+    pub revenue_shares: Vec<RevenueShare>,
+}
+
+#[event]
+pub struct FeeVaultInitialized {
+    pub mint: Pubkey,
+    pub fee_vault: Pubkey,
+    pub fee_ledger: Pubkey,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub mint: Pubkey,
+    pub amounts: Vec<u64>, // Paid per revenue share, in config order
+}
+
+#[event]
+pub struct ConfigMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
 }
 
 #[event]
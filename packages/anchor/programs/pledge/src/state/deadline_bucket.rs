@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{DEADLINE_BUCKET_SEED, MAX_BUCKET_ENTRIES, SECONDS_PER_BUCKET};
+use crate::utils::{create_pda_account, grow_account};
+
+/// Deadline index for one shard of a day - each day is split into DEADLINE_BUCKET_SHARDS
+/// buckets so no single bucket can be filled to lock others out of the index
+#[account]
+pub struct DeadlineBucket {
+    pub day: i64,             // Deadline / SECONDS_PER_BUCKET (also a PDA seed)
+    pub shard: u8,            // Client-chosen shard of the day (also a PDA seed)
+    pub rent_payer: Pubkey,   // Receives the rent when the empty bucket is closed
+    pub pledges: Vec<Pubkey>, // Unsettled pledges with a deadline on this day (max MAX_BUCKET_ENTRIES)
+    pub bump: u8,
+}
+
+impl DeadlineBucket {
+    /// Account size holding `entries` pledges - buckets grow one entry at a time
+    pub const fn space_for(entries: usize) -> usize {
+        8 +     // discriminator
+        8 +     // day
+        1 +     // shard
+        32 +    // rent_payer
+        4 + entries * 32 + // pledges
+        1 // bump
+    }
+
+    /// Bucket index for a deadline
+    pub fn day_for(deadline: i64) -> i64 {
+        deadline.div_euclid(SECONDS_PER_BUCKET)
+    }
+
+    /// Index a pledge (no-op if already present)
+    /// A full bucket never blocks the pledge - it stays unindexed and a PledgeNotIndexed
+    /// event tells cranks to track it themselves
+    pub fn add_pledge(&mut self, pledge: Pubkey) {
+        if self.pledges.contains(&pledge) {
+            return;
+        }
+        if self.pledges.len() >= MAX_BUCKET_ENTRIES {
+            emit!(PledgeNotIndexed {
+                pledge,
+                day: self.day,
+                shard: self.shard,
+            });
+            return;
+        }
+        self.pledges.push(pledge);
+    }
+
+    /// Index a pledge in a bucket passed as a raw account, creating the bucket on first use
+    /// `payer` covers rent only for the entry it adds - slots freed by settled pledges are reused
+    pub fn add_pledge_to<'info>(
+        bucket_info: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        pledge: Pubkey,
+        day: i64,
+        shard: u8,
+        bump: u8,
+    ) -> Result<()> {
+        let mut bucket = if bucket_info.data_is_empty() {
+            let day_bytes = day.to_le_bytes();
+            let bucket_seeds: &[&[u8]] = &[DEADLINE_BUCKET_SEED, &day_bytes, &[shard], &[bump]];
+            create_pda_account(
+                bucket_info,
+                payer,
+                system_program,
+                DeadlineBucket::space_for(1),
+                &[bucket_seeds],
+            )?;
+            DeadlineBucket {
+                day,
+                shard,
+                rent_payer: payer.key(),
+                pledges: Vec::new(),
+                bump,
+            }
+        } else {
+            require_keys_eq!(
+                *bucket_info.owner,
+                crate::ID,
+                anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram
+            );
+            DeadlineBucket::try_deserialize(&mut &bucket_info.try_borrow_data()?[..])?
+        };
+
+        bucket.add_pledge(pledge);
+        grow_account(
+            bucket_info,
+            payer,
+            system_program,
+            DeadlineBucket::space_for(bucket.pledges.len()),
+        )?;
+        bucket.try_serialize(&mut &mut bucket_info.try_borrow_mut_data()?[..])
+    }
+
+    /// Drop a pledge from the index (no-op if absent)
+    pub fn remove_pledge(&mut self, pledge: &Pubkey) {
+        if let Some(index) = self.pledges.iter().position(|key| key == pledge) {
+            self.pledges.swap_remove(index);
+        }
+    }
+
+    /// Drop a pledge from a bucket passed as a raw account
+    /// Buckets that were never created (pledges created before deadline indexing) are skipped
+    pub fn remove_pledge_from(bucket_info: &AccountInfo, pledge: &Pubkey) -> Result<()> {
+        if bucket_info.owner != &crate::ID || bucket_info.data_is_empty() {
+            return Ok(());
+        }
+
+        let mut data = bucket_info.try_borrow_mut_data()?;
+        let mut bucket = DeadlineBucket::try_deserialize(&mut &data[..])?;
+        bucket.remove_pledge(pledge);
+        bucket.try_serialize(&mut &mut data[..])
+    }
+}
+
+#[event]
+pub struct DeadlineBucketClosed {
+    pub day: i64,
+    pub shard: u8,
+    pub rent_payer: Pubkey,
+}
+
+#[event]
+pub struct PledgeNotIndexed {
+    pub pledge: Pubkey,
+    pub day: i64,
+    pub shard: u8, // Full bucket the pledge was left out of
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_bucket_leaves_pledge_unindexed() {
+        let mut bucket = DeadlineBucket {
+            day: 20_000,
+            shard: 3,
+            rent_payer: Pubkey::new_unique(),
+            pledges: (0..MAX_BUCKET_ENTRIES).map(|_| Pubkey::new_unique()).collect(),
+            bump: 255,
+        };
+
+        // Adding to a full bucket is not an error - the pledge just isn't listed
+        let pledge = Pubkey::new_unique();
+        bucket.add_pledge(pledge);
+        assert_eq!(bucket.pledges.len(), MAX_BUCKET_ENTRIES);
+        assert!(!bucket.pledges.contains(&pledge));
+
+        // Room frees up as indexed pledges settle
+        let settled = bucket.pledges[0];
+        bucket.remove_pledge(&settled);
+        bucket.add_pledge(pledge);
+        assert!(bucket.pledges.contains(&pledge));
+    }
+}
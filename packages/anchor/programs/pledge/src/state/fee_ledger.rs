@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_REVENUE_SHARES;
+use crate::errors::ErrorCode;
+
+/// Undistributed revenue held in one mint's fee vault - every mint keeps its own
+/// balances so a distribution only ever pays out tokens of the vault's mint
+#[account]
+pub struct FeeLedger {
+    pub mint: Pubkey,                       // Mint of the fee vault (also the PDA seed)
+    pub accrued: [u64; MAX_REVENUE_SHARES], // Per revenue share, in config order
+    pub bump: u8,
+}
+
+impl FeeLedger {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // mint
+        MAX_REVENUE_SHARES * 8 + // accrued
+        1; // bump
+
+    /// Credit a revenue share's slot
+    pub fn credit(&mut self, index: usize, amount: u64) -> Result<()> {
+        let accrued = self
+            .accrued
+            .get_mut(index)
+            .ok_or(ErrorCode::InvalidRevenueShares)?;
+        *accrued = accrued.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::utils::fees::calculate_split;
+
+/// Pledge vault funds lent out through the config's lending adapter
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct YieldPosition {
+    pub adapter: Pubkey,   // Lending adapter program holding the funds
+    pub principal: u64,    // Deposited from the vault - always the full stake
+    pub user_bps: u16,     // User's share of the yield (config value at deposit)
+    pub withdrawn: bool,   // Funds are back in the vault
+    pub yield_amount: u64, // Returned above the principal (set on withdrawal)
+}
+
+impl YieldPosition {
+    /// Record the adapter's return - it must cover the principal so settlement math stays exact
+    pub fn record_withdrawal(&mut self, returned: u64) -> Result<()> {
+        require!(!self.withdrawn, ErrorCode::YieldNotDeposited);
+        self.yield_amount = returned
+            .checked_sub(self.principal)
+            .ok_or(ErrorCode::YieldShortfall)?;
+        self.withdrawn = true;
+        Ok(())
+    }
+
+    /// Split the yield into (user share, revenue share)
+    pub fn split_yield(&self) -> Result<(u64, u64)> {
+        calculate_split(self.yield_amount, self.user_bps)
+    }
+}
+
+#[event]
+pub struct YieldDeposited {
+    pub pledge: Pubkey,
+    pub adapter: Pubkey,
+    pub principal: u64,
+}
+
+#[event]
+pub struct YieldWithdrawn {
+    pub pledge: Pubkey,
+    pub adapter: Pubkey,
+    pub principal: u64,
+    pub yield_amount: u64,
+    pub user_yield: u64, // Paid with the refund at settlement (the rest goes to the revenue shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yield_splits_above_exact_principal() {
+        let mut position = YieldPosition {
+            adapter: Pubkey::new_unique(),
+            principal: 10_000_000,
+            user_bps: 5000,
+            withdrawn: false,
+            yield_amount: 0,
+        };
+
+        // A return below the principal is rejected and leaves the position open
+        assert!(position.record_withdrawal(9_999_999).is_err());
+        assert!(!position.withdrawn);
+
+        position.record_withdrawal(10_300_001).unwrap();
+        assert_eq!(position.yield_amount, 300_001);
+        assert_eq!(position.split_yield().unwrap(), (150_000, 150_001));
+        assert!(position.record_withdrawal(10_300_001).is_err());
+    }
+}
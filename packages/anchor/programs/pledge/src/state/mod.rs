@@ -1,5 +1,27 @@
+pub mod challenge;
+pub mod charity;
 pub mod config;
+pub mod deadline_bucket;
+pub mod fee_ledger;
+pub mod lending;
 pub mod pledge;
+pub mod recovery;
+pub mod session;
+pub mod sponsorship;
+pub mod team;
+pub mod user_counter;
+pub mod user_limits;
 
+pub use challenge::*;
+pub use charity::*;
 pub use config::*;
+pub use deadline_bucket::*;
+pub use fee_ledger::*;
+pub use lending::*;
 pub use pledge::*;
+pub use recovery::*;
+pub use session::*;
+pub use sponsorship::*;
+pub use team::*;
+pub use user_counter::*;
+pub use user_limits::*;
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::PLEDGE_RESERVED_BYTES;
+use crate::errors::ErrorCode;
+use crate::state::{TeamReporting, TeamState, YieldPosition};
+
 #[account]
 pub struct Pledge {
     pub user: Pubkey,                       // User who created the pledge
@@ -10,12 +14,51 @@ pub struct Pledge {
     pub completion_percentage: Option<u8>,  // Reported completion (0-100)
     pub reported_at: Option<i64>,           // When user reported completion
     pub created_at: i64,                    // When pledge was created
+    pub charity: Option<Pubkey>,            // Registered charity record (None = config.charity)
+    pub beneficiary: Option<Pubkey>,        // Custom forfeiture beneficiary wallet (friend or anti-charity)
     pub bump: u8,
-    pub vault_bump: u8, // Bump for token vault PDA
+    pub vault_bump: u8,                     // Bump for token vault PDA
+    pub version: u8,                        // Layout version (0 = created before versioning)
+    pub index: Option<u64>,                 // UserCounter index seeding the PDA (None = seeded by created_at)
+    pub metadata: GoalMetadata,             // Commitment to the off-chain goal (zeroed = none)
+    pub metadata_version: u32,              // Bumped on every metadata edit
+    pub early_report: bool,                 // Opted in to a 100% report (and settlement) before the deadline
+    pub late_penalty_bps: u16,              // Share of the refund lost to a late report (set when reported)
+    pub grace_period_seconds: Option<i64>,  // User-chosen grace period (None = config.grace_period_seconds)
+    pub default_outcome: DefaultOutcome,    // Settlement when the user never reports
+    pub funder: Option<Pubkey>,             // Third party that staked on the user's behalf (None = user)
+    pub refund_to_user: bool,               // Funder opted to send refunds to the user instead of back to itself
+    pub challenge: Option<Pubkey>,          // Group challenge this pledge joined
+    pub challenge_paid: bool,               // Received its share of the challenge pool
+    pub team: Option<TeamState>,            // Team pledge contributors and reporting (None = solo)
+    pub sponsorship: u64,                   // Sponsor bonus deposited (0 = unsponsored)
+    pub yield_position: Option<YieldPosition>, // Vault funds lent out for yield (None = idle vault)
+    pub rent_payer: Option<Pubkey>,         // Sponsor that paid the pledge and vault rent (None = user)
+    pub refund_account: Option<Pubkey>,     // Token account refunds must go to (None = any of the recipient's)
+    pub recovery: Option<Pubkey>,           // Recovery address that took the pledge over (None = owner)
+    pub deadline_shard: u8,                 // Deadline bucket shard the pledge is indexed in
+    pub beneficiary_bps: u16,               // Share of the charity slot the beneficiary receives (0 = none)
+    pub reserved: [u8; PLEDGE_RESERVED_BYTES], // Zeroed space for future fields
+}
+
+/// Goal details committed on chain - the title/description themselves stay off chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct GoalMetadata {
+    pub content_hash: [u8; 32], // SHA-256 of "<title>\n<description>"
+    pub category: u16,          // App-defined goal category code
+    pub is_public: bool,        // Listed in public goals
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+/// Custom forfeiture beneficiary chosen at creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BeneficiaryTerms {
+    pub wallet: Pubkey, // Friend or anti-charity wallet
+    pub bps: u16,       // Share of the charity slot it receives (at most config.max_beneficiary_bps)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
 pub enum PledgeStatus {
+    #[default]
     Active,    // Pledge is ongoing
     Reported,  // User reported, awaiting processing
     Completed, // Processed with refund
@@ -23,9 +66,37 @@ pub enum PledgeStatus {
     Cancelled, // User cancelled (if allowed)
 }
 
-impl Default for PledgeStatus {
-    fn default() -> Self {
-        PledgeStatus::Active
+/// How an unreported pledge settles once its report window closes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub enum DefaultOutcome {
+    #[default]
+    Attested,       // Crank supplies the percentage from check-in/attestation data (pre-policy behavior)
+    Forfeit,        // Settle at 0%
+    Percentage(u8), // Settle at a percentage chosen at creation
+}
+
+impl DefaultOutcome {
+    /// Check a chosen percentage is 0-100 and within the admin's cap - challenge pledges
+    /// must forfeit, or an unreported stake would share the pool with honest completers
+    pub fn validate(&self, max_percentage: u8, challenge: bool) -> Result<()> {
+        if let DefaultOutcome::Percentage(percentage) = self {
+            require!(*percentage <= 100, ErrorCode::InvalidCompletionPercentage);
+            require!(*percentage <= max_percentage, ErrorCode::InvalidDefaultOutcome);
+        }
+        require!(
+            !challenge || *self == DefaultOutcome::Forfeit,
+            ErrorCode::InvalidDefaultOutcome
+        );
+        Ok(())
+    }
+
+    /// Completion percentage to settle an expired pledge at
+    pub fn resolve(&self, attested_percentage: u8) -> u8 {
+        match self {
+            DefaultOutcome::Attested => attested_percentage,
+            DefaultOutcome::Forfeit => 0,
+            DefaultOutcome::Percentage(percentage) => *percentage,
+        }
     }
 }
 
@@ -39,8 +110,64 @@ impl Pledge {
         1 + 1 + // completion_percentage (Option<u8>)
         1 + 8 + // reported_at (Option<i64>)
         8 +     // created_at
+        1 + 32 + // charity (Option<Pubkey>)
+        1 + 32 + // beneficiary (Option<Pubkey>)
         1 +     // bump
-        1; // vault_bump
+        1 +     // vault_bump
+        1 +     // version
+        1 + 8 + // index (Option<u64>)
+        GoalMetadata::INIT_SPACE + // metadata
+        4 +     // metadata_version
+        1 +     // early_report
+        2 +     // late_penalty_bps
+        1 + 8 + // grace_period_seconds (Option<i64>)
+        DefaultOutcome::INIT_SPACE + // default_outcome
+        1 + 32 + // funder (Option<Pubkey>)
+        1 +     // refund_to_user
+        1 + 32 + // challenge (Option<Pubkey>)
+        1 +     // challenge_paid
+        1 + TeamState::INIT_SPACE + // team (Option<TeamState>)
+        8 +     // sponsorship
+        1 + YieldPosition::INIT_SPACE + // yield_position (Option<YieldPosition>)
+        1 + 32 + // rent_payer (Option<Pubkey>)
+        1 + 32 + // refund_account (Option<Pubkey>)
+        1 + 32 + // recovery (Option<Pubkey>)
+        1 +     // deadline_shard
+        2 +     // beneficiary_bps
+        PLEDGE_RESERVED_BYTES; // reserved
+
+    /// Wallet that owns the goal: the user, or their recovery address once it took the pledge over
+    pub fn goal_owner(&self) -> Pubkey {
+        self.recovery.unwrap_or(self.user)
+    }
+
+    /// Wallet whose token account receives refunds: the funder, unless it opted to refund the
+    /// user - or the user's recovery address once it took the pledge over
+    pub fn refund_recipient(&self) -> Pubkey {
+        match self.funder {
+            Some(funder) if !self.refund_to_user => funder,
+            _ => self.goal_owner(),
+        }
+    }
+
+    /// Whether a refund may be paid into this token account (the recorded one, if any)
+    pub fn accepts_refund_account(&self, token_account: Pubkey) -> bool {
+        self.refund_account.is_none_or(|account| account == token_account)
+    }
+
+    /// Whether the owner reports completion (solo pledges and captain-reported teams)
+    pub fn captain_reports(&self) -> bool {
+        self.team
+            .is_none_or(|team| team.reporting == TeamReporting::Captain)
+    }
+
+    /// Per-user PDA seed: the counter index, or created_at for pledges created before the counter
+    pub fn seed(&self) -> [u8; 8] {
+        match self.index {
+            Some(index) => index.to_le_bytes(),
+            None => self.created_at.to_le_bytes(),
+        }
+    }
 }
 
 #[event]
@@ -49,18 +176,34 @@ pub struct PledgeCreated {
     pub user: Pubkey,
     pub stake_amount: u64,
     pub deadline: i64,
+    pub charity: Option<Pubkey>,
+    pub beneficiary: Option<Pubkey>,
+    pub beneficiary_bps: u16,
+    pub metadata: GoalMetadata,
+    pub grace_period_seconds: Option<i64>,
+    pub default_outcome: DefaultOutcome,
+    pub funder: Option<Pubkey>,
+    pub refund_to_user: bool,
+    pub challenge: Option<Pubkey>,
+    pub team: Option<TeamReporting>,
+    pub rent_payer: Option<Pubkey>,
+    pub refund_account: Option<Pubkey>,
 }
 
 #[event]
 pub struct PledgeEdited {
     pub pledge: Pubkey,
     pub penalty_paid: u64,
+    pub charity: Option<Pubkey>,
+    pub metadata: GoalMetadata,
+    pub metadata_version: u32,
 }
 
 #[event]
 pub struct CompletionReported {
     pub pledge: Pubkey,
     pub completion_percentage: u8,
+    pub late_penalty_bps: u16,
 }
 
 #[event]
@@ -69,11 +212,39 @@ pub struct PledgeCompleted {
     pub completion_percentage: u8,
     pub refund_amount: u64,
     pub fee_amount: u64,
+    pub charity: Option<Pubkey>,
+    pub beneficiary_amount: u64,
+    pub crank_reward: u64,
+    pub sponsor: Option<Pubkey>,
+    pub sponsorship_amount: u64, // Bonus deposited by the sponsor
+    pub sponsor_bonus: u64,      // Part of it paid with the refund (the rest went back)
 }
 
 #[event]
 pub struct PledgeForfeited {
     pub pledge: Pubkey,
-    pub treasury_amount: u64,
-    pub charity_amount: u64,
+    pub revenue_amounts: Vec<u64>, // Per revenue share, in config order
+    pub charity: Option<Pubkey>,
+    pub beneficiary: Option<Pubkey>,
+    pub beneficiary_amount: u64,
+    pub crank_reward: u64,
+    pub sponsor: Option<Pubkey>,
+    pub sponsorship_amount: u64, // Bonus returned to the sponsor
+}
+
+#[event]
+pub struct PledgeMigrated {
+    pub pledge: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct BatchProcessed {
+    pub mint: Pubkey,
+    pub processed: u32,
+    pub skipped: Vec<Pubkey>, // Ineligible pledges left for single-pledge instructions
+    pub total_refunded: u64,
+    pub total_to_fee_vault: u64,
+    pub total_crank_rewards: u64,
 }
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{RECOVERY_CHALLENGE_SECONDS, RECOVERY_INACTIVITY_SECONDS};
+use crate::errors::ErrorCode;
+use crate::state::Pledge;
+
+/// Recovery address registered on the user's profile - takes over the user's open pledges
+/// once the wallet has been inactive for RECOVERY_INACTIVITY_SECONDS and the owner let
+/// the RECOVERY_CHALLENGE_SECONDS challenge window pass without cancelling. Owner-signed
+/// pledge instructions count as activity.
+#[account]
+pub struct Recovery {
+    pub user: Pubkey,              // Wallet being protected
+    pub recovery_address: Pubkey,  // Wallet that may take over the user's pledges
+    pub last_active: i64,          // Owner's last check-in (any owner-signed pledge instruction)
+    pub initiated_at: Option<i64>, // Pending recovery started (None = none pending)
+    pub bump: u8,
+}
+
+impl Recovery {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // user
+        32 +    // recovery_address
+        8 +     // last_active
+        1 + 8 + // initiated_at (Option<i64>)
+        1; // bump
+
+    /// When a started recovery becomes final (None = none pending)
+    pub fn finalizes_at(&self) -> Option<i64> {
+        self.initiated_at
+            .map(|initiated_at| initiated_at.saturating_add(RECOVERY_CHALLENGE_SECONDS))
+    }
+
+    /// Whether a started recovery outlasted its challenge window
+    pub fn is_final(&self, now: i64) -> bool {
+        self.finalizes_at().is_some_and(|finalizes_at| now >= finalizes_at)
+    }
+
+    /// Start a recovery once the owner has been inactive long enough - returns when it finalizes
+    pub fn initiate(&mut self, signer: Pubkey, now: i64) -> Result<i64> {
+        require!(signer == self.recovery_address, ErrorCode::InvalidRecovery);
+        require!(self.initiated_at.is_none(), ErrorCode::RecoveryPending);
+        let due_at = self
+            .last_active
+            .checked_add(RECOVERY_INACTIVITY_SECONDS)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(now >= due_at, ErrorCode::RecoveryNotDue);
+
+        let finalizes_at = now
+            .checked_add(RECOVERY_CHALLENGE_SECONDS)
+            .ok_or(ErrorCode::Overflow)?;
+        self.initiated_at = Some(now);
+        Ok(finalizes_at)
+    }
+
+    /// Owner cancels a pending recovery during the challenge window (counts as activity)
+    pub fn cancel(&mut self, now: i64) -> Result<()> {
+        require!(self.initiated_at.is_some(), ErrorCode::RecoveryNotPending);
+        require!(!self.is_final(now), ErrorCode::RecoveryFinalized);
+
+        self.initiated_at = None;
+        self.last_active = now;
+        Ok(())
+    }
+
+    /// Owner activity restarts the inactivity window and cancels a recovery that isn't final
+    /// yet - returns whether it cancelled one
+    pub fn record_activity(&mut self, now: i64) -> bool {
+        let cancelled = self.initiated_at.is_some() && !self.is_final(now);
+        if cancelled {
+            self.initiated_at = None;
+        }
+        self.last_active = now;
+        cancelled
+    }
+
+    /// Record owner activity on the user's recovery profile (no-op if they never set one)
+    pub fn record_activity_on(recovery_info: &AccountInfo, now: i64) -> Result<()> {
+        if recovery_info.owner != &crate::ID || recovery_info.data_is_empty() {
+            return Ok(());
+        }
+
+        let mut data = recovery_info.try_borrow_mut_data()?;
+        let mut recovery = Recovery::try_deserialize(&mut &data[..])?;
+        if recovery.record_activity(now) {
+            emit!(RecoveryCancelled {
+                user: recovery.user,
+                recovery_address: recovery.recovery_address,
+            });
+        }
+        recovery.try_serialize(&mut &mut data[..])
+    }
+
+    /// Whether a started recovery still has to decide where `pledge`'s refund goes - a covered
+    /// pledge refunding the owner waits for the owner to cancel or the recovery address to claim it
+    pub fn holds_settlement(&self, pledge: &Pledge) -> bool {
+        pledge.refund_recipient() == pledge.user
+            && self
+                .finalizes_at()
+                .is_some_and(|finalizes_at| pledge.created_at < finalizes_at)
+    }
+
+    /// Whether the owner's recovery profile holds back `pledge`'s settlement (false if they
+    /// never set one)
+    pub fn holds_settlement_on(recovery_info: &AccountInfo, pledge: &Pledge) -> Result<bool> {
+        if recovery_info.owner != &crate::ID || recovery_info.data_is_empty() {
+            return Ok(false);
+        }
+
+        let recovery = Recovery::try_deserialize(&mut &recovery_info.try_borrow_data()?[..])?;
+        Ok(recovery.holds_settlement(pledge))
+    }
+
+    /// Check `signer` may take over `user`'s pledge created at `created_at` - a final
+    /// recovery only covers pledges that existed when it became final
+    pub fn check_claimant(
+        &self,
+        signer: Pubkey,
+        user: Pubkey,
+        created_at: i64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            self.user == user && self.recovery_address == signer,
+            ErrorCode::InvalidRecovery
+        );
+        require!(self.is_final(now), ErrorCode::RecoveryNotFinal);
+        require!(
+            self.finalizes_at().is_some_and(|finalizes_at| created_at < finalizes_at),
+            ErrorCode::PledgeAfterRecovery
+        );
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RecoveryAddressSet {
+    pub user: Pubkey,
+    pub recovery_address: Pubkey,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub user: Pubkey,
+    pub recovery_address: Pubkey,
+    pub finalizes_at: i64, // Owner can cancel until then
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub user: Pubkey,
+    pub recovery_address: Pubkey,
+}
+
+#[event]
+pub struct PledgeRecovered {
+    pub pledge: Pubkey,
+    pub user: Pubkey,
+    pub recovery_address: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PledgeStatus;
+    use crate::utils::migration::PledgeV0;
+
+    #[test]
+    fn test_recovery_waits_for_inactivity_and_challenge_window() {
+        let user = Pubkey::new_unique();
+        let recovery_address = Pubkey::new_unique();
+        let mut recovery = Recovery {
+            user,
+            recovery_address,
+            last_active: 1_700_000_000,
+            initiated_at: None,
+            bump: 255,
+        };
+        let due_at = 1_700_000_000 + RECOVERY_INACTIVITY_SECONDS;
+
+        assert!(recovery.initiate(recovery_address, due_at - 1).is_err());
+        assert!(recovery.initiate(Pubkey::new_unique(), due_at).is_err());
+        assert!(recovery.cancel(due_at).is_err());
+
+        // Owner cancels within the challenge window, restarting the inactivity clock
+        recovery.initiate(recovery_address, due_at).unwrap();
+        assert!(recovery.initiate(recovery_address, due_at).is_err());
+        recovery.cancel(due_at + 1).unwrap();
+        assert_eq!(recovery.last_active, due_at + 1);
+        assert!(recovery.initiate(recovery_address, due_at + 1).is_err());
+
+        // Owner activity during the challenge window cancels the recovery too
+        let started = due_at + 1 + RECOVERY_INACTIVITY_SECONDS;
+        recovery.initiate(recovery_address, started).unwrap();
+        assert!(recovery.record_activity(started + 1));
+        assert_eq!(recovery.initiated_at, None);
+        assert_eq!(recovery.last_active, started + 1);
+
+        // Unchallenged recovery becomes final and can no longer be cancelled
+        let started = started + 1 + RECOVERY_INACTIVITY_SECONDS;
+        let finalizes_at = recovery.initiate(recovery_address, started).unwrap();
+        let created_at = started - 1;
+        assert!(recovery
+            .check_claimant(recovery_address, user, created_at, finalizes_at - 1)
+            .is_err());
+        assert!(recovery
+            .check_claimant(recovery_address, user, created_at, finalizes_at)
+            .is_ok());
+        assert!(recovery.check_claimant(user, user, created_at, finalizes_at).is_err());
+        assert!(recovery.cancel(finalizes_at).is_err());
+
+        // Pledges created once it became final stay with the owner, whose activity no
+        // longer undoes it
+        assert!(recovery
+            .check_claimant(recovery_address, user, finalizes_at, finalizes_at)
+            .is_err());
+        assert!(!recovery.record_activity(finalizes_at));
+        assert!(recovery.is_final(finalizes_at));
+    }
+
+    #[test]
+    fn test_pending_recovery_holds_settlement_until_claimed() {
+        let user = Pubkey::new_unique();
+        let recovery_address = Pubkey::new_unique();
+        let mut recovery = Recovery {
+            user,
+            recovery_address,
+            last_active: 1_700_000_000,
+            initiated_at: None,
+            bump: 255,
+        };
+        let mut pledge = PledgeV0 {
+            user,
+            mint: Pubkey::new_unique(),
+            stake_amount: 10_000_000,
+            deadline: 1_700_086_400,
+            status: PledgeStatus::Active,
+            completion_percentage: None,
+            reported_at: None,
+            created_at: 1_700_000_000,
+            bump: 254,
+            vault_bump: 253,
+        }
+        .upgrade();
+        assert!(!recovery.holds_settlement(&pledge));
+
+        // The pledge expires while a started recovery is still in its challenge window
+        let started = 1_700_000_000 + RECOVERY_INACTIVITY_SECONDS;
+        let finalizes_at = recovery.initiate(recovery_address, started).unwrap();
+        assert!(pledge.deadline < started);
+        assert!(recovery.holds_settlement(&pledge));
+
+        // Still held once final, until the recovery address takes the pledge over
+        assert!(recovery.is_final(finalizes_at));
+        assert!(recovery.holds_settlement(&pledge));
+        recovery
+            .check_claimant(recovery_address, user, pledge.created_at, finalizes_at)
+            .unwrap();
+        pledge.recovery = Some(recovery_address);
+        assert!(!recovery.holds_settlement(&pledge));
+        assert_eq!(pledge.refund_recipient(), recovery_address);
+
+        // A funder's refund never reaches the lost wallet, so it is not held
+        pledge.recovery = None;
+        pledge.funder = Some(Pubkey::new_unique());
+        assert!(!recovery.holds_settlement(&pledge));
+
+        // Pledges created after the recovery became final stay with the owner
+        pledge.funder = None;
+        pledge.created_at = finalizes_at;
+        assert!(!recovery.holds_settlement(&pledge));
+    }
+}
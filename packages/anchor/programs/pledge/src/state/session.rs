@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Delegated session key - may report completion for the user's pledges, never move funds
+#[account]
+pub struct SessionAuthority {
+    pub user: Pubkey,           // Wallet that registered the key
+    pub session_key: Pubkey,    // Key allowed to sign on the user's behalf
+    pub pledge: Option<Pubkey>, // Only this pledge (None = every pledge of the user)
+    pub expires_at: i64,        // Key stops working at this time
+    pub bump: u8,
+}
+
+impl SessionAuthority {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // user
+        32 +    // session_key
+        1 + 32 + // pledge (Option<Pubkey>)
+        8 +     // expires_at
+        1; // bump
+
+    /// Check `signer` may act for `user` on `pledge` at `now`
+    pub fn check(&self, signer: Pubkey, user: Pubkey, pledge: Pubkey, now: i64) -> Result<()> {
+        require!(
+            self.user == user
+                && self.session_key == signer
+                && self.pledge.is_none_or(|scoped| scoped == pledge),
+            ErrorCode::InvalidSession
+        );
+        require!(now < self.expires_at, ErrorCode::SessionExpired);
+        Ok(())
+    }
+}
+
+#[event]
+pub struct SessionCreated {
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+    pub pledge: Option<Pubkey>,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub user: Pubkey,
+    pub session_key: Pubkey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_is_scoped_and_expires() {
+        let user = Pubkey::new_unique();
+        let pledge = Pubkey::new_unique();
+        let mut session = SessionAuthority {
+            user,
+            session_key: Pubkey::new_unique(),
+            pledge: None,
+            expires_at: 1_700_000_000,
+            bump: 255,
+        };
+        let key = session.session_key;
+
+        assert!(session.check(key, user, pledge, 1_699_999_999).is_ok());
+        assert!(session.check(key, user, pledge, 1_700_000_000).is_err());
+        assert!(session.check(Pubkey::new_unique(), user, pledge, 0).is_err());
+        assert!(session.check(key, Pubkey::new_unique(), pledge, 0).is_err());
+
+        session.pledge = Some(pledge);
+        assert!(session.check(key, user, pledge, 0).is_ok());
+        assert!(session.check(key, user, Pubkey::new_unique(), 0).is_err());
+    }
+}
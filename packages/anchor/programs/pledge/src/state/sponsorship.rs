@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+#[account]
+pub struct Sponsorship {
+    pub pledge: Pubkey,  // Pledge the bonus rides on
+    pub sponsor: Pubkey, // Wallet that deposited the bonus and gets back what isn't earned
+    pub vault: Pubkey,   // Sponsor vault holding the bonus (authority: the pledge PDA)
+    pub amount: u64,     // Total bonus deposited
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Sponsorship {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // pledge
+        32 +    // sponsor
+        32 +    // vault
+        8 +     // amount
+        1 +     // bump
+        1; // vault_bump
+
+    /// Bonus earned at a completion percentage - the full amount at 100%, pro-rata below
+    pub fn bonus_for(&self, completion_percentage: u8) -> Result<u64> {
+        require!(
+            completion_percentage <= 100,
+            ErrorCode::InvalidCompletionPercentage
+        );
+        Ok(self
+            .amount
+            .checked_mul(completion_percentage as u64)
+            .ok_or(ErrorCode::Overflow)?
+            / 100)
+    }
+}
+
+#[event]
+pub struct SponsorshipAdded {
+    pub pledge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub total: u64, // Bonus deposited so far
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bonus_is_pro_rata_to_completion() {
+        let sponsorship = Sponsorship {
+            pledge: Pubkey::new_unique(),
+            sponsor: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+            amount: 5_000_000,
+            bump: 255,
+            vault_bump: 254,
+        };
+
+        assert_eq!(sponsorship.bonus_for(100).unwrap(), 5_000_000);
+        assert_eq!(sponsorship.bonus_for(60).unwrap(), 3_000_000);
+        assert_eq!(sponsorship.bonus_for(0).unwrap(), 0);
+        assert!(sponsorship.bonus_for(101).is_err());
+    }
+}
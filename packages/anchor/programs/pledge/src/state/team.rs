@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+
+/// Who reports a team pledge's completion
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum TeamReporting {
+    Captain,    // The pledge owner reports, as for a solo pledge
+    Quorum(u8), // This many contributors vote; the lowest voted percentage is reported
+}
+
+impl TeamReporting {
+    /// Check a quorum needs at least one vote
+    pub fn validate(&self) -> Result<()> {
+        if let TeamReporting::Quorum(quorum) = self {
+            require!(*quorum > 0, ErrorCode::InvalidTeam);
+        }
+        Ok(())
+    }
+}
+
+/// Team pledge bookkeeping - the vault holds the captain's stake plus every contribution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TeamState {
+    pub reporting: TeamReporting, // Captain or contributor quorum
+    pub captain_stake: u64,       // Captain's own share of pledge.stake_amount
+    pub contributors: u32,        // Contribution records created
+    pub votes: u32,               // Quorum votes cast
+    pub lowest_vote: u8,          // Lowest percentage voted so far (valid once votes > 0)
+    pub refund_pool: u64,         // Contributors' refunds left in the vault at settlement
+    pub claimed: u32,             // Contributors that claimed their refund
+}
+
+impl TeamState {
+    pub fn new(reporting: TeamReporting, captain_stake: u64) -> Self {
+        Self {
+            reporting,
+            captain_stake,
+            contributors: 0,
+            votes: 0,
+            lowest_vote: 0,
+            refund_pool: 0,
+            claimed: 0,
+        }
+    }
+
+    /// Count a contributor vote - returns the percentage to report once the quorum is reached
+    pub fn record_vote(&mut self, completion_percentage: u8) -> Result<Option<u8>> {
+        let TeamReporting::Quorum(quorum) = self.reporting else {
+            return err!(ErrorCode::InvalidTeamReport);
+        };
+
+        self.lowest_vote = if self.votes == 0 {
+            completion_percentage
+        } else {
+            self.lowest_vote.min(completion_percentage)
+        };
+        self.votes = self.votes.checked_add(1).ok_or(ErrorCode::Overflow)?;
+
+        Ok((self.votes >= quorum as u32).then_some(self.lowest_vote))
+    }
+
+    /// Captain's part of a pledge-wide amount, proportional to the captain's stake
+    pub fn captain_share(&self, amount: u64, stake_amount: u64) -> Result<u64> {
+        proportional(amount, self.captain_stake, stake_amount)
+    }
+
+    /// Next contributor's refund - the last claimant also takes the rounding dust
+    pub fn next_refund(
+        &self,
+        contribution: u64,
+        stake_amount: u64,
+        vault_balance: u64,
+    ) -> Result<u64> {
+        require!(self.claimed < self.contributors, ErrorCode::InvalidContributor);
+        if self.claimed + 1 == self.contributors {
+            return Ok(vault_balance);
+        }
+        let contributed = stake_amount
+            .checked_sub(self.captain_stake)
+            .ok_or(ErrorCode::Underflow)?;
+        proportional(self.refund_pool, contribution, contributed)
+    }
+}
+
+/// amount * part / whole without intermediate overflow
+fn proportional(amount: u64, part: u64, whole: u64) -> Result<u64> {
+    if whole == 0 {
+        return Ok(0);
+    }
+    let share = (amount as u128)
+        .checked_mul(part as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / whole as u128;
+    u64::try_from(share).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+#[account]
+pub struct Contribution {
+    pub pledge: Pubkey,      // Team pledge contributed to
+    pub contributor: Pubkey, // Wallet that staked and receives the refund share
+    pub amount: u64,         // Tokens contributed to the pledge vault
+    pub voted: bool,         // Cast a quorum vote
+    pub bump: u8,
+}
+
+impl Contribution {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // pledge
+        32 +    // contributor
+        8 +     // amount
+        1 +     // voted
+        1; // bump
+}
+
+#[event]
+pub struct TeamContributed {
+    pub pledge: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub stake_amount: u64, // Pledge total after the contribution
+}
+
+#[event]
+pub struct TeamVoteCast {
+    pub pledge: Pubkey,
+    pub contributor: Pubkey,
+    pub completion_percentage: u8,
+    pub votes: u32,
+}
+
+#[event]
+pub struct TeamRefundClaimed {
+    pub pledge: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quorum_reports_lowest_vote() {
+        let mut team = TeamState::new(TeamReporting::Quorum(3), 10_000_000);
+
+        assert_eq!(team.record_vote(100).unwrap(), None);
+        assert_eq!(team.record_vote(60).unwrap(), None);
+        assert_eq!(team.record_vote(80).unwrap(), Some(60));
+
+        let mut captain_team = TeamState::new(TeamReporting::Captain, 10_000_000);
+        assert!(captain_team.record_vote(100).is_err());
+    }
+
+    #[test]
+    fn test_refunds_split_by_contribution() {
+        // Captain staked 10, contributors 20 and 10 - 30 USDC refunded at 75%
+        let stake_amount = 40_000_000;
+        let mut team = TeamState::new(TeamReporting::Captain, 10_000_000);
+        team.contributors = 2;
+
+        let captain_refund = team.captain_share(30_000_000, stake_amount).unwrap();
+        assert_eq!(captain_refund, 7_500_000);
+        team.refund_pool = 30_000_000 - captain_refund;
+
+        let first = team.next_refund(20_000_000, stake_amount, team.refund_pool).unwrap();
+        assert_eq!(first, 15_000_000);
+        team.claimed = 1;
+
+        // Last claimant takes what is left in the vault
+        let last = team
+            .next_refund(10_000_000, stake_amount, team.refund_pool - first)
+            .unwrap();
+        assert_eq!(last, 7_500_000);
+        team.claimed = 2;
+        assert!(team.next_refund(10_000_000, stake_amount, 0).is_err());
+    }
+}
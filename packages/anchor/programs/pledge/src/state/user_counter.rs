@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::LIMITS_WINDOW_SECONDS;
+use crate::errors::ErrorCode;
+use crate::state::{Pledge, PledgeLimits};
+
+#[account]
+pub struct UserCounter {
+    pub user: Pubkey,         // Owner of the counted pledges
+    pub next_index: u64,      // Seed index of the user's next pledge
+    pub active_pledges: u32,  // Unsettled pledges created through this counter
+    pub active_stake: u64,    // Total stake still held in those pledges' vaults
+    pub window_start: i64,    // Start of the current LIMITS_WINDOW_SECONDS window
+    pub window_stake: u64,    // Stake of pledges created in the window
+    pub window_forfeited: u64, // Stake lost (fees, penalties, forfeitures) in the window
+    pub bump: u8,
+}
+
+impl UserCounter {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // user
+        8 +     // next_index
+        4 +     // active_pledges
+        8 +     // active_stake
+        8 +     // window_start
+        8 +     // window_stake
+        8 +     // window_forfeited
+        1; // bump
+
+    /// Hand out the next pledge index
+    pub fn claim_index(&mut self) -> Result<u64> {
+        let index = self.next_index;
+        self.next_index = index.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        Ok(index)
+    }
+
+    /// Start a fresh window once the current one has run its course
+    pub fn roll_window(&mut self, now: i64) {
+        if now.saturating_sub(self.window_start) >= LIMITS_WINDOW_SECONDS {
+            self.window_start = now;
+            self.window_stake = 0;
+            self.window_forfeited = 0;
+        }
+    }
+
+    /// Count a new pledge against the per-user exposure limits
+    /// (call roll_window first)
+    pub fn open_pledge(&mut self, stake_amount: u64, limits: &PledgeLimits) -> Result<()> {
+        let active_pledges = self
+            .active_pledges
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            limits.max_active_pledges == 0 || active_pledges <= limits.max_active_pledges,
+            ErrorCode::TooManyActivePledges
+        );
+
+        let active_stake = self
+            .active_stake
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            limits.max_active_stake == 0 || active_stake <= limits.max_active_stake,
+            ErrorCode::ActiveStakeLimitExceeded
+        );
+
+        self.active_pledges = active_pledges;
+        self.active_stake = active_stake;
+        self.window_stake = self.window_stake.saturating_add(stake_amount);
+        Ok(())
+    }
+
+    /// Stop counting stake that left a pledge's vault (and the pledge itself once settled),
+    /// tallying the part the user lost in the current window
+    pub fn release(&mut self, stake_amount: u64, lost: u64, settled: bool, now: i64) {
+        self.roll_window(now);
+        self.active_stake = self.active_stake.saturating_sub(stake_amount);
+        self.window_forfeited = self.window_forfeited.saturating_add(lost);
+        if settled {
+            self.active_pledges = self.active_pledges.saturating_sub(1);
+        }
+    }
+
+    /// Release a pledge's stake from a counter passed as a raw account
+    /// Pledges created before the counter (no index) were never counted and are skipped
+    pub fn release_from(
+        counter_info: &AccountInfo,
+        pledge: &Pledge,
+        stake_amount: u64,
+        lost: u64,
+        settled: bool,
+    ) -> Result<()> {
+        if pledge.index.is_none()
+            || counter_info.owner != &crate::ID
+            || counter_info.data_is_empty()
+        {
+            return Ok(());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let mut data = counter_info.try_borrow_mut_data()?;
+        let mut counter = UserCounter::try_deserialize(&mut &data[..])?;
+        counter.release(stake_amount, lost, settled, now);
+        counter.try_serialize(&mut &mut data[..])
+    }
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::LIMIT_RAISE_DELAY_SECONDS;
+use crate::errors::ErrorCode;
+use crate::state::UserCounter;
+
+/// Self-imposed caps (0 = no cap)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, InitSpace)]
+pub struct UserCaps {
+    pub max_monthly_stake: u64,      // Total stake across pledges created in the window
+    pub max_single_stake: u64,       // Stake of any one pledge
+    pub max_monthly_forfeiture: u64, // Losses in the window plus stake still at risk
+}
+
+impl UserCaps {
+    /// Tighten each cap to the stricter of the current and target values
+    fn tighten_to(&mut self, target: &UserCaps) {
+        self.max_monthly_stake = stricter(self.max_monthly_stake, target.max_monthly_stake);
+        self.max_single_stake = stricter(self.max_single_stake, target.max_single_stake);
+        self.max_monthly_forfeiture =
+            stricter(self.max_monthly_forfeiture, target.max_monthly_forfeiture);
+    }
+}
+
+/// Stricter of two caps where 0 means no cap
+fn stricter(a: u64, b: u64) -> u64 {
+    match (a, b) {
+        (0, cap) | (cap, 0) => cap,
+        (a, b) => a.min(b),
+    }
+}
+
+#[account]
+pub struct UserLimits {
+    pub user: Pubkey,              // User who set these limits
+    pub caps: UserCaps,            // Caps in effect
+    pub pending_caps: UserCaps,    // Raised caps waiting out the delay
+    pub pending_effective_at: i64, // When pending_caps take effect (0 = nothing pending)
+    pub excluded_until: i64,       // Self-exclusion: no new pledges before this time
+    pub bump: u8,
+}
+
+impl UserLimits {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // user
+        UserCaps::INIT_SPACE + // caps
+        UserCaps::INIT_SPACE + // pending_caps
+        8 +     // pending_effective_at
+        8 +     // excluded_until
+        1; // bump
+
+    /// Promote pending caps once their delay has passed
+    pub fn apply_pending(&mut self, now: i64) {
+        if self.pending_effective_at != 0 && now >= self.pending_effective_at {
+            self.caps = self.pending_caps;
+            self.pending_effective_at = 0;
+        }
+    }
+
+    /// Move towards the requested caps: lowering applies now, raising waits
+    /// LIMIT_RAISE_DELAY_SECONDS (restarted by every change)
+    pub fn request_caps(
+        &mut self,
+        max_monthly_stake: Option<u64>,
+        max_single_stake: Option<u64>,
+        max_monthly_forfeiture: Option<u64>,
+        now: i64,
+    ) -> Result<()> {
+        self.apply_pending(now);
+
+        let mut target = if self.pending_effective_at != 0 {
+            self.pending_caps
+        } else {
+            self.caps
+        };
+        if let Some(cap) = max_monthly_stake {
+            target.max_monthly_stake = cap;
+        }
+        if let Some(cap) = max_single_stake {
+            target.max_single_stake = cap;
+        }
+        if let Some(cap) = max_monthly_forfeiture {
+            target.max_monthly_forfeiture = cap;
+        }
+
+        self.caps.tighten_to(&target);
+
+        if target == self.caps {
+            self.pending_effective_at = 0;
+        } else {
+            self.pending_caps = target;
+            self.pending_effective_at = now
+                .checked_add(LIMIT_RAISE_DELAY_SECONDS)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Extend self-exclusion (it can never be shortened)
+    pub fn exclude_for(&mut self, seconds: i64, now: i64) -> Result<()> {
+        require!(seconds > 0, ErrorCode::InvalidLimits);
+        let until = now.checked_add(seconds).ok_or(ErrorCode::Overflow)?;
+        self.excluded_until = self.excluded_until.max(until);
+        Ok(())
+    }
+
+    /// Check a new pledge against the caps, given the user's counter for the current window
+    pub fn check_pledge(&self, counter: &UserCounter, stake_amount: u64, now: i64) -> Result<()> {
+        require!(now >= self.excluded_until, ErrorCode::SelfExcluded);
+
+        let caps = &self.caps;
+        require!(
+            caps.max_single_stake == 0 || stake_amount <= caps.max_single_stake,
+            ErrorCode::SingleStakeLimitExceeded
+        );
+
+        let window_stake = counter
+            .window_stake
+            .checked_add(stake_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            caps.max_monthly_stake == 0 || window_stake <= caps.max_monthly_stake,
+            ErrorCode::MonthlyStakeLimitExceeded
+        );
+
+        // Worst case: every active pledge, and this one, is forfeited in full
+        let at_risk = counter
+            .window_forfeited
+            .checked_add(counter.active_stake)
+            .and_then(|total| total.checked_add(stake_amount))
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            caps.max_monthly_forfeiture == 0 || at_risk <= caps.max_monthly_forfeiture,
+            ErrorCode::ForfeitureLimitExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Enforce limits passed as a raw account (users who never set limits have none)
+    pub fn enforce_on(
+        limits_info: &AccountInfo,
+        counter: &UserCounter,
+        stake_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        if limits_info.owner != &crate::ID || limits_info.data_is_empty() {
+            return Ok(());
+        }
+
+        let mut data = limits_info.try_borrow_mut_data()?;
+        let mut limits = UserLimits::try_deserialize(&mut &data[..])?;
+        limits.apply_pending(now);
+        limits.check_pledge(counter, stake_amount, now)?;
+        limits.try_serialize(&mut &mut data[..])
+    }
+}
+
+#[event]
+pub struct UserLimitsUpdated {
+    pub user: Pubkey,
+    pub caps: UserCaps,
+    pub pending_caps: Option<UserCaps>,
+    pub pending_effective_at: Option<i64>,
+    pub excluded_until: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn limits() -> UserLimits {
+        UserLimits {
+            user: Pubkey::new_unique(),
+            caps: UserCaps::default(),
+            pending_caps: UserCaps::default(),
+            pending_effective_at: 0,
+            excluded_until: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_lowering_applies_immediately() {
+        let mut limits = limits();
+        limits.request_caps(Some(100), None, None, NOW).unwrap();
+        assert_eq!(limits.caps.max_monthly_stake, 100);
+        assert_eq!(limits.pending_effective_at, 0);
+
+        limits.request_caps(Some(50), None, None, NOW).unwrap();
+        assert_eq!(limits.caps.max_monthly_stake, 50);
+        assert_eq!(limits.pending_effective_at, 0);
+    }
+
+    #[test]
+    fn test_raising_waits_for_delay() {
+        let mut limits = limits();
+        limits.request_caps(None, Some(100), None, NOW).unwrap();
+
+        // Raising, including removing the cap (0), is queued
+        limits.request_caps(None, Some(0), None, NOW).unwrap();
+        assert_eq!(limits.caps.max_single_stake, 100);
+        assert_eq!(limits.pending_caps.max_single_stake, 0);
+        assert_eq!(limits.pending_effective_at, NOW + LIMIT_RAISE_DELAY_SECONDS);
+
+        limits.apply_pending(NOW + LIMIT_RAISE_DELAY_SECONDS - 1);
+        assert_eq!(limits.caps.max_single_stake, 100);
+
+        limits.apply_pending(NOW + LIMIT_RAISE_DELAY_SECONDS);
+        assert_eq!(limits.caps.max_single_stake, 0);
+        assert_eq!(limits.pending_effective_at, 0);
+    }
+
+    #[test]
+    fn test_lowering_replaces_pending_raise() {
+        let mut limits = limits();
+        limits.request_caps(Some(100), Some(100), None, NOW).unwrap();
+        limits.request_caps(Some(200), None, None, NOW).unwrap();
+
+        // Lowering the other cap keeps the pending raise, but restarts its delay
+        limits.request_caps(None, Some(50), None, NOW + 10).unwrap();
+        assert_eq!(limits.caps.max_single_stake, 50);
+        assert_eq!(limits.caps.max_monthly_stake, 100);
+        assert_eq!(limits.pending_caps.max_monthly_stake, 200);
+        assert_eq!(limits.pending_effective_at, NOW + 10 + LIMIT_RAISE_DELAY_SECONDS);
+
+        // Lowering the raised cap back cancels the raise
+        limits.request_caps(Some(100), None, None, NOW + 20).unwrap();
+        assert_eq!(limits.caps.max_monthly_stake, 100);
+        assert_eq!(limits.pending_effective_at, 0);
+    }
+
+    #[test]
+    fn test_exclusion_cannot_be_shortened() {
+        let mut limits = limits();
+        limits.exclude_for(1000, NOW).unwrap();
+        limits.exclude_for(10, NOW).unwrap();
+        assert_eq!(limits.excluded_until, NOW + 1000);
+        assert!(limits.exclude_for(0, NOW).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, transfer, Allocate, Assign, Transfer};
+use std::slice::ChunksExact;
+
+/// Split remaining accounts into groups of `group_len` - None when they don't divide evenly
+pub fn account_groups<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    group_len: usize,
+) -> Option<ChunksExact<'a, AccountInfo<'info>>> {
+    let groups = accounts.chunks_exact(group_len);
+    groups.remainder().is_empty().then_some(groups)
+}
+
+/// Create a program-owned PDA of `space` bytes, funding its rent from `payer`
+/// Works whether or not the address already holds lamports
+pub fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent_needed = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent_needed > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        transfer(transfer_ctx, rent_needed)?;
+    }
+
+    let allocate_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Allocate {
+            account_to_allocate: account.clone(),
+        },
+        signer_seeds,
+    );
+    allocate(allocate_ctx, space as u64)?;
+
+    let assign_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Assign {
+            account_to_assign: account.clone(),
+        },
+        signer_seeds,
+    );
+    assign(assign_ctx, &crate::ID)
+}
@@ -37,8 +37,8 @@ pub fn calculate_partial_refund(
     Ok((refund, fee))
 }
 
-/// Calculate treasury/charity split for forfeitures/fees
-/// Returns (treasury_amount, charity_amount)
+/// Calculate a two-way bps split (first part by bps, remainder to the second)
+/// Returns (first_amount, second_amount)
 pub fn calculate_split(amount: u64, treasury_split_bps: u16) -> Result<(u64, u64)> {
     let treasury = amount
         .checked_mul(treasury_split_bps as u64)
@@ -51,6 +51,52 @@ pub fn calculate_split(amount: u64, treasury_split_bps: u16) -> Result<(u64, u64
     Ok((treasury, charity))
 }
 
+/// Split an amount across N recipients by bps, in order
+/// The last recipient absorbs rounding dust so the parts always sum to `amount`
+pub fn calculate_revenue_split(amount: u64, shares_bps: &[u16]) -> Result<Vec<u64>> {
+    let mut amounts = Vec::with_capacity(shares_bps.len());
+    let mut remaining = amount;
+
+    for (index, bps) in shares_bps.iter().enumerate() {
+        let share = if index + 1 == shares_bps.len() {
+            remaining
+        } else {
+            amount
+                .checked_mul(*bps as u64)
+                .ok_or(ErrorCode::Overflow)?
+                .checked_div(BPS_DENOMINATOR)
+                .ok_or(ErrorCode::Overflow)?
+        };
+        remaining = remaining.checked_sub(share).ok_or(ErrorCode::Underflow)?;
+        amounts.push(share);
+    }
+
+    Ok(amounts)
+}
+
+/// Calculate the late report penalty, ramping linearly from 0 at the end of the
+/// grace period to `max_penalty_bps` at the end of the late window
+pub fn calculate_late_penalty_bps(
+    seconds_late: i64,
+    window_seconds: i64,
+    max_penalty_bps: u16,
+) -> Result<u16> {
+    if seconds_late <= 0 {
+        return Ok(0);
+    }
+    if seconds_late >= window_seconds {
+        return Ok(max_penalty_bps);
+    }
+
+    let penalty_bps = (max_penalty_bps as i64)
+        .checked_mul(seconds_late)
+        .ok_or(ErrorCode::Overflow)?
+        .checked_div(window_seconds)
+        .ok_or(ErrorCode::Overflow)?;
+
+    Ok(penalty_bps as u16)
+}
+
 /// Calculate edit penalty
 pub fn calculate_edit_penalty(remaining_stake: u64, penalty_bps: u16) -> Result<u64> {
     Ok(remaining_stake
@@ -121,6 +167,40 @@ mod tests {
         assert_eq!(charity, 500_000);
     }
 
+    #[test]
+    fn test_revenue_split_matches_two_way_split() {
+        // 70/30 split of 1,000,000 via the N-way split
+        let amounts = calculate_revenue_split(1_000_000, &[7000, 3000]).unwrap();
+        assert_eq!(amounts, vec![700_000, 300_000]);
+    }
+
+    #[test]
+    fn test_revenue_split_three_way_remainder_to_last() {
+        // 1,000,001 split 60/30/10 - rounding dust goes to the last recipient
+        let amounts = calculate_revenue_split(1_000_001, &[6000, 3000, 1000]).unwrap();
+        assert_eq!(amounts, vec![600_000, 300_000, 100_001]);
+        assert_eq!(amounts.iter().sum::<u64>(), 1_000_001);
+    }
+
+    #[test]
+    fn test_late_penalty_ramps_over_window() {
+        // 50% max penalty over a 1 day window
+        assert_eq!(calculate_late_penalty_bps(0, 86400, 5000).unwrap(), 0);
+        assert_eq!(calculate_late_penalty_bps(21600, 86400, 5000).unwrap(), 1250);
+        assert_eq!(calculate_late_penalty_bps(43200, 86400, 5000).unwrap(), 2500);
+        assert_eq!(calculate_late_penalty_bps(86400, 86400, 5000).unwrap(), 5000);
+        assert_eq!(calculate_late_penalty_bps(100_000, 86400, 5000).unwrap(), 5000);
+    }
+
+    #[test]
+    fn test_late_penalty_applied_to_refund() {
+        // 10 USDC refund, half way through the window at 50% max = 25% penalty
+        let penalty_bps = calculate_late_penalty_bps(43200, 86400, 5000).unwrap();
+        let (penalty, refund) = calculate_split(10_000_000, penalty_bps).unwrap();
+        assert_eq!(penalty, 2_500_000);
+        assert_eq!(refund, 7_500_000);
+    }
+
     #[test]
     fn test_invalid_completion_percentage() {
         let result = calculate_partial_refund(1_000_000, 101, 100);
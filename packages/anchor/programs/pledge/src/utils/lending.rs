@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+// Lending adapter interface - any program exposing these Anchor-style instructions
+// can hold pledge stakes once the admin selects it in config.yield_policy. The
+// adapter owns one position per (reserve, pledge) and must return at least the
+// principal on withdrawal; the pledge program checks vault balances either way.
+//
+//   deposit(amount: u64): owner (signer), payer (signer, mut), source (mut),
+//       reserve, reserve_vault (mut), position (mut), token_program, system_program
+//   withdraw(): owner (signer), rent_receiver (mut), destination (mut),
+//       reserve, reserve_vault (mut), position (mut), token_program
+const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182]; // sha256("global:deposit")
+const WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34]; // sha256("global:withdraw")
+
+/// Adapter-side accounts for a pledge's position (validated by the adapter)
+pub struct LendingAccounts<'info> {
+    pub adapter_program: AccountInfo<'info>,
+    pub reserve: AccountInfo<'info>,
+    pub reserve_vault: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Lend `amount` from the pledge vault, signed by the pledge PDA
+#[allow(clippy::too_many_arguments)]
+pub fn lending_deposit<'info>(
+    lending: &LendingAccounts<'info>,
+    owner: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = DEPOSIT_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: lending.adapter_program.key(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner.key(), true),
+            AccountMeta::new(payer.key(), true),
+            AccountMeta::new(source.key(), false),
+            AccountMeta::new_readonly(lending.reserve.key(), false),
+            AccountMeta::new(lending.reserve_vault.key(), false),
+            AccountMeta::new(lending.position.key(), false),
+            AccountMeta::new_readonly(lending.token_program.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            owner.clone(),
+            payer.clone(),
+            source.clone(),
+            lending.reserve.clone(),
+            lending.reserve_vault.clone(),
+            lending.position.clone(),
+            lending.token_program.clone(),
+            system_program.clone(),
+            lending.adapter_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Withdraw the whole position (principal plus yield) back into the pledge vault
+pub fn lending_withdraw<'info>(
+    lending: &LendingAccounts<'info>,
+    owner: &AccountInfo<'info>,
+    rent_receiver: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: lending.adapter_program.key(),
+        accounts: vec![
+            AccountMeta::new_readonly(owner.key(), true),
+            AccountMeta::new(rent_receiver.key(), false),
+            AccountMeta::new(destination.key(), false),
+            AccountMeta::new_readonly(lending.reserve.key(), false),
+            AccountMeta::new(lending.reserve_vault.key(), false),
+            AccountMeta::new(lending.position.key(), false),
+            AccountMeta::new_readonly(lending.token_program.key(), false),
+        ],
+        data: WITHDRAW_DISCRIMINATOR.to_vec(),
+    };
+    invoke_signed(
+        &instruction,
+        &[
+            owner.clone(),
+            rent_receiver.clone(),
+            destination.clone(),
+            lending.reserve.clone(),
+            lending.reserve_vault.clone(),
+            lending.position.clone(),
+            lending.token_program.clone(),
+            lending.adapter_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::constants::{
+    BPS_DENOMINATOR, CONFIG_RESERVED_BYTES, CONFIG_VERSION, DEFAULT_CHALLENGE_CUT_BPS,
+    DEFAULT_CRANK_REWARD_BPS, DEFAULT_MAX_BENEFICIARY_BPS, DEFAULT_MAX_DEFAULT_PERCENTAGE,
+    PLEDGE_RESERVED_BYTES, PLEDGE_VERSION,
+};
+use crate::errors::ErrorCode;
+use crate::state::{
+    DefaultOutcome, GoalMetadata, GracePeriodBounds, LateReportPolicy, Pledge, PledgeLimits,
+    PledgeStatus, ProgramConfig, RevenueShareParams, YieldPolicy,
+};
+
+/// Pledge layout before versioning, as deployed (same discriminator as the current layout)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PledgeV0 {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub stake_amount: u64,
+    pub deadline: i64,
+    pub status: PledgeStatus,
+    pub completion_percentage: Option<u8>,
+    pub reported_at: Option<i64>,
+    pub created_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl PledgeV0 {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // user
+        32 +    // mint
+        8 +     // stake_amount
+        8 +     // deadline
+        1 +     // status (enum)
+        1 + 1 + // completion_percentage (Option<u8>)
+        1 + 8 + // reported_at (Option<i64>)
+        8 +     // created_at
+        1 +     // bump
+        1; // vault_bump
+
+    /// Decode a pledge account still on the baseline layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        decode_legacy(data, Pledge::DISCRIMINATOR)
+    }
+
+    /// Map the baseline fields onto the current layout - everything added since
+    /// takes the value a pledge created without the feature would have
+    pub fn upgrade(self) -> Pledge {
+        Pledge {
+            user: self.user,
+            mint: self.mint,
+            stake_amount: self.stake_amount,
+            deadline: self.deadline,
+            status: self.status,
+            completion_percentage: self.completion_percentage,
+            reported_at: self.reported_at,
+            created_at: self.created_at,
+            charity: None,
+            beneficiary: None,
+            bump: self.bump,
+            vault_bump: self.vault_bump,
+            version: PLEDGE_VERSION,
+            index: None, // PDA stays seeded by created_at
+            metadata: GoalMetadata::default(),
+            metadata_version: 0,
+            early_report: false,
+            late_penalty_bps: 0,
+            grace_period_seconds: None,
+            default_outcome: DefaultOutcome::Attested,
+            funder: None,
+            refund_to_user: false,
+            challenge: None,
+            challenge_paid: false,
+            team: None,
+            sponsorship: 0,
+            yield_position: None,
+            rent_payer: None,
+            refund_account: None,
+            recovery: None,
+            deadline_shard: 0, // Baseline pledges were never indexed
+            beneficiary_bps: 0,
+            reserved: [0; PLEDGE_RESERVED_BYTES],
+        }
+    }
+}
+
+/// Config layout before versioning, as deployed (same discriminator as the current layout)
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProgramConfigV0 {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub charity: Pubkey,
+    pub treasury_split_bps: u16,
+    pub partial_fee_bps: u16,
+    pub edit_penalty_bps: u16,
+    pub grace_period_seconds: i64,
+    pub paused: bool,
+    pub bump: u8,
+}
+
+impl ProgramConfigV0 {
+    pub const INIT_SPACE: usize = 8 +  // discriminator
+        32 +    // admin
+        32 +    // treasury
+        32 +    // charity
+        2 +     // treasury_split_bps
+        2 +     // partial_fee_bps
+        2 +     // edit_penalty_bps
+        8 +     // grace_period_seconds
+        1 +     // paused
+        1; // bump
+
+    /// Decode a config account still on the baseline layout
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        decode_legacy(data, ProgramConfig::DISCRIMINATOR)
+    }
+
+    /// Map the baseline fields onto the current layout - the treasury/charity split
+    /// becomes two revenue shares, settings added since take their initialize defaults
+    pub fn upgrade(self) -> Result<ProgramConfig> {
+        let charity_bps = (BPS_DENOMINATOR as u16)
+            .checked_sub(self.treasury_split_bps)
+            .ok_or(ErrorCode::InvalidRevenueShares)?;
+
+        let mut config = ProgramConfig {
+            admin: self.admin,
+            revenue_shares: Vec::new(),
+            partial_fee_bps: self.partial_fee_bps,
+            edit_penalty_bps: self.edit_penalty_bps,
+            grace_period_seconds: self.grace_period_seconds,
+            paused: self.paused,
+            max_beneficiary_bps: DEFAULT_MAX_BENEFICIARY_BPS,
+            crank_reward_bps: DEFAULT_CRANK_REWARD_BPS,
+            bump: self.bump,
+            version: CONFIG_VERSION,
+            limits: PledgeLimits::default(),
+            allow_early_report: false,
+            late_report: LateReportPolicy::default(),
+            grace_bounds: GracePeriodBounds::default(),
+            challenge_cut_bps: DEFAULT_CHALLENGE_CUT_BPS,
+            yield_policy: YieldPolicy::default(),
+            max_default_percentage: DEFAULT_MAX_DEFAULT_PERCENTAGE,
+            reserved: [0; CONFIG_RESERVED_BYTES],
+        };
+        config.set_revenue_shares(&[
+            RevenueShareParams {
+                recipient: self.treasury,
+                bps: self.treasury_split_bps,
+                is_charity: false,
+            },
+            RevenueShareParams {
+                recipient: self.charity,
+                bps: charity_bps,
+                is_charity: true,
+            },
+        ])?;
+        Ok(config)
+    }
+}
+
+/// Borsh-decode a legacy account body after checking its discriminator
+fn decode_legacy<T: AnchorDeserialize>(data: &[u8], discriminator: &[u8]) -> Result<T> {
+    require!(
+        data.starts_with(discriminator),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    T::deserialize(&mut &data[discriminator.len()..])
+        .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Grow an account's data to `new_len` (zero-filled), topping up rent from `payer`
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    if account.data_len() >= new_len {
+        return Ok(());
+    }
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_needed > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+        );
+        transfer(transfer_ctx, rent_needed)?;
+    }
+
+    account.resize(new_len)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::RevenueShare;
+
+    /// Account data as stored on chain: discriminator + borsh, zero-padded to the account size
+    fn account_data<T: AnchorSerialize>(discriminator: &[u8], value: &T, space: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        value.serialize(&mut data).unwrap();
+        assert!(data.len() <= space);
+        data.resize(space, 0);
+        data
+    }
+
+    fn baseline_pledge() -> PledgeV0 {
+        PledgeV0 {
+            user: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            stake_amount: 10_000_000,
+            deadline: 1_700_086_400,
+            status: PledgeStatus::Reported,
+            completion_percentage: Some(50),
+            reported_at: Some(1_700_090_000),
+            created_at: 1_700_000_000,
+            bump: 254,
+            vault_bump: 253,
+        }
+    }
+
+    fn baseline_config() -> ProgramConfigV0 {
+        ProgramConfigV0 {
+            admin: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            charity: Pubkey::new_unique(),
+            treasury_split_bps: 7000,
+            partial_fee_bps: 100,
+            edit_penalty_bps: 1000,
+            grace_period_seconds: 86400,
+            paused: true,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_baseline_pledge_does_not_read_as_current_layout() {
+        // The baseline has no charity/beneficiary, so its bump lands on an Option tag
+        let mut data = account_data(Pledge::DISCRIMINATOR, &baseline_pledge(), PledgeV0::INIT_SPACE);
+        assert!(Pledge::try_deserialize(&mut &data[..]).is_err());
+        data.resize(Pledge::INIT_SPACE, 0);
+        assert!(Pledge::try_deserialize(&mut &data[..]).is_err());
+    }
+
+    #[test]
+    fn test_baseline_pledge_upgrades_field_by_field() {
+        let legacy = baseline_pledge();
+        let mut data = account_data(Pledge::DISCRIMINATOR, &legacy, PledgeV0::INIT_SPACE);
+        data.resize(Pledge::INIT_SPACE, 0);
+
+        let pledge = PledgeV0::try_from_account_data(&data).unwrap().upgrade();
+        assert_eq!(pledge.user, legacy.user);
+        assert_eq!(pledge.mint, legacy.mint);
+        assert_eq!(pledge.stake_amount, legacy.stake_amount);
+        assert_eq!(pledge.deadline, legacy.deadline);
+        assert!(pledge.status == PledgeStatus::Reported);
+        assert_eq!(pledge.completion_percentage, Some(50));
+        assert_eq!(pledge.reported_at, legacy.reported_at);
+        assert_eq!(pledge.created_at, legacy.created_at);
+        assert_eq!(pledge.charity, None);
+        assert_eq!(pledge.beneficiary, None);
+        assert_eq!(pledge.bump, 254);
+        assert_eq!(pledge.vault_bump, 253);
+        assert_eq!(pledge.version, PLEDGE_VERSION);
+        assert_eq!(pledge.index, None);
+        assert!(pledge.metadata == GoalMetadata::default());
+        assert_eq!(pledge.metadata_version, 0);
+        assert!(!pledge.early_report);
+        assert_eq!(pledge.late_penalty_bps, 0);
+        assert_eq!(pledge.grace_period_seconds, None);
+        assert!(pledge.default_outcome == DefaultOutcome::Attested);
+        assert_eq!(pledge.funder, None);
+        assert!(!pledge.refund_to_user);
+        assert_eq!(pledge.refund_recipient(), legacy.user);
+        assert_eq!(pledge.challenge, None);
+        assert!(!pledge.challenge_paid);
+        assert!(pledge.team.is_none());
+        assert!(pledge.captain_reports());
+        assert_eq!(pledge.sponsorship, 0);
+        assert!(pledge.yield_position.is_none());
+        assert_eq!(pledge.rent_payer, None);
+        assert_eq!(pledge.refund_account, None);
+        assert_eq!(pledge.recovery, None);
+        assert_eq!(pledge.deadline_shard, 0);
+        assert!(pledge.accepts_refund_account(Pubkey::new_unique()));
+        assert_eq!(pledge.seed(), legacy.created_at.to_le_bytes());
+        assert_eq!(pledge.reserved, [0; PLEDGE_RESERVED_BYTES]);
+
+        // Upgraded pledge fits the grown account
+        pledge.try_serialize(&mut &mut data[..]).unwrap();
+        let reloaded = Pledge::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.version, PLEDGE_VERSION);
+        assert_eq!(reloaded.created_at, legacy.created_at);
+        assert_eq!(reloaded.vault_bump, 253);
+    }
+
+    #[test]
+    fn test_baseline_config_upgrades_field_by_field() {
+        let legacy = baseline_config();
+        let mut data = account_data(
+            ProgramConfig::DISCRIMINATOR,
+            &legacy,
+            ProgramConfigV0::INIT_SPACE,
+        );
+        data.resize(ProgramConfig::INIT_SPACE, 0);
+
+        let config = ProgramConfigV0::try_from_account_data(&data)
+            .unwrap()
+            .upgrade()
+            .unwrap();
+        assert_eq!(config.admin, legacy.admin);
+        assert!(
+            config.revenue_shares
+                == vec![
+                    RevenueShare {
+                        recipient: legacy.treasury,
+                        bps: 7000,
+                        is_charity: false,
+                        accrued: 0,
+                    },
+                    RevenueShare {
+                        recipient: legacy.charity,
+                        bps: 3000,
+                        is_charity: true,
+                        accrued: 0,
+                    },
+                ]
+        );
+        assert_eq!(config.partial_fee_bps, 100);
+        assert_eq!(config.edit_penalty_bps, 1000);
+        assert_eq!(config.grace_period_seconds, 86400);
+        assert!(config.paused);
+        assert_eq!(config.max_beneficiary_bps, DEFAULT_MAX_BENEFICIARY_BPS);
+        assert_eq!(config.crank_reward_bps, DEFAULT_CRANK_REWARD_BPS);
+        assert_eq!(config.bump, 255);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.limits == PledgeLimits::default());
+        assert!(!config.allow_early_report);
+        assert!(config.late_report == LateReportPolicy::default());
+        assert!(config.grace_bounds == GracePeriodBounds::default());
+        assert_eq!(config.challenge_cut_bps, DEFAULT_CHALLENGE_CUT_BPS);
+        assert!(config.yield_policy == YieldPolicy::default());
+        assert_eq!(config.max_default_percentage, DEFAULT_MAX_DEFAULT_PERCENTAGE);
+        assert_eq!(config.reserved, [0; CONFIG_RESERVED_BYTES]);
+
+        // Upgraded config fits the grown account
+        config.try_serialize(&mut &mut data[..]).unwrap();
+        let reloaded = ProgramConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.version, CONFIG_VERSION);
+        assert_eq!(reloaded.revenue_shares.len(), 2);
+    }
+
+    #[test]
+    fn test_baseline_decode_checks_discriminator() {
+        let data = account_data(
+            ProgramConfig::DISCRIMINATOR,
+            &baseline_config(),
+            ProgramConfigV0::INIT_SPACE,
+        );
+        assert!(PledgeV0::try_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_baseline_space_matches_layout() {
+        // Every Option set fills the baseline account exactly
+        let mut pledge = Pledge::DISCRIMINATOR.to_vec();
+        baseline_pledge().serialize(&mut pledge).unwrap();
+        assert_eq!(pledge.len(), PledgeV0::INIT_SPACE);
+
+        let mut config = ProgramConfig::DISCRIMINATOR.to_vec();
+        baseline_config().serialize(&mut config).unwrap();
+        assert_eq!(config.len(), ProgramConfigV0::INIT_SPACE);
+    }
+}
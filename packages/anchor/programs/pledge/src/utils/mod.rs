@@ -1,3 +1,11 @@
+pub mod accounts;
 pub mod fees;
+pub mod lending;
+pub mod migration;
+pub mod settlement;
 
+pub use accounts::*;
 pub use fees::*;
+pub use lending::*;
+pub use migration::*;
+pub use settlement::*;
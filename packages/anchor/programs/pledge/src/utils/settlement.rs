@@ -0,0 +1,421 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, TokenAccount, Transfer};
+
+use crate::constants::PLEDGE_SEED;
+use crate::errors::ErrorCode;
+use crate::state::{
+    Challenge, CharityLedger, CharityRecord, DeadlineBucket, FeeLedger, Pledge, PledgeCompleted,
+    PledgeForfeited, PledgeStatus, ProgramConfig, Recovery, Sponsorship, UserCounter,
+};
+use crate::utils::fees::{calculate_partial_refund, calculate_split};
+
+/// Accounts a pledge settlement moves tokens between
+pub struct SettlementAccounts<'a, 'info> {
+    pub vault: AccountInfo<'info>,
+    pub user: AccountInfo<'info>,
+    pub rent_payer: Option<AccountInfo<'info>>, // Required when pledge.rent_payer is set
+    pub user_token_account: AccountInfo<'info>,
+    pub fee_vault: AccountInfo<'info>,
+    pub fee_ledger: &'a mut Account<'info, FeeLedger>, // Ledger for the pledge's mint
+    pub deadline_bucket: AccountInfo<'info>, // Address already verified by the caller
+    pub user_counter: AccountInfo<'info>,    // Address already verified by the caller
+    pub recovery: AccountInfo<'info>,        // Owner's recovery profile, address already verified
+    pub charity: Option<&'a Account<'info, CharityRecord>>,
+    pub charity_ledger: Option<&'a mut Account<'info, CharityLedger>>, // For the pledge's mint
+    pub beneficiary_token_account: Option<AccountInfo<'info>>,
+    pub crank_token_account: Option<AccountInfo<'info>>, // Settler's reward destination (None = no reward)
+    pub challenge: Option<&'a mut Account<'info, Challenge>>, // Required when pledge.challenge is set
+    pub challenge_vault: Option<AccountInfo<'info>>,
+    pub sponsorship: Option<&'a mut Account<'info, Sponsorship>>, // Required when sponsored
+    pub sponsor_vault: Option<AccountInfo<'info>>,
+    pub sponsor: Option<AccountInfo<'info>>, // Sponsor wallet, gets the sponsor accounts' rent
+    pub sponsor_token_account: Option<AccountInfo<'info>>,
+    pub bonus_token_account: Option<AccountInfo<'info>>, // Goal owner's, required when refunds go to a funder
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Amounts moved by a settlement
+pub struct Settlement {
+    pub refund_amount: u64,
+    pub fee_vault_amount: u64,
+    pub beneficiary_amount: u64,
+    pub crank_reward: u64,
+}
+
+/// Settle a pledge at the given completion percentage
+/// Refunds the user (plus their share of any lending yield), rewards the crank, pays
+/// the beneficiary, moves revenue into the fee vault, pools challenge forfeitures,
+/// settles any sponsor bonus, closes the vault (team pledges keep it open for
+/// contributor claims), drops the pledge from its deadline bucket and the user's
+/// active totals, and marks the pledge Completed or Forfeited
+pub fn settle_pledge<'info>(
+    config: &mut ProgramConfig,
+    pledge: &mut Account<'info, Pledge>,
+    completion_percentage: u8,
+    accounts: SettlementAccounts<'_, 'info>,
+) -> Result<Settlement> {
+    // A pending recovery decides whether the refund goes to the owner or the recovery address
+    require!(
+        !Recovery::holds_settlement_on(&accounts.recovery, pledge)?,
+        ErrorCode::RecoveryPending
+    );
+
+    // Lent stakes must be back in the vault - the yield is split outside the principal math
+    let (user_yield, yield_revenue) = match pledge.yield_position {
+        Some(position) => {
+            require!(position.withdrawn, ErrorCode::YieldPositionOpen);
+            position.split_yield()?
+        }
+        None => (0, 0),
+    };
+
+    // Calculate refund and fee based on completion
+    let (refund_amount, fee_amount) = calculate_partial_refund(
+        pledge.stake_amount,
+        completion_percentage,
+        config.partial_fee_bps,
+    )?;
+
+    // Late reports give up part of the refund, collected as a fee
+    let (late_penalty, refund_amount) = calculate_split(refund_amount, pledge.late_penalty_bps)?;
+    let fee_amount = fee_amount
+        .checked_add(late_penalty)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // Calculate forfeited amount (what's not refunded)
+    let forfeited_amount = pledge
+        .stake_amount
+        .checked_sub(refund_amount)
+        .ok_or(ErrorCode::Underflow)?
+        .checked_sub(fee_amount)
+        .ok_or(ErrorCode::Underflow)?;
+
+    // Challenge pledges pool their forfeiture for the completers, minus the revenue cut
+    let (forfeited_revenue, pooled_amount) = if pledge.challenge.is_some() {
+        calculate_split(forfeited_amount, config.challenge_cut_bps)?
+    } else {
+        (forfeited_amount, 0)
+    };
+
+    // Total going to revenue shares = fee + forfeited (less any pooled amount)
+    let total_revenue = fee_amount
+        .checked_add(forfeited_revenue)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // Crank reward comes off the top of fees + forfeitures, never the refund
+    let (crank_reward, total_to_split) = if accounts.crank_token_account.is_some() {
+        calculate_split(total_revenue, config.crank_reward_bps)?
+    } else {
+        (0, total_revenue)
+    };
+
+    let mut revenue_amounts = config.split_revenue(total_to_split)?;
+
+    // Custom beneficiary takes the cut of the charity share chosen at creation
+    let beneficiary_amount = if pledge.beneficiary.is_some() {
+        require!(
+            pledge.beneficiary != Some(pledge.user),
+            ErrorCode::InvalidBeneficiary
+        );
+        config.take_beneficiary_cut(pledge.beneficiary_bps, &mut revenue_amounts)?
+    } else {
+        0
+    };
+
+    // Team pledges refund the captain's share now - contributors claim theirs from the vault
+    let (owner_stake, owner_refund) = match pledge.team {
+        Some(team) => (
+            team.captain_stake,
+            team.captain_share(refund_amount, pledge.stake_amount)?,
+        ),
+        None => (pledge.stake_amount, refund_amount),
+    };
+
+    // Yield revenue follows the revenue shares, untouched by crank reward and beneficiary cut
+    for (amount, yield_share) in revenue_amounts
+        .iter_mut()
+        .zip(config.split_revenue(yield_revenue)?)
+    {
+        *amount = amount.checked_add(yield_share).ok_or(ErrorCode::Overflow)?;
+    }
+
+    let fee_vault_amount = total_to_split
+        .checked_sub(beneficiary_amount)
+        .ok_or(ErrorCode::Underflow)?
+        .checked_add(yield_revenue)
+        .ok_or(ErrorCode::Overflow)?;
+
+    // Create PDA signer seeds for pledge (which is the vault authority)
+    let user_key = pledge.user;
+    let pledge_seed = pledge.seed();
+    let pledge_seeds = &[
+        PLEDGE_SEED,
+        user_key.as_ref(),
+        pledge_seed.as_ref(),
+        &[pledge.bump],
+    ];
+    let signer_seeds = &[&pledge_seeds[..]];
+
+    // Transfer refund and the user's yield share to user (if any)
+    let user_amount = owner_refund
+        .checked_add(user_yield)
+        .ok_or(ErrorCode::Overflow)?;
+    if user_amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            Transfer {
+                from: accounts.vault.clone(),
+                to: accounts.user_token_account.clone(),
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, user_amount)?;
+    }
+
+    // Reward the crank (if any)
+    if let Some(crank_token_account) = accounts
+        .crank_token_account
+        .clone()
+        .filter(|_| crank_reward > 0)
+    {
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            Transfer {
+                from: accounts.vault.clone(),
+                to: crank_token_account,
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, crank_reward)?;
+    }
+
+    // Pay the forfeiture beneficiary directly (if any)
+    if beneficiary_amount > 0 {
+        let beneficiary_token_account = accounts
+            .beneficiary_token_account
+            .clone()
+            .ok_or(ErrorCode::InvalidBeneficiary)?;
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            Transfer {
+                from: accounts.vault.clone(),
+                to: beneficiary_token_account,
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, beneficiary_amount)?;
+    }
+
+    // Move revenue share into the fee vault (if any)
+    if fee_vault_amount > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            Transfer {
+                from: accounts.vault.clone(),
+                to: accounts.fee_vault.clone(),
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer(transfer_ctx, fee_vault_amount)?;
+    }
+    let charity = config.accrue_pledge_fees(
+        pledge,
+        accounts.fee_ledger,
+        accounts.charity,
+        accounts.charity_ledger,
+        &revenue_amounts,
+    )?;
+
+    // Pool the challenge share and count the participant as settled
+    if pledge.challenge.is_some() {
+        let challenge = accounts
+            .challenge
+            .filter(|challenge| Some(challenge.key()) == pledge.challenge)
+            .ok_or(ErrorCode::InvalidChallenge)?;
+        let challenge_vault = accounts
+            .challenge_vault
+            .filter(|challenge_vault| challenge_vault.key() == challenge.vault)
+            .ok_or(ErrorCode::InvalidChallenge)?;
+        if pooled_amount > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                accounts.token_program.clone(),
+                Transfer {
+                    from: accounts.vault.clone(),
+                    to: challenge_vault,
+                    authority: pledge.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer(transfer_ctx, pooled_amount)?;
+        }
+        challenge.record_settlement(completion_percentage, pooled_amount)?;
+    }
+
+    // Pay the earned sponsor bonus to the goal owner (with the refund, unless a funder takes
+    // that) and return the rest to the sponsor
+    let (sponsor, sponsor_bonus) = if pledge.sponsorship > 0 {
+        let sponsorship = accounts.sponsorship.ok_or(ErrorCode::InvalidSponsor)?;
+        let sponsor_vault = accounts
+            .sponsor_vault
+            .filter(|sponsor_vault| sponsor_vault.key() == sponsorship.vault)
+            .ok_or(ErrorCode::InvalidSponsor)?;
+        let sponsor_info = accounts
+            .sponsor
+            .filter(|sponsor| sponsor.key() == sponsorship.sponsor)
+            .ok_or(ErrorCode::InvalidSponsor)?;
+        let sponsor_token_account = accounts
+            .sponsor_token_account
+            .ok_or(ErrorCode::InvalidSponsor)?;
+        let returned_to =
+            TokenAccount::try_deserialize(&mut &sponsor_token_account.try_borrow_data()?[..])?;
+        require!(
+            sponsor_token_account.owner == accounts.token_program.key
+                && returned_to.owner == sponsorship.sponsor
+                && returned_to.mint == pledge.mint,
+            ErrorCode::InvalidSponsor
+        );
+
+        let sponsor_bonus = sponsorship.bonus_for(completion_percentage)?;
+        let returned = sponsorship
+            .amount
+            .checked_sub(sponsor_bonus)
+            .ok_or(ErrorCode::Underflow)?;
+        let bonus_to = if pledge.refund_recipient() == pledge.goal_owner() {
+            accounts.user_token_account.clone()
+        } else {
+            let bonus_token_account = accounts
+                .bonus_token_account
+                .ok_or(ErrorCode::InvalidBonusAccount)?;
+            let paid_to =
+                TokenAccount::try_deserialize(&mut &bonus_token_account.try_borrow_data()?[..])?;
+            require!(
+                bonus_token_account.owner == accounts.token_program.key
+                    && paid_to.owner == pledge.goal_owner()
+                    && paid_to.mint == pledge.mint,
+                ErrorCode::InvalidBonusAccount
+            );
+            bonus_token_account
+        };
+        for (to, amount) in [
+            (bonus_to, sponsor_bonus),
+            (sponsor_token_account, returned),
+        ] {
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    accounts.token_program.clone(),
+                    Transfer {
+                        from: sponsor_vault.clone(),
+                        to,
+                        authority: pledge.to_account_info(),
+                    },
+                    signer_seeds,
+                );
+                transfer(transfer_ctx, amount)?;
+            }
+        }
+
+        // Sponsor paid the rent for both sponsor accounts
+        let close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            CloseAccount {
+                account: sponsor_vault,
+                destination: sponsor_info.clone(),
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_ctx)?;
+        sponsorship.close(sponsor_info)?;
+
+        (Some(sponsorship.sponsor), sponsor_bonus)
+    } else {
+        (None, 0)
+    };
+
+    // Close vault account (rent back to its payer) unless contributors still have refunds to claim
+    let contributors_pending = match pledge.team.as_mut() {
+        Some(team) if team.contributors > 0 => {
+            team.refund_pool = refund_amount
+                .checked_sub(owner_refund)
+                .ok_or(ErrorCode::Underflow)?;
+            true
+        }
+        _ => false,
+    };
+    if !contributors_pending {
+        let rent_recipient = rent_recipient(pledge, &accounts.user, accounts.rent_payer)?;
+        let close_ctx = CpiContext::new_with_signer(
+            accounts.token_program.clone(),
+            CloseAccount {
+                account: accounts.vault.clone(),
+                destination: rent_recipient,
+                authority: pledge.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_ctx)?;
+    }
+
+    // Only the owner's own stake was counted against their limits
+    DeadlineBucket::remove_pledge_from(&accounts.deadline_bucket, &pledge.key())?;
+    let lost = owner_stake
+        .checked_sub(owner_refund)
+        .ok_or(ErrorCode::Underflow)?;
+    UserCounter::release_from(&accounts.user_counter, pledge, owner_stake, lost, true)?;
+
+    pledge.completion_percentage = Some(completion_percentage);
+
+    // Update status based on outcome
+    if completion_percentage > 0 {
+        pledge.status = PledgeStatus::Completed;
+        emit!(PledgeCompleted {
+            pledge: pledge.key(),
+            completion_percentage,
+            refund_amount,
+            fee_amount,
+            charity,
+            beneficiary_amount,
+            crank_reward,
+            sponsor,
+            sponsorship_amount: pledge.sponsorship,
+            sponsor_bonus,
+        });
+    } else {
+        pledge.status = PledgeStatus::Forfeited;
+        emit!(PledgeForfeited {
+            pledge: pledge.key(),
+            revenue_amounts,
+            charity,
+            beneficiary: pledge.beneficiary,
+            beneficiary_amount,
+            crank_reward,
+            sponsor,
+            sponsorship_amount: pledge.sponsorship,
+        });
+    }
+
+    Ok(Settlement {
+        refund_amount,
+        fee_vault_amount,
+        beneficiary_amount,
+        crank_reward,
+    })
+}
+
+/// Account receiving a pledge's vault rent: its sponsoring rent payer, otherwise the user
+pub fn rent_recipient<'info>(
+    pledge: &Pledge,
+    user: &AccountInfo<'info>,
+    rent_payer: Option<AccountInfo<'info>>,
+) -> Result<AccountInfo<'info>> {
+    match pledge.rent_payer {
+        Some(payer) => rent_payer
+            .filter(|rent_payer| rent_payer.key() == payer)
+            .ok_or(error!(ErrorCode::InvalidRentPayer)),
+        None => Ok(user.clone()),
+    }
+}
@@ -22,6 +22,9 @@ export const DEFAULT_PARTIAL_FEE_BPS = 100; // 1%
 export const DEFAULT_EDIT_PENALTY_BPS = 1000; // 10%
 export const DEFAULT_GRACE_PERIOD = 86400; // 1 day in seconds
 
+// Revenue split
+export const MAX_REVENUE_SHARES = 5;
+
 /**
  * Network configuration
  */
@@ -111,16 +114,17 @@ export function deriveConfigPda(): [PublicKey, number] {
 
 /**
  * Derive pledge PDA
+ * Seed is the user's pledge index (created_at for pledges created before the per-user counter)
  */
 export function derivePledgePda(
   user: PublicKey,
-  createdAt: anchor.BN
+  seed: anchor.BN
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [
       Buffer.from(PLEDGE_SEED),
       user.toBuffer(),
-      createdAt.toArrayLike(Buffer, "le", 8),
+      seed.toArrayLike(Buffer, "le", 8),
     ],
     PROGRAM_ID
   );
@@ -143,6 +147,141 @@ export function formatBps(bps: number): string {
   return `${(bps / 100).toFixed(2)}%`;
 }
 
+/**
+ * Revenue share argument for initialize/update_config
+ */
+export interface RevenueShareInput {
+  recipient: PublicKey;
+  bps: number;
+  isCharity: boolean;
+}
+
+/**
+ * Parse revenue shares from "recipient:bps[:charity],..."
+ * e.g. "7xKX...abc:7000,8yLY...def:3000:charity"
+ */
+export function parseRevenueShares(value: string): RevenueShareInput[] {
+  return value.split(",").map((entry) => {
+    const [recipient, bps, flag] = entry.split(":");
+    if (!recipient || !bps) {
+      throw new Error(`Invalid revenue share "${entry}" - expected recipient:bps[:charity]`);
+    }
+    return {
+      recipient: new PublicKey(recipient),
+      bps: parseInt(bps),
+      isCharity: flag === "charity",
+    };
+  });
+}
+
+/**
+ * Validate revenue shares the same way the program does
+ */
+export function validateRevenueShares(shares: RevenueShareInput[]): string | null {
+  if (shares.length === 0 || shares.length > MAX_REVENUE_SHARES) {
+    return `Revenue shares must have 1 to ${MAX_REVENUE_SHARES} entries`;
+  }
+  const totalBps = shares.reduce((total, share) => total + share.bps, 0);
+  if (totalBps !== 10000) {
+    return `Revenue shares must sum to 10000 BPS (got ${totalBps})`;
+  }
+  if (shares.filter((share) => share.isCharity).length > 1) {
+    return "At most one revenue share can be the charity slot";
+  }
+  return null;
+}
+
+/**
+ * Print revenue shares, one per line
+ */
+export function printRevenueShares(
+  shares: { recipient: PublicKey; bps: number; isCharity: boolean }[],
+  indent: string = "  "
+): void {
+  shares.forEach((share, index) => {
+    const label = share.isCharity ? " (charity slot)" : "";
+    console.log(`${indent}[${index}] ${share.recipient.toBase58()}: ${formatBps(share.bps)}${label}`);
+  });
+}
+
+/**
+ * Exposure limits (0 = no limit)
+ */
+export interface LimitsInput {
+  minStake: anchor.BN;
+  maxStake: anchor.BN;
+  minDurationSeconds: anchor.BN;
+  maxDurationSeconds: anchor.BN;
+  maxActivePledges: number;
+  maxActiveStake: anchor.BN;
+}
+
+/**
+ * update-config flags that set a limit
+ */
+export const LIMIT_FLAGS = [
+  "min-stake",
+  "max-stake",
+  "min-duration",
+  "max-duration",
+  "max-active-pledges",
+  "max-active-stake",
+];
+
+/**
+ * Validate limits the same way the program does
+ */
+export function validateLimits(limits: LimitsInput): string | null {
+  if (limits.minDurationSeconds.isNeg() || limits.maxDurationSeconds.isNeg()) {
+    return "Durations must not be negative";
+  }
+  if (!limits.maxStake.isZero() && limits.minStake.gt(limits.maxStake)) {
+    return "Minimum stake must not exceed maximum stake";
+  }
+  if (!limits.maxDurationSeconds.isZero() && limits.minDurationSeconds.gt(limits.maxDurationSeconds)) {
+    return "Minimum duration must not exceed maximum duration";
+  }
+  return null;
+}
+
+/**
+ * Print limits, one per line
+ */
+export function printLimits(limits: LimitsInput, indent: string = "  "): void {
+  const amount = (value: anchor.BN) => (value.isZero() ? "No limit" : formatUsdc(BigInt(value.toString())));
+  const seconds = (value: anchor.BN) => (value.isZero() ? "No limit" : formatSeconds(value.toNumber()));
+  console.log(`${indent}Min Stake: ${amount(limits.minStake)}`);
+  console.log(`${indent}Max Stake: ${amount(limits.maxStake)}`);
+  console.log(`${indent}Min Duration: ${seconds(limits.minDurationSeconds)}`);
+  console.log(`${indent}Max Duration: ${seconds(limits.maxDurationSeconds)}`);
+  console.log(`${indent}Max Active Pledges: ${limits.maxActivePledges || "No limit"}`);
+  console.log(`${indent}Max Active Stake: ${amount(limits.maxActiveStake)}`);
+}
+
+/**
+ * Format a late report policy on one line
+ */
+export function formatLateReport(lateReport: { windowSeconds: anchor.BN; maxPenaltyBps: number }): string {
+  if (lateReport.windowSeconds.isZero()) return "Disabled";
+  return `${formatSeconds(lateReport.windowSeconds.toNumber())} after grace, up to ${formatBps(lateReport.maxPenaltyBps)} penalty`;
+}
+
+/**
+ * Format the range of grace periods users may choose
+ */
+export function formatGraceBounds(graceBounds: { minSeconds: anchor.BN; maxSeconds: anchor.BN }): string {
+  if (graceBounds.maxSeconds.isZero()) return "Global grace period only";
+  return `${formatSeconds(graceBounds.minSeconds.toNumber())} to ${formatSeconds(graceBounds.maxSeconds.toNumber())}`;
+}
+
+/**
+ * Format the lending adapter and the user's share of its yield
+ */
+export function formatYieldPolicy(yieldPolicy: { adapter: PublicKey | null; userBps: number }): string {
+  if (!yieldPolicy.adapter) return "Off";
+  return `${yieldPolicy.adapter.toBase58()}, user keeps ${formatBps(yieldPolicy.userBps)} of yield`;
+}
+
 /**
  * Format seconds as human readable
  */
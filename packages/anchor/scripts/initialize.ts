@@ -12,6 +12,7 @@
  *   --treasury        Treasury wallet public key (required)
  *   --charity         Charity wallet public key (required)
  *   --treasury-split  Treasury split in BPS (default: 7000 = 70%)
+ *   --revenue-shares  Full revenue split "recipient:bps[:charity],..." (replaces treasury/charity)
  *   --partial-fee     Partial completion fee in BPS (default: 100 = 1%)
  *   --edit-penalty    Edit penalty in BPS (default: 1000 = 10%)
  *   --grace-period    Grace period in seconds (default: 86400 = 1 day)
@@ -38,6 +39,10 @@ import {
   formatBps,
   formatSeconds,
   confirm,
+  parseRevenueShares,
+  validateRevenueShares,
+  printRevenueShares,
+  RevenueShareInput,
   DEFAULT_TREASURY_SPLIT_BPS,
   DEFAULT_PARTIAL_FEE_BPS,
   DEFAULT_EDIT_PENALTY_BPS,
@@ -59,14 +64,8 @@ async function main() {
   const gracePeriod = parseInt(args["grace-period"] || String(DEFAULT_GRACE_PERIOD));
 
   // Validate required arguments
-  if (!treasuryPubkey) {
-    printError("Missing required argument: --treasury");
-    console.log("Usage: npx ts-node scripts/initialize.ts --treasury <pubkey> --charity <pubkey>");
-    process.exit(1);
-  }
-
-  if (!charityPubkey) {
-    printError("Missing required argument: --charity");
+  if (!args["revenue-shares"] && (!treasuryPubkey || !charityPubkey)) {
+    printError("Missing required arguments: --treasury and --charity (or --revenue-shares)");
     console.log("Usage: npx ts-node scripts/initialize.ts --treasury <pubkey> --charity <pubkey>");
     process.exit(1);
   }
@@ -103,22 +102,38 @@ async function main() {
     process.exit(1);
   }
 
-  // Parse pubkeys
-  let treasury: PublicKey;
-  let charity: PublicKey;
+  // Build revenue shares
+  let revenueShares: RevenueShareInput[];
   try {
-    treasury = new PublicKey(treasuryPubkey);
-    charity = new PublicKey(charityPubkey);
+    revenueShares = args["revenue-shares"]
+      ? parseRevenueShares(args["revenue-shares"])
+      : [
+          {
+            recipient: new PublicKey(treasuryPubkey),
+            bps: treasurySplitBps,
+            isCharity: false,
+          },
+          {
+            recipient: new PublicKey(charityPubkey),
+            bps: 10000 - treasurySplitBps,
+            isCharity: true,
+          },
+        ];
   } catch (error: any) {
-    printError(`Invalid public key: ${error.message}`);
+    printError(`Invalid revenue shares: ${error.message}`);
+    process.exit(1);
+  }
+
+  const sharesError = validateRevenueShares(revenueShares);
+  if (sharesError) {
+    printError(sharesError);
     process.exit(1);
   }
 
-  console.log("Treasury:", treasury.toBase58());
-  console.log("Charity:", charity.toBase58());
+  console.log("Revenue shares:");
+  printRevenueShares(revenueShares);
   console.log("");
   console.log("Config values:");
-  console.log("  Treasury split:", formatBps(treasurySplitBps), `(${treasurySplitBps} BPS)`);
   console.log("  Partial fee:", formatBps(partialFeeBps), `(${partialFeeBps} BPS)`);
   console.log("  Edit penalty:", formatBps(editPenaltyBps), `(${editPenaltyBps} BPS)`);
   console.log("  Grace period:", formatSeconds(gracePeriod), `(${gracePeriod} seconds)`);
@@ -174,9 +189,7 @@ async function main() {
   try {
     const tx = await program.methods
       .initialize(
-        treasury,
-        charity,
-        treasurySplitBps,
+        revenueShares,
         partialFeeBps,
         editPenaltyBps,
         new anchor.BN(gracePeriod)
@@ -195,8 +208,8 @@ async function main() {
     const config = await program.account.programConfig.fetch(configPda);
     console.log("\nVerified config:");
     console.log("  Admin:", config.admin.toBase58());
-    console.log("  Treasury:", config.treasury.toBase58());
-    console.log("  Charity:", config.charity.toBase58());
+    console.log("  Revenue shares:");
+    printRevenueShares(config.revenueShares, "    ");
     console.log("  Paused:", config.paused);
   } catch (error: any) {
     printError(`Failed to initialize: ${error.message}`);
@@ -126,13 +126,20 @@ async function main() {
   try {
     const tx = await program.methods
       .updateConfig(
-        null, // treasury
-        null, // charity
-        null, // treasurySplitBps
+        null, // revenueShares
         null, // partialFeeBps
         null, // editPenaltyBps
         null, // gracePeriodSeconds
-        shouldPause // paused
+        shouldPause, // paused
+        null, // maxBeneficiaryBps
+        null, // crankRewardBps
+        null, // limits
+        null, // allowEarlyReport
+        null, // lateReport
+        null, // graceBounds
+        null, // challengeCutBps
+        null, // yieldPolicy
+        null // maxDefaultPercentage
       )
       .accounts({
         admin: admin.publicKey,
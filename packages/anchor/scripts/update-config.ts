@@ -9,22 +9,55 @@
  * Options:
  *   --network         Network to use: localhost, devnet, mainnet (default: localhost)
  *   --admin           Path to admin keypair (default: ./admin-wallet.json or ~/.config/solana/id.json)
- *   --treasury        New treasury wallet public key
- *   --charity         New charity wallet public key
- *   --treasury-split  New treasury split in BPS (max 10000)
+ *   --revenue-shares  New revenue split "recipient:bps[:charity],..." (1-5 shares, sum 10000)
  *   --partial-fee     New partial completion fee in BPS (max 1000)
  *   --edit-penalty    New edit penalty in BPS (max 1000)
  *   --grace-period    New grace period in seconds
+ *   --max-beneficiary New max custom beneficiary share in BPS (max 10000)
+ *   --crank-reward    New crank reward in BPS of fees + forfeitures (max 500)
+ *   --min-stake       Minimum stake per pledge in token base units (0 = no limit)
+ *   --max-stake       Maximum stake per pledge in token base units (0 = no limit)
+ *   --min-duration    Minimum pledge duration in seconds (0 = no limit)
+ *   --max-duration    Maximum pledge duration in seconds (0 = no limit)
+ *   --max-active-pledges  Maximum unsettled pledges per user (0 = no limit)
+ *   --max-active-stake    Maximum total unsettled stake per user in token base units (0 = no limit)
+ *   --early-report    Let every pledge report 100% before its deadline: on or off
+ *   --late-window     Seconds after the grace period a late report is still accepted (0 = disabled)
+ *   --late-penalty    Refund share in BPS lost by a report at the end of the late window (max 10000)
+ *   --min-grace       Shortest grace period a user may choose for their pledge, in seconds
+ *   --max-grace       Longest grace period a user may choose for their pledge, in seconds (0 = users can't choose)
+ *   --challenge-cut   Share of challenge forfeitures in BPS kept as revenue instead of pooled (max 10000)
+ *   --yield-adapter   Lending adapter program pledge stakes may be lent through ("none" = yield mode off)
+ *   --yield-user-share  User's share of lending yield in BPS, the rest is split like revenue (max 10000)
+ *   --max-default     Highest fixed default outcome percentage users may pick (max 100)
  *
  * Examples:
- *   # Update treasury split to 80%
- *   npx ts-node scripts/update-config.ts --network devnet --treasury-split 8000
+ *   # Split revenue 80/20 between treasury and charity
+ *   npx ts-node scripts/update-config.ts --network devnet --revenue-shares 7xKX...abc:8000,8yLY...def:2000:charity
  *
  *   # Update multiple values
  *   npx ts-node scripts/update-config.ts --network devnet --partial-fee 200 --edit-penalty 500
  *
- *   # Update treasury wallet
- *   npx ts-node scripts/update-config.ts --network devnet --treasury 7xKX...abc
+ *   # Cap pledges at 1000 USDC and users at 3 active pledges
+ *   npx ts-node scripts/update-config.ts --network devnet --max-stake 1000000000 --max-active-pledges 3
+ *
+ *   # Allow early completion reports for all pledges
+ *   npx ts-node scripts/update-config.ts --network devnet --early-report on
+ *
+ *   # Accept reports up to 3 days after the grace period, losing up to 50% of the refund
+ *   npx ts-node scripts/update-config.ts --network devnet --late-window 259200 --late-penalty 5000
+ *
+ *   # Let users pick a grace period between 1 hour and 7 days
+ *   npx ts-node scripts/update-config.ts --network devnet --min-grace 3600 --max-grace 604800
+ *
+ *   # Keep 5% of challenge forfeitures as revenue, pooling the rest for completers
+ *   npx ts-node scripts/update-config.ts --network devnet --challenge-cut 500
+ *
+ *   # Turn on yield mode, giving users half of the yield
+ *   npx ts-node scripts/update-config.ts --network devnet --yield-adapter 9Yjv...CgHv --yield-user-share 5000
+ *
+ *   # Let unreported pledges default to at most a 25% refund
+ *   npx ts-node scripts/update-config.ts --network devnet --max-default 25
  */
 
 import { Connection, PublicKey } from "@solana/web3.js";
@@ -42,6 +75,16 @@ import {
   formatBps,
   formatSeconds,
   confirm,
+  parseRevenueShares,
+  validateRevenueShares,
+  printRevenueShares,
+  validateLimits,
+  printLimits,
+  formatLateReport,
+  formatGraceBounds,
+  formatYieldPolicy,
+  LIMIT_FLAGS,
+  RevenueShareInput,
   Network,
 } from "./common";
 
@@ -53,35 +96,71 @@ async function main() {
   const adminPath = args.admin || getDefaultAdminKeypairPath();
 
   // Parse optional update values
-  const treasuryPubkey = args.treasury;
-  const charityPubkey = args.charity;
-  const treasurySplitBps = args["treasury-split"] ? parseInt(args["treasury-split"]) : null;
+  const revenueSharesArg = args["revenue-shares"];
   const partialFeeBps = args["partial-fee"] ? parseInt(args["partial-fee"]) : null;
   const editPenaltyBps = args["edit-penalty"] ? parseInt(args["edit-penalty"]) : null;
   const gracePeriod = args["grace-period"] ? parseInt(args["grace-period"]) : null;
+  const maxBeneficiaryBps = args["max-beneficiary"] ? parseInt(args["max-beneficiary"]) : null;
+  const crankRewardBps = args["crank-reward"] ? parseInt(args["crank-reward"]) : null;
+  const limitArgs = LIMIT_FLAGS.filter((flag) => args[flag] !== undefined);
+  const earlyReportArg = args["early-report"];
+  const lateWindow = args["late-window"] !== undefined ? parseInt(args["late-window"]) : null;
+  const latePenaltyBps = args["late-penalty"] !== undefined ? parseInt(args["late-penalty"]) : null;
+  const minGrace = args["min-grace"] !== undefined ? parseInt(args["min-grace"]) : null;
+  const maxGrace = args["max-grace"] !== undefined ? parseInt(args["max-grace"]) : null;
+  const challengeCutBps = args["challenge-cut"] !== undefined ? parseInt(args["challenge-cut"]) : null;
+  const yieldAdapterArg = args["yield-adapter"];
+  const yieldUserBps = args["yield-user-share"] !== undefined ? parseInt(args["yield-user-share"]) : null;
+  const maxDefaultPercentage = args["max-default"] !== undefined ? parseInt(args["max-default"]) : null;
 
   // Check if any update values provided
-  if (!treasuryPubkey && !charityPubkey && treasurySplitBps === null &&
-      partialFeeBps === null && editPenaltyBps === null && gracePeriod === null) {
+  if (!revenueSharesArg && partialFeeBps === null && editPenaltyBps === null && gracePeriod === null &&
+      maxBeneficiaryBps === null && crankRewardBps === null && limitArgs.length === 0 &&
+      earlyReportArg === undefined && lateWindow === null && latePenaltyBps === null &&
+      minGrace === null && maxGrace === null && challengeCutBps === null &&
+      yieldAdapterArg === undefined && yieldUserBps === null && maxDefaultPercentage === null) {
     printError("No update values provided.");
     console.log("Usage: npx ts-node scripts/update-config.ts --network <network> [options]");
     console.log("");
     console.log("Options:");
-    console.log("  --treasury        New treasury wallet public key");
-    console.log("  --charity         New charity wallet public key");
-    console.log("  --treasury-split  New treasury split in BPS (max 10000)");
+    console.log("  --revenue-shares  New revenue split \"recipient:bps[:charity],...\" (1-5 shares, sum 10000)");
     console.log("  --partial-fee     New partial completion fee in BPS (max 1000)");
     console.log("  --edit-penalty    New edit penalty in BPS (max 1000)");
     console.log("  --grace-period    New grace period in seconds");
+    console.log("  --max-beneficiary New max custom beneficiary share in BPS (max 10000)");
+    console.log("  --crank-reward    New crank reward in BPS of fees + forfeitures (max 500)");
+    console.log("  --min-stake, --max-stake, --min-duration, --max-duration,");
+    console.log("  --max-active-pledges, --max-active-stake   Exposure limits (0 = no limit)");
+    console.log("  --early-report    Let every pledge report 100% before its deadline: on or off");
+    console.log("  --late-window     Seconds after the grace period a late report is still accepted (0 = disabled)");
+    console.log("  --late-penalty    Refund share in BPS lost by a report at the end of the late window (max 10000)");
+    console.log("  --min-grace, --max-grace   Grace periods users may choose, in seconds (max 0 = users can't choose)");
+    console.log("  --challenge-cut   Share of challenge forfeitures in BPS kept as revenue instead of pooled (max 10000)");
+    console.log("  --yield-adapter   Lending adapter program for pledge stakes (\"none\" = yield mode off)");
+    console.log("  --yield-user-share  User's share of lending yield in BPS (max 10000)");
+    console.log("  --max-default     Highest fixed default outcome percentage users may pick (max 100)");
     process.exit(1);
   }
 
-  // Validate BPS values
-  if (treasurySplitBps !== null && treasurySplitBps > 10000) {
-    printError("Treasury split must be <= 10000 BPS (100%)");
-    process.exit(1);
+  // Parse and validate revenue shares
+  let revenueShares: RevenueShareInput[] | null = null;
+  if (revenueSharesArg) {
+    try {
+      revenueShares = parseRevenueShares(revenueSharesArg);
+    } catch (error: any) {
+      printError(`Invalid revenue shares: ${error.message}`);
+      process.exit(1);
+    }
+
+    const sharesError = validateRevenueShares(revenueShares);
+    if (sharesError) {
+      printError(sharesError);
+      process.exit(1);
+    }
   }
 
+  // Validate BPS values
+
   if (partialFeeBps !== null && partialFeeBps > 1000) {
     printError("Partial fee must be <= 1000 BPS (10%)");
     process.exit(1);
@@ -92,6 +171,57 @@ async function main() {
     process.exit(1);
   }
 
+  if (maxBeneficiaryBps !== null && maxBeneficiaryBps > 10000) {
+    printError("Max beneficiary share must be <= 10000 BPS (100%)");
+    process.exit(1);
+  }
+
+  if (crankRewardBps !== null && crankRewardBps > 500) {
+    printError("Crank reward must be <= 500 BPS (5%)");
+    process.exit(1);
+  }
+
+  if (earlyReportArg !== undefined && earlyReportArg !== "on" && earlyReportArg !== "off") {
+    printError("Early report must be 'on' or 'off'");
+    process.exit(1);
+  }
+  const allowEarlyReport = earlyReportArg !== undefined ? earlyReportArg === "on" : null;
+
+  if (lateWindow !== null && lateWindow < 0) {
+    printError("Late window must be >= 0 seconds");
+    process.exit(1);
+  }
+
+  if (latePenaltyBps !== null && latePenaltyBps > 10000) {
+    printError("Late penalty must be <= 10000 BPS (100%)");
+    process.exit(1);
+  }
+
+  if (maxDefaultPercentage !== null && (maxDefaultPercentage < 0 || maxDefaultPercentage > 100)) {
+    printError("Max default percentage must be between 0 and 100");
+    process.exit(1);
+  }
+
+  if (challengeCutBps !== null && challengeCutBps > 10000) {
+    printError("Challenge cut must be <= 10000 BPS (100%)");
+    process.exit(1);
+  }
+
+  if (yieldUserBps !== null && yieldUserBps > 10000) {
+    printError("Yield user share must be <= 10000 BPS (100%)");
+    process.exit(1);
+  }
+
+  let yieldAdapter: PublicKey | null | undefined = undefined;
+  if (yieldAdapterArg !== undefined) {
+    try {
+      yieldAdapter = yieldAdapterArg === "none" ? null : new PublicKey(yieldAdapterArg);
+    } catch {
+      printError(`Invalid yield adapter: ${yieldAdapterArg}`);
+      process.exit(1);
+    }
+  }
+
   printHeader("Update Pledge Program Config");
 
   console.log("Network:", network);
@@ -108,28 +238,6 @@ async function main() {
     process.exit(1);
   }
 
-  // Parse pubkeys
-  let treasury: PublicKey | null = null;
-  let charity: PublicKey | null = null;
-
-  if (treasuryPubkey) {
-    try {
-      treasury = new PublicKey(treasuryPubkey);
-    } catch (error: any) {
-      printError(`Invalid treasury public key: ${error.message}`);
-      process.exit(1);
-    }
-  }
-
-  if (charityPubkey) {
-    try {
-      charity = new PublicKey(charityPubkey);
-    } catch (error: any) {
-      printError(`Invalid charity public key: ${error.message}`);
-      process.exit(1);
-    }
-  }
-
   // Connect
   const clusterUrl = getClusterUrl(network);
   const connection = new Connection(clusterUrl, "confirmed");
@@ -160,26 +268,70 @@ async function main() {
     process.exit(1);
   }
 
-  // Show changes
-  console.log("\nChanges to apply:");
-  console.log("-".repeat(40));
+  // Limits are replaced as a whole - unspecified flags keep their current value
+  let limits = null;
+  if (limitArgs.length > 0) {
+    const current = currentConfig.limits;
+    const limitValue = (flag: string, currentValue: anchor.BN | number) =>
+      args[flag] !== undefined ? new anchor.BN(args[flag]) : new anchor.BN(currentValue);
+    limits = {
+      minStake: limitValue("min-stake", current.minStake),
+      maxStake: limitValue("max-stake", current.maxStake),
+      minDurationSeconds: limitValue("min-duration", current.minDurationSeconds),
+      maxDurationSeconds: limitValue("max-duration", current.maxDurationSeconds),
+      maxActivePledges: limitValue("max-active-pledges", current.maxActivePledges).toNumber(),
+      maxActiveStake: limitValue("max-active-stake", current.maxActiveStake),
+    };
+
+    const limitsError = validateLimits(limits);
+    if (limitsError) {
+      printError(limitsError);
+      process.exit(1);
+    }
+  }
+
+  // Late report policy is replaced as a whole - an unspecified flag keeps its current value
+  let lateReport = null;
+  if (lateWindow !== null || latePenaltyBps !== null) {
+    lateReport = {
+      windowSeconds: new anchor.BN(lateWindow ?? currentConfig.lateReport.windowSeconds.toNumber()),
+      maxPenaltyBps: latePenaltyBps ?? currentConfig.lateReport.maxPenaltyBps,
+    };
+  }
 
-  if (treasury) {
-    console.log("Treasury:");
-    console.log("  Current:", currentConfig.treasury.toBase58());
-    console.log("  New:    ", treasury.toBase58());
+  // Grace bounds are replaced as a whole - an unspecified flag keeps its current value
+  let graceBounds = null;
+  if (minGrace !== null || maxGrace !== null) {
+    graceBounds = {
+      minSeconds: new anchor.BN(minGrace ?? currentConfig.graceBounds.minSeconds.toNumber()),
+      maxSeconds: new anchor.BN(maxGrace ?? currentConfig.graceBounds.maxSeconds.toNumber()),
+    };
+
+    if (graceBounds.minSeconds.isNeg() || graceBounds.minSeconds.gt(graceBounds.maxSeconds)) {
+      printError("Grace bounds must be >= 0 with min <= max");
+      process.exit(1);
+    }
   }
 
-  if (charity) {
-    console.log("Charity:");
-    console.log("  Current:", currentConfig.charity.toBase58());
-    console.log("  New:    ", charity.toBase58());
+  // Yield policy is replaced as a whole - an unspecified flag keeps its current value
+  let yieldPolicy = null;
+  if (yieldAdapter !== undefined || yieldUserBps !== null) {
+    yieldPolicy = {
+      adapter: yieldAdapter !== undefined ? yieldAdapter : currentConfig.yieldPolicy.adapter,
+      userBps: yieldUserBps ?? currentConfig.yieldPolicy.userBps,
+    };
   }
 
-  if (treasurySplitBps !== null) {
-    console.log("Treasury Split:");
-    console.log("  Current:", formatBps(currentConfig.treasurySplitBps), `(${currentConfig.treasurySplitBps} BPS)`);
-    console.log("  New:    ", formatBps(treasurySplitBps), `(${treasurySplitBps} BPS)`);
+  // Show changes
+  console.log("\nChanges to apply:");
+  console.log("-".repeat(40));
+
+  if (revenueShares) {
+    console.log("Revenue Shares:");
+    console.log("  Current:");
+    printRevenueShares(currentConfig.revenueShares, "    ");
+    console.log("  New:");
+    printRevenueShares(revenueShares, "    ");
   }
 
   if (partialFeeBps !== null) {
@@ -200,6 +352,62 @@ async function main() {
     console.log("  New:    ", formatSeconds(gracePeriod), `(${gracePeriod} seconds)`);
   }
 
+  if (maxBeneficiaryBps !== null) {
+    console.log("Max Beneficiary Share:");
+    console.log("  Current:", formatBps(currentConfig.maxBeneficiaryBps), `(${currentConfig.maxBeneficiaryBps} BPS)`);
+    console.log("  New:    ", formatBps(maxBeneficiaryBps), `(${maxBeneficiaryBps} BPS)`);
+  }
+
+  if (crankRewardBps !== null) {
+    console.log("Crank Reward:");
+    console.log("  Current:", formatBps(currentConfig.crankRewardBps), `(${currentConfig.crankRewardBps} BPS)`);
+    console.log("  New:    ", formatBps(crankRewardBps), `(${crankRewardBps} BPS)`);
+  }
+
+  if (limits) {
+    console.log("Limits:");
+    console.log("  Current:");
+    printLimits(currentConfig.limits, "    ");
+    console.log("  New:");
+    printLimits(limits, "    ");
+  }
+
+  if (allowEarlyReport !== null) {
+    console.log("Early Report (all pledges):");
+    console.log("  Current:", currentConfig.allowEarlyReport ? "On" : "Off");
+    console.log("  New:    ", allowEarlyReport ? "On" : "Off");
+  }
+
+  if (lateReport) {
+    console.log("Late Reports:");
+    console.log("  Current:", formatLateReport(currentConfig.lateReport));
+    console.log("  New:    ", formatLateReport(lateReport));
+  }
+
+  if (graceBounds) {
+    console.log("User Grace Periods:");
+    console.log("  Current:", formatGraceBounds(currentConfig.graceBounds));
+    console.log("  New:    ", formatGraceBounds(graceBounds));
+  }
+
+  if (challengeCutBps !== null) {
+    console.log("Challenge Cut:");
+    console.log("  Current:", formatBps(currentConfig.challengeCutBps), `(${currentConfig.challengeCutBps} BPS)`);
+    console.log("  New:    ", formatBps(challengeCutBps), `(${challengeCutBps} BPS)`);
+  }
+
+  if (yieldPolicy) {
+    console.log("Yield Mode:");
+    console.log("  Current:", formatYieldPolicy(currentConfig.yieldPolicy));
+    console.log("  New:    ", formatYieldPolicy(yieldPolicy));
+  }
+
+  if (maxDefaultPercentage !== null) {
+    console.log("Max Default Percentage:");
+    console.log("  Current:", `${currentConfig.maxDefaultPercentage}%`);
+    console.log("  New:    ", `${maxDefaultPercentage}%`);
+  }
+
   console.log("");
 
   // Confirm for mainnet
@@ -217,13 +425,20 @@ async function main() {
   try {
     const tx = await program.methods
       .updateConfig(
-        treasury,
-        charity,
-        treasurySplitBps,
+        revenueShares,
         partialFeeBps,
         editPenaltyBps,
         gracePeriod !== null ? new anchor.BN(gracePeriod) : null,
-        null // paused - use pause.ts instead
+        null, // paused - use pause.ts instead
+        maxBeneficiaryBps,
+        crankRewardBps,
+        limits,
+        allowEarlyReport,
+        lateReport,
+        graceBounds,
+        challengeCutBps,
+        yieldPolicy,
+        maxDefaultPercentage
       )
       .accounts({
         admin: admin.publicKey,
@@ -238,12 +453,21 @@ async function main() {
     // Verify
     const newConfig = await program.account.programConfig.fetch(configPda);
     console.log("\nUpdated config:");
-    console.log("  Treasury:", newConfig.treasury.toBase58());
-    console.log("  Charity:", newConfig.charity.toBase58());
-    console.log("  Treasury Split:", formatBps(newConfig.treasurySplitBps));
+    console.log("  Revenue Shares:");
+    printRevenueShares(newConfig.revenueShares, "    ");
     console.log("  Partial Fee:", formatBps(newConfig.partialFeeBps));
     console.log("  Edit Penalty:", formatBps(newConfig.editPenaltyBps));
     console.log("  Grace Period:", formatSeconds(newConfig.gracePeriodSeconds.toNumber()));
+    console.log("  Max Beneficiary Share:", formatBps(newConfig.maxBeneficiaryBps));
+    console.log("  Crank Reward:", formatBps(newConfig.crankRewardBps));
+    console.log("  Limits:");
+    printLimits(newConfig.limits, "    ");
+    console.log("  Early Report (all pledges):", newConfig.allowEarlyReport ? "On" : "Off");
+    console.log("  Late Reports:", formatLateReport(newConfig.lateReport));
+    console.log("  User Grace Periods:", formatGraceBounds(newConfig.graceBounds));
+    console.log("  Challenge Cut:", formatBps(newConfig.challengeCutBps));
+    console.log("  Yield Mode:", formatYieldPolicy(newConfig.yieldPolicy));
+    console.log("  Max Default Percentage:", `${newConfig.maxDefaultPercentage}%`);
     console.log("  Paused:", newConfig.paused);
   } catch (error: any) {
     printError(`Failed to update config: ${error.message}`);
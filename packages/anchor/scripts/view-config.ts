@@ -25,6 +25,11 @@ import {
   printError,
   formatBps,
   formatSeconds,
+  printRevenueShares,
+  printLimits,
+  formatLateReport,
+  formatGraceBounds,
+  formatYieldPolicy,
   Network,
   loadKeypair,
   getDefaultAdminKeypairPath,
@@ -84,12 +89,40 @@ async function main() {
       console.log(JSON.stringify({
         configPda: configPda.toBase58(),
         admin: config.admin.toBase58(),
-        treasury: config.treasury.toBase58(),
-        charity: config.charity.toBase58(),
-        treasurySplitBps: config.treasurySplitBps,
+        revenueShares: config.revenueShares.map((share) => ({
+          recipient: share.recipient.toBase58(),
+          bps: share.bps,
+          isCharity: share.isCharity,
+          accrued: share.accrued.toString(),
+        })),
         partialFeeBps: config.partialFeeBps,
         editPenaltyBps: config.editPenaltyBps,
         gracePeriodSeconds: config.gracePeriodSeconds.toNumber(),
+        maxBeneficiaryBps: config.maxBeneficiaryBps,
+        crankRewardBps: config.crankRewardBps,
+        limits: {
+          minStake: config.limits.minStake.toString(),
+          maxStake: config.limits.maxStake.toString(),
+          minDurationSeconds: config.limits.minDurationSeconds.toNumber(),
+          maxDurationSeconds: config.limits.maxDurationSeconds.toNumber(),
+          maxActivePledges: config.limits.maxActivePledges,
+          maxActiveStake: config.limits.maxActiveStake.toString(),
+        },
+        allowEarlyReport: config.allowEarlyReport,
+        lateReport: {
+          windowSeconds: config.lateReport.windowSeconds.toNumber(),
+          maxPenaltyBps: config.lateReport.maxPenaltyBps,
+        },
+        graceBounds: {
+          minSeconds: config.graceBounds.minSeconds.toNumber(),
+          maxSeconds: config.graceBounds.maxSeconds.toNumber(),
+        },
+        challengeCutBps: config.challengeCutBps,
+        yieldPolicy: {
+          adapter: config.yieldPolicy.adapter?.toBase58() ?? null,
+          userBps: config.yieldPolicy.userBps,
+        },
+        maxDefaultPercentage: config.maxDefaultPercentage,
         paused: config.paused,
         bump: config.bump,
       }, null, 2));
@@ -99,16 +132,32 @@ async function main() {
       console.log("-".repeat(40));
       console.log("");
       console.log("Admin:", config.admin.toBase58());
-      console.log("Treasury:", config.treasury.toBase58());
-      console.log("Charity:", config.charity.toBase58());
+      console.log("");
+      console.log("Revenue Shares:");
+      printRevenueShares(config.revenueShares);
+      console.log("");
+      console.log("Accrued (undistributed, all mints):");
+      config.revenueShares.forEach((share, index) => {
+        console.log(`  [${index}] ${share.accrued.toString()}`);
+      });
       console.log("");
       console.log("Fee Settings:");
-      console.log("  Treasury Split:", formatBps(config.treasurySplitBps), `(${config.treasurySplitBps} BPS)`);
       console.log("  Partial Fee:", formatBps(config.partialFeeBps), `(${config.partialFeeBps} BPS)`);
       console.log("  Edit Penalty:", formatBps(config.editPenaltyBps), `(${config.editPenaltyBps} BPS)`);
+      console.log("  Max Beneficiary Share:", formatBps(config.maxBeneficiaryBps), `(${config.maxBeneficiaryBps} BPS)`);
+      console.log("  Crank Reward:", formatBps(config.crankRewardBps), `(${config.crankRewardBps} BPS)`);
+      console.log("  Challenge Cut:", formatBps(config.challengeCutBps), `(${config.challengeCutBps} BPS)`);
+      console.log("  Yield Mode:", formatYieldPolicy(config.yieldPolicy));
       console.log("");
       console.log("Timing:");
       console.log("  Grace Period:", formatSeconds(config.gracePeriodSeconds.toNumber()), `(${config.gracePeriodSeconds.toNumber()} seconds)`);
+      console.log("  Early Report (all pledges):", config.allowEarlyReport ? "On" : "Off");
+      console.log("  Late Reports:", formatLateReport(config.lateReport));
+      console.log("  User Grace Periods:", formatGraceBounds(config.graceBounds));
+      console.log("  Max Default Percentage:", `${config.maxDefaultPercentage}%`);
+      console.log("");
+      console.log("Limits:");
+      printLimits(config.limits);
       console.log("");
       console.log("Status:");
       console.log("  Paused:", config.paused ? "YES - PROGRAM IS PAUSED" : "No");
@@ -5,17 +5,20 @@
  *
  * Usage:
  *   npx ts-node scripts/view-pledge.ts --network <network> --pledge <address>
+ *   npx ts-node scripts/view-pledge.ts --network <network> --user <address> --index <n>
  *   npx ts-node scripts/view-pledge.ts --network <network> --user <address> --created-at <timestamp>
  *
  * Options:
  *   --network      Network to use: localhost, devnet, mainnet (default: localhost)
  *   --pledge       Pledge account address
- *   --user         User wallet address (used with --created-at to derive PDA)
- *   --created-at   Unix timestamp when pledge was created (used with --user)
+ *   --user         User wallet address (used with --index or --created-at to derive PDA)
+ *   --index        User's pledge index (used with --user)
+ *   --created-at   Unix timestamp seeding a pledge created before per-user indexes (used with --user)
  *   --json         Output as JSON
  *
  * Examples:
  *   npx ts-node scripts/view-pledge.ts --network devnet --pledge 7xKX...abc
+ *   npx ts-node scripts/view-pledge.ts --network devnet --user 8yLY...def --index 0
  *   npx ts-node scripts/view-pledge.ts --network devnet --user 8yLY...def --created-at 1700000000
  */
 
@@ -33,9 +36,21 @@ import {
   printError,
   formatUsdc,
   formatSeconds,
+  formatBps,
   Network,
 } from "./common";
 
+function formatDefaultOutcome(outcome: any): string {
+  if (outcome.forfeit) return "Forfeit";
+  if (outcome.percentage) return `Settle at ${outcome.percentage[0]}%`;
+  return "Crank-attested percentage";
+}
+
+function formatTeamReporting(reporting: any): string {
+  if (reporting.quorum) return `Quorum of ${reporting.quorum[0]} contributors`;
+  return "Captain";
+}
+
 function formatStatus(status: any): string {
   if (status.active) return "Active";
   if (status.reported) return "Reported";
@@ -56,15 +71,16 @@ async function main() {
   const network = (args.network || "localhost") as Network;
   const pledgeAddress = args.pledge;
   const userAddress = args.user;
-  const createdAt = args["created-at"];
+  const seed = args.index ?? args["created-at"];
   const outputJson = args.json === "true";
 
   // Validate arguments
-  if (!pledgeAddress && (!userAddress || !createdAt)) {
-    printError("Must provide either --pledge or both --user and --created-at");
+  if (!pledgeAddress && (!userAddress || !seed)) {
+    printError("Must provide either --pledge or --user with --index (or --created-at)");
     console.log("");
     console.log("Usage:");
     console.log("  npx ts-node scripts/view-pledge.ts --network <network> --pledge <address>");
+    console.log("  npx ts-node scripts/view-pledge.ts --network <network> --user <address> --index <n>");
     console.log("  npx ts-node scripts/view-pledge.ts --network <network> --user <address> --created-at <timestamp>");
     process.exit(1);
   }
@@ -103,8 +119,7 @@ async function main() {
   } else {
     try {
       const user = new PublicKey(userAddress!);
-      const createdAtBn = new anchor.BN(createdAt!);
-      [pledgePda] = derivePledgePda(user, createdAtBn);
+      [pledgePda] = derivePledgePda(user, new anchor.BN(seed!));
     } catch (error: any) {
       printError(`Failed to derive pledge PDA: ${error.message}`);
       process.exit(1);
@@ -135,6 +150,33 @@ async function main() {
         pledgeAddress: pledgePda.toBase58(),
         vaultAddress: vaultPda.toBase58(),
         user: pledge.user.toBase58(),
+        funder: pledge.funder?.toBase58() ?? null,
+        rentPayer: pledge.rentPayer?.toBase58() ?? null,
+        refundAccount: pledge.refundAccount?.toBase58() ?? null,
+        recovery: pledge.recovery?.toBase58() ?? null,
+        refundToUser: pledge.refundToUser,
+        challenge: pledge.challenge?.toBase58() ?? null,
+        challengePaid: pledge.challengePaid,
+        team: pledge.team
+          ? {
+              reporting: formatTeamReporting(pledge.team.reporting),
+              captainStake: pledge.team.captainStake.toString(),
+              contributors: pledge.team.contributors,
+              votes: pledge.team.votes,
+              refundPool: pledge.team.refundPool.toString(),
+              claimed: pledge.team.claimed,
+            }
+          : null,
+        sponsorship: pledge.sponsorship.toString(),
+        yieldPosition: pledge.yieldPosition
+          ? {
+              adapter: pledge.yieldPosition.adapter.toBase58(),
+              principal: pledge.yieldPosition.principal.toString(),
+              userBps: pledge.yieldPosition.userBps,
+              withdrawn: pledge.yieldPosition.withdrawn,
+              yieldAmount: pledge.yieldPosition.yieldAmount.toString(),
+            }
+          : null,
         mint: pledge.mint.toBase58(),
         stakeAmount: pledge.stakeAmount.toString(),
         deadline: pledge.deadline.toNumber(),
@@ -142,6 +184,18 @@ async function main() {
         completionPercentage: pledge.completionPercentage,
         reportedAt: pledge.reportedAt?.toNumber() || null,
         createdAt: pledge.createdAt.toNumber(),
+        index: pledge.index?.toNumber() ?? null,
+        deadlineShard: pledge.deadlineShard,
+        metadata: {
+          contentHash: Buffer.from(pledge.metadata.contentHash).toString("hex"),
+          category: pledge.metadata.category,
+          isPublic: pledge.metadata.isPublic,
+          version: pledge.metadataVersion,
+        },
+        earlyReport: pledge.earlyReport,
+        latePenaltyBps: pledge.latePenaltyBps,
+        gracePeriodSeconds: pledge.gracePeriodSeconds?.toNumber() ?? null,
+        defaultOutcome: formatDefaultOutcome(pledge.defaultOutcome),
         vaultBalance: vaultBalance.toString(),
         bump: pledge.bump,
         vaultBump: pledge.vaultBump,
@@ -151,7 +205,45 @@ async function main() {
       console.log("-".repeat(40));
       console.log("");
       console.log("User:", pledge.user.toBase58());
+      if (pledge.funder) {
+        console.log("Funder:", pledge.funder.toBase58());
+        console.log("Refunds To:", pledge.refundToUser ? "User" : "Funder");
+      }
+      if (pledge.rentPayer) {
+        console.log("Rent Paid By:", pledge.rentPayer.toBase58());
+      }
+      if (pledge.refundAccount) {
+        console.log("Refund Account:", pledge.refundAccount.toBase58());
+      }
+      if (pledge.recovery) {
+        console.log("Recovered By:", pledge.recovery.toBase58());
+      }
+      if (pledge.challenge) {
+        console.log("Challenge:", pledge.challenge.toBase58());
+        console.log("Challenge Payout:", pledge.challengePaid ? "Paid" : "Pending");
+      }
+      if (pledge.yieldPosition) {
+        console.log("Lent Through:", pledge.yieldPosition.adapter.toBase58());
+        console.log(
+          "Yield:",
+          pledge.yieldPosition.withdrawn
+            ? `${formatUsdc(pledge.yieldPosition.yieldAmount.toNumber())} (user keeps ${formatBps(pledge.yieldPosition.userBps)})`
+            : "Not withdrawn yet"
+        );
+      }
+      if (pledge.sponsorship.gtn(0)) {
+        console.log("Sponsor Bonus:", formatUsdc(pledge.sponsorship.toNumber()));
+      }
+      if (pledge.team) {
+        console.log("Team Reporting:", formatTeamReporting(pledge.team.reporting));
+        console.log("Captain Stake:", formatUsdc(pledge.team.captainStake.toNumber()));
+        console.log("Contributors:", pledge.team.contributors, `(${pledge.team.claimed} claimed)`);
+        if (pledge.team.votes > 0) {
+          console.log("Votes:", pledge.team.votes, `(lowest ${pledge.team.lowestVote}%)`);
+        }
+      }
       console.log("Mint:", pledge.mint.toBase58());
+      console.log("Index:", pledge.index ? pledge.index.toString() : "None (seeded by created_at)");
       console.log("Vault:", vaultPda.toBase58());
       console.log("");
       console.log("Stake Amount:", formatUsdc(pledge.stakeAmount.toNumber()));
@@ -162,12 +254,27 @@ async function main() {
         console.log("Completion:", `${pledge.completionPercentage}%`);
       }
       console.log("");
+      console.log("Goal Metadata:");
+      console.log("  Content Hash:", Buffer.from(pledge.metadata.contentHash).toString("hex"));
+      console.log("  Category:", pledge.metadata.category);
+      console.log("  Public:", pledge.metadata.isPublic ? "Yes" : "No");
+      console.log("  Version:", pledge.metadataVersion);
+      console.log("");
       console.log("Timing:");
       console.log("  Created:", formatTimestamp(pledge.createdAt.toNumber()));
       console.log("  Deadline:", formatTimestamp(pledge.deadline.toNumber()));
+      console.log("  Early Report:", pledge.earlyReport ? "Allowed" : "No");
+      console.log(
+        "  Grace Period:",
+        pledge.gracePeriodSeconds ? formatSeconds(pledge.gracePeriodSeconds.toNumber()) : "Global"
+      );
+      console.log("  If Unreported:", formatDefaultOutcome(pledge.defaultOutcome));
       if (pledge.reportedAt) {
         console.log("  Reported:", formatTimestamp(pledge.reportedAt.toNumber()));
       }
+      if (pledge.latePenaltyBps > 0) {
+        console.log("  Late Penalty:", formatBps(pledge.latePenaltyBps));
+      }
 
       // Check if deadline passed
       const now = Math.floor(Date.now() / 1000);
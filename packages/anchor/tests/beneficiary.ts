@@ -0,0 +1,180 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  getNextPledgeIndex,
+  deriveVaultPda,
+  getCurrentTimestamp,
+  getTokenBalance,
+  getFeeVault,
+  getAccruedFees,
+  airdrop,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  TestContext,
+  TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  HUNDRED_USDC,
+  DEFAULT_MAX_BENEFICIARY_BPS,
+  sleep,
+} from "./utils/helpers";
+
+describe("forfeiture_beneficiary", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  it("pays the chosen share of a forfeiture's charity slot to the beneficiary", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const friend = await createTestUser(ctx, 0);
+
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 2);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, { wallet: friend.keypair.publicKey, bps: 2000 }, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair])
+      .rpc();
+    expect((await ctx.program.account.pledge.fetch(pledgePda)).beneficiaryBps).to.equal(2000);
+
+    await sleep(3000);
+
+    await ctx.program.methods
+      .reportCompletion(0)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const friendBefore = await getTokenBalance(ctx.provider.connection, friend.tokenAccount);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+          beneficiaryTokenAccount: friend.tokenAccount,
+        },
+      })
+      .signers([crank])
+      .rpc();
+
+    // 0% completion = 10 USDC forfeited
+    // Treasury (70%) = 7,000,000
+    // Charity share (30%) = 3,000,000, beneficiary gets the 20% of it chosen at creation
+    const beneficiaryAmount = 600_000;
+
+    const friendAfter = await getTokenBalance(ctx.provider.connection, friend.tokenAccount);
+    expect(Number(friendAfter - friendBefore)).to.equal(beneficiaryAmount);
+
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
+    expect(Number(treasuryAfter - treasuryBefore)).to.equal(7_000_000);
+    expect(Number(charityAfter - charityBefore)).to.equal(3_000_000 - beneficiaryAmount);
+  });
+
+  it("fails when the beneficiary is the pledge owner", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    try {
+      await ctx.program.methods
+        .createPledge(new anchor.BN(TEN_USDC), deadline, { wallet: user.keypair.publicKey, bps: 2000 }, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          userTokenAccount: user.tokenAccount,
+          mint: ctx.usdcMint,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidBeneficiary error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidBeneficiary");
+    }
+  });
+
+  it("fails when the beneficiary share is above the admin cap", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const friend = await createTestUser(ctx, 0);
+
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    try {
+      await ctx.program.methods
+        .createPledge(new anchor.BN(TEN_USDC), deadline, { wallet: friend.keypair.publicKey, bps: DEFAULT_MAX_BENEFICIARY_BPS + 1 }, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          userTokenAccount: user.tokenAccount,
+          mint: ctx.usdcMint,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidBeneficiaryShare error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidBeneficiaryShare");
+    }
+  });
+});
@@ -0,0 +1,448 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  deriveVaultPda,
+  deriveChallengePda,
+  deriveChallengeVaultPda,
+  getNextPledgeIndex,
+  getCurrentTimestamp,
+  getTokenBalance,
+  getFeeVault,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  NO_METADATA,
+  FORFEIT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("challenge", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+  let creator: UserContext;
+  let nextChallengeId = 0;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+    creator = await createTestUser(ctx, 0);
+  });
+
+  async function createChallenge(
+    deadlineOffset = 8,
+    duel: { opponent: PublicKey; acceptByOffset: number } | null = null,
+    challengeCreator: UserContext = creator,
+    joinByOffset = Math.floor(deadlineOffset / 2)
+  ): Promise<{ challengePda: PublicKey; challengeVault: PublicKey; deadline: anchor.BN }> {
+    const challengeId = new anchor.BN(nextChallengeId++);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + deadlineOffset);
+    const joinBy = new anchor.BN(currentTimestamp + joinByOffset);
+
+    const [challengePda] = deriveChallengePda(
+      ctx.program.programId,
+      challengeCreator.keypair.publicKey,
+      challengeId
+    );
+    const [challengeVault] = deriveChallengeVaultPda(ctx.program.programId, challengePda);
+
+    await ctx.program.methods
+      .createChallenge(
+        challengeId,
+        new anchor.BN(TEN_USDC),
+        deadline,
+        joinBy,
+        duel
+          ? { opponent: duel.opponent, acceptBy: new anchor.BN(currentTimestamp + duel.acceptByOffset) }
+          : null
+      )
+      .accounts({
+        creator: challengeCreator.keypair.publicKey,
+        challenge: challengePda,
+        challengeVault,
+        mint: ctx.usdcMint,
+      })
+      .signers([challengeCreator.keypair])
+      .rpc();
+
+    return { challengePda, challengeVault, deadline };
+  }
+
+  async function joinChallenge(
+    user: UserContext,
+    challengePda: PublicKey,
+    deadline: anchor.BN,
+    stakeAmount = TEN_USDC,
+    defaultOutcome: object = FORFEIT_OUTCOME
+  ): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey }> {
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, defaultOutcome, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+        challenge: challengePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    return { pledgePda, vaultPda };
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    challengePda: PublicKey,
+    challengeVault: PublicKey,
+    completionPercentage: number
+  ) {
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+          challenge: challengePda,
+          challengeVault,
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  async function distribute(
+    challengePda: PublicKey,
+    challengeVault: PublicKey,
+    payouts: { pledgePda: PublicKey; tokenAccount: PublicKey }[]
+  ) {
+    await ctx.program.methods
+      .distributeChallenge()
+      .accounts({
+        caller: crank.publicKey,
+        challenge: challengePda,
+        challengeVault,
+        creator: creator.keypair.publicKey,
+        feeVault: getFeeVault(ctx),
+      })
+      .remainingAccounts(
+        payouts.flatMap(({ pledgePda, tokenAccount }) => [
+          { pubkey: pledgePda, isWritable: true, isSigner: false },
+          { pubkey: tokenAccount, isWritable: true, isSigner: false },
+        ])
+      )
+      .signers([crank])
+      .rpc();
+  }
+
+  it("pays the pooled forfeiture to the completer and closes the challenge", async () => {
+    const completer = await createTestUser(ctx, HUNDRED_USDC);
+    const failer = await createTestUser(ctx, HUNDRED_USDC);
+    const { challengePda, challengeVault, deadline } = await createChallenge();
+
+    const completerPledge = await joinChallenge(completer, challengePda, deadline);
+    const failerPledge = await joinChallenge(failer, challengePda, deadline);
+
+    let challenge = await ctx.program.account.challenge.fetch(challengePda);
+    expect(challenge.participants).to.equal(2);
+
+    await sleep(9000);
+    await reportAndProcess(
+      completer, completerPledge.pledgePda, completerPledge.vaultPda, challengePda, challengeVault, 100
+    );
+    await reportAndProcess(
+      failer, failerPledge.pledgePda, failerPledge.vaultPda, challengePda, challengeVault, 0
+    );
+
+    // 10% challenge cut goes to revenue, the rest is pooled
+    challenge = await ctx.program.account.challenge.fetch(challengePda);
+    expect(challenge.settled).to.equal(2);
+    expect(challenge.completers).to.equal(1);
+    expect(challenge.pool.toNumber()).to.equal(TEN_USDC * 0.9);
+
+    const creatorLamportsBefore = await ctx.provider.connection.getBalance(creator.keypair.publicKey);
+    await distribute(challengePda, challengeVault, [
+      { pledgePda: completerPledge.pledgePda, tokenAccount: completer.tokenAccount },
+    ]);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, completer.tokenAccount))).to.equal(
+      HUNDRED_USDC + TEN_USDC * 0.9
+    );
+
+    // Paid out - the challenge and its pool vault are closed and their rent goes back to the creator
+    expect(await ctx.program.account.challenge.fetchNullable(challengePda)).to.be.null;
+    expect(await ctx.provider.connection.getAccountInfo(challengeVault)).to.be.null;
+    expect(await ctx.provider.connection.getBalance(creator.keypair.publicKey)).to.be.greaterThan(
+      creatorLamportsBefore
+    );
+    const pledge = await ctx.program.account.pledge.fetch(completerPledge.pledgePda);
+    expect(pledge.challenge.toBase58()).to.equal(challengePda.toBase58());
+    expect(pledge.challengePaid).to.equal(true);
+  });
+
+  it("fails to join once joining has closed", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { challengePda, deadline } = await createChallenge(60, null, creator, 2);
+
+    await sleep(3000);
+    try {
+      await joinChallenge(user, challengePda, deadline);
+      expect.fail("Should have thrown ChallengeJoinClosed error");
+    } catch (err) {
+      expect(err.message).to.include("ChallengeJoinClosed");
+    }
+  });
+
+  it("fails to open a challenge whose joining runs past its midpoint", async () => {
+    try {
+      await createChallenge(60, null, creator, 31);
+      expect.fail("Should have thrown InvalidJoinCutoff error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidJoinCutoff");
+    }
+  });
+
+  it("fails to join with a different stake", async () => {
+    const { challengePda, deadline } = await createChallenge(60);
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await joinChallenge(user, challengePda, deadline, TEN_USDC * 2);
+      expect.fail("Should have thrown ChallengeTermsMismatch error");
+    } catch (err) {
+      expect(err.message).to.include("ChallengeTermsMismatch");
+    }
+  });
+
+  it("fails to edit a challenge pledge", async () => {
+    const { challengePda, deadline } = await createChallenge(60);
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await joinChallenge(user, challengePda, deadline);
+
+    try {
+      await ctx.program.methods
+        .editPledge(deadline.addn(3600), null)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          newDeadlineBucket: getDeadlineBucket(ctx, deadline.addn(3600)),
+          feeVault: getFeeVault(ctx),
+        })
+        .signers([user.keypair])
+        .rpc();
+      expect.fail("Should have thrown ChallengePledgeLocked error");
+    } catch (err) {
+      expect(err.message).to.include("ChallengePledgeLocked");
+    }
+  });
+
+  it("fails to settle a challenge pledge without the challenge accounts", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { challengePda, deadline } = await createChallenge();
+    const { pledgePda, vaultPda } = await joinChallenge(user, challengePda, deadline);
+
+    await sleep(9000);
+    await ctx.program.methods
+      .reportCompletion(0)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    try {
+      await ctx.program.methods
+        .processCompletion()
+        .accounts({
+          crank: crank.publicKey,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault: getFeeVault(ctx),
+          },
+        })
+        .signers([crank])
+        .rpc();
+      expect.fail("Should have thrown InvalidChallenge error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidChallenge");
+    }
+  });
+
+  it("fails to join with a default outcome other than forfeit", async () => {
+    const { challengePda, deadline } = await createChallenge(60);
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await joinChallenge(user, challengePda, deadline, TEN_USDC, { percentage: { 0: 0 } });
+      expect.fail("Should have thrown InvalidDefaultOutcome error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidDefaultOutcome");
+    }
+  });
+
+  it("fails to sponsor a challenge pledge", async () => {
+    const { challengePda, deadline } = await createChallenge(60);
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await joinChallenge(user, challengePda, deadline);
+
+    try {
+      await ctx.program.methods
+        .addSponsorship(new anchor.BN(TEN_USDC))
+        .accounts({
+          sponsor: sponsor.keypair.publicKey,
+          pledge: pledgePda,
+          mint: ctx.usdcMint,
+          sponsorTokenAccount: sponsor.tokenAccount,
+        })
+        .signers([sponsor.keypair])
+        .rpc();
+      expect.fail("Should have thrown SponsorshipUnavailable error");
+    } catch (err) {
+      expect(err.message).to.include("SponsorshipUnavailable");
+    }
+  });
+
+  it("fails to distribute before every participant settled", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { challengePda, challengeVault, deadline } = await createChallenge();
+    await joinChallenge(user, challengePda, deadline);
+
+    await sleep(9000);
+    try {
+      await distribute(challengePda, challengeVault, []);
+      expect.fail("Should have thrown ChallengeNotSettled error");
+    } catch (err) {
+      expect(err.message).to.include("ChallengeNotSettled");
+    }
+  });
+
+  describe("duel", () => {
+    it("admits the creator, then only the invited opponent", async () => {
+      const challenger = await createTestUser(ctx, HUNDRED_USDC);
+      const opponent = await createTestUser(ctx, HUNDRED_USDC);
+      const stranger = await createTestUser(ctx, HUNDRED_USDC);
+      const { challengePda, deadline } = await createChallenge(
+        60, { opponent: opponent.keypair.publicKey, acceptByOffset: 30 }, challenger
+      );
+
+      await joinChallenge(challenger, challengePda, deadline);
+      try {
+        await joinChallenge(stranger, challengePda, deadline);
+        expect.fail("Should have thrown DuelNotJoinable error");
+      } catch (err) {
+        expect(err.message).to.include("DuelNotJoinable");
+      }
+
+      await joinChallenge(opponent, challengePda, deadline);
+      const challenge = await ctx.program.account.challenge.fetch(challengePda);
+      expect(challenge.participants).to.equal(2);
+      expect(challenge.duel.opponent.toBase58()).to.equal(opponent.keypair.publicKey.toBase58());
+    });
+
+    it("refunds the creator when the opponent never accepts", async () => {
+      const challenger = await createTestUser(ctx, HUNDRED_USDC);
+      const opponent = await createTestUser(ctx, HUNDRED_USDC);
+      const { challengePda, deadline } = await createChallenge(
+        60, { opponent: opponent.keypair.publicKey, acceptByOffset: 2 }, challenger
+      );
+      const { pledgePda, vaultPda } = await joinChallenge(challenger, challengePda, deadline);
+
+      const expireDuel = () =>
+        ctx.program.methods
+          .expireDuel()
+          .accounts({
+            caller: crank.publicKey,
+            challenge: challengePda,
+            pledge: pledgePda,
+            vault: vaultPda,
+            user: challenger.keypair.publicKey,
+            userTokenAccount: challenger.tokenAccount,
+            deadlineBucket: getDeadlineBucket(ctx, deadline),
+          })
+          .signers([crank])
+          .rpc();
+
+      try {
+        await expireDuel();
+        expect.fail("Should have thrown DuelNotExpired error");
+      } catch (err) {
+        expect(err.message).to.include("DuelNotExpired");
+      }
+
+      await sleep(3000);
+      try {
+        await joinChallenge(opponent, challengePda, deadline);
+        expect.fail("Should have thrown DuelNotJoinable error");
+      } catch (err) {
+        expect(err.message).to.include("DuelNotJoinable");
+      }
+
+      await expireDuel();
+
+      expect(Number(await getTokenBalance(ctx.provider.connection, challenger.tokenAccount))).to.equal(
+        HUNDRED_USDC
+      );
+      const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+      expect(pledge.status).to.deep.equal({ cancelled: {} });
+      const challenge = await ctx.program.account.challenge.fetch(challengePda);
+      expect(challenge.distributed).to.equal(true);
+    });
+
+    it("fails to invite the creator", async () => {
+      const challenger = await createTestUser(ctx, HUNDRED_USDC);
+
+      try {
+        await createChallenge(60, { opponent: challenger.keypair.publicKey, acceptByOffset: 30 }, challenger);
+        expect.fail("Should have thrown InvalidDuel error");
+      } catch (err) {
+        expect(err.message).to.include("InvalidDuel");
+      }
+    });
+  });
+});
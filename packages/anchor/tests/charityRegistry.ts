@@ -0,0 +1,298 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAssociatedTokenAccount } from "@solana/spl-token";
+import { createHash } from "crypto";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  getNextPledgeIndex,
+  deriveVaultPda,
+  getCurrentTimestamp,
+  getTokenBalance,
+  getFeeVault,
+  getAccruedFees,
+  deriveCharityLedgerPda,
+  airdrop,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  TestContext,
+  TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  HUNDRED_USDC,
+  CHARITY_SEED,
+} from "./utils/helpers";
+
+describe("charity_registry", () => {
+  let ctx: TestContext;
+  let payout: Keypair;
+  let payoutTokenAccount: PublicKey;
+  let charityPda: PublicKey;
+  let charityLedgerPda: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    payout = Keypair.generate();
+    await airdrop(ctx.provider.connection, payout.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL);
+    payoutTokenAccount = await createAssociatedTokenAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      ctx.usdcMint,
+      payout.publicKey
+    );
+  });
+
+  it("registers a charity", async () => {
+    // Unique name per run so the PDA is fresh
+    const nameHash = createHash("sha256")
+      .update(`Test Charity ${Date.now()}`)
+      .digest();
+    [charityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(CHARITY_SEED), nameHash],
+      ctx.program.programId
+    );
+
+    await ctx.program.methods
+      .registerCharity(Array.from(nameHash), payout.publicKey)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        charity: charityPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const charity = await ctx.program.account.charityRecord.fetch(charityPda);
+    expect(charity.payout.toBase58()).to.equal(payout.publicKey.toBase58());
+    expect(charity.active).to.be.true;
+  });
+
+  it("fails to pick a charity without a ledger for the pledge's mint", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    try {
+      await ctx.program.methods
+        .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          userTokenAccount: user.tokenAccount,
+          mint: ctx.usdcMint,
+          charity: charityPda,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidCharityLedger error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidCharityLedger");
+    }
+  });
+
+  it("opens the charity's ledger for a mint", async () => {
+    await ctx.program.methods
+      .initializeCharityLedger()
+      .accounts({
+        payer: payout.publicKey,
+        charity: charityPda,
+        mint: ctx.usdcMint,
+      })
+      .signers([payout])
+      .rpc();
+
+    [charityLedgerPda] = deriveCharityLedgerPda(ctx.program.programId, charityPda, ctx.usdcMint);
+    const ledger = await ctx.program.account.charityLedger.fetch(charityLedgerPda);
+    expect(ledger.charity.toBase58()).to.equal(charityPda.toBase58());
+    expect(ledger.mint.toBase58()).to.equal(ctx.usdcMint.toBase58());
+    expect(ledger.accruedFees.toNumber()).to.equal(0);
+  });
+
+  it("routes a pledge's charity share to the selected charity", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+        charity: charityPda,
+        charityLedger: charityLedgerPda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.charity.toBase58()).to.equal(charityPda.toBase58());
+
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
+
+    // Edit penalty: 10% of 10 USDC = 1 USDC, split 70/30
+    await ctx.program.methods
+      .editPledge(null, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault: getFeeVault(ctx),
+        charity: charityPda,
+        charityLedger: charityLedgerPda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
+    expect(Number(treasuryAfter - treasuryBefore)).to.equal(700_000);
+    expect(Number(charityAfter - charityBefore)).to.equal(0);
+
+    const ledger = await ctx.program.account.charityLedger.fetch(charityLedgerPda);
+    expect(ledger.accruedFees.toNumber()).to.equal(300_000);
+  });
+
+  it("fails to settle without the pledge's charity account", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+        charity: charityPda,
+        charityLedger: charityLedgerPda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    try {
+      await ctx.program.methods
+        .editPledge(null, null)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          feeVault: getFeeVault(ctx),
+          charity: null,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidCharity error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidCharity");
+    }
+  });
+
+  it("distributes accrued fees to the charity payout wallet", async () => {
+    const before = await getTokenBalance(ctx.provider.connection, payoutTokenAccount);
+
+    await ctx.program.methods
+      .distributeCharityFees()
+      .accounts({
+        caller: payout.publicKey,
+        charity: charityPda,
+        feeVault: getFeeVault(ctx),
+        charityLedger: charityLedgerPda,
+        payoutTokenAccount,
+      })
+      .signers([payout])
+      .rpc();
+
+    const after = await getTokenBalance(ctx.provider.connection, payoutTokenAccount);
+    expect(Number(after - before)).to.equal(300_000);
+
+    const ledger = await ctx.program.account.charityLedger.fetch(charityLedgerPda);
+    expect(ledger.accruedFees.toNumber()).to.equal(0);
+  });
+
+  it("rejects new pledges for a deactivated charity", async () => {
+    await ctx.program.methods
+      .updateCharity(null, false)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        charity: charityPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    try {
+      await ctx.program.methods
+        .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          userTokenAccount: user.tokenAccount,
+          mint: ctx.usdcMint,
+          charity: charityPda,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown CharityNotActive error");
+    } catch (err) {
+      expect(err.message).to.include("CharityNotActive");
+    }
+  });
+});
@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getFeeVault,
+  getAccruedFees,
+  getPledgeDeadlineBucket,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("crank_reward", () => {
+  let ctx: TestContext;
+  let crank: UserContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    // Crank needs a token account to receive rewards
+    crank = await createTestUser(ctx, 0);
+  });
+
+  async function reportedPledge(completionPercentage: number) {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 2);
+
+    await sleep(3000);
+
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    return { user, pledgePda, vaultPda };
+  }
+
+  it("pays the crank its cut of a forfeiture", async () => {
+    const { user, pledgePda, vaultPda } = await reportedPledge(0);
+
+    const config = await ctx.program.account.programConfig.fetch(ctx.configPda);
+    const crankBefore = await getTokenBalance(ctx.provider.connection, crank.tokenAccount);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.keypair.publicKey,
+        crankTokenAccount: crank.tokenAccount,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank.keypair])
+      .rpc();
+
+    // Reward comes off the top of the 10 USDC forfeiture, the rest splits 70/30
+    const crankReward = (TEN_USDC * config.crankRewardBps) / 10000;
+    const remaining = TEN_USDC - crankReward;
+    const treasuryAmount = (remaining * 7000) / 10000;
+
+    const crankAfter = await getTokenBalance(ctx.provider.connection, crank.tokenAccount);
+    expect(Number(crankAfter - crankBefore)).to.equal(crankReward);
+
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
+    expect(Number(treasuryAfter - treasuryBefore)).to.equal(treasuryAmount);
+    expect(Number(charityAfter - charityBefore)).to.equal(remaining - treasuryAmount);
+  });
+
+  it("pays no reward on a 100% completion", async () => {
+    const { user, pledgePda, vaultPda } = await reportedPledge(100);
+
+    const crankBefore = await getTokenBalance(ctx.provider.connection, crank.tokenAccount);
+    const userBefore = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.keypair.publicKey,
+        crankTokenAccount: crank.tokenAccount,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank.keypair])
+      .rpc();
+
+    const crankAfter = await getTokenBalance(ctx.provider.connection, crank.tokenAccount);
+    expect(Number(crankAfter - crankBefore)).to.equal(0);
+
+    const userAfter = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+    expect(Number(userAfter - userBefore)).to.equal(TEN_USDC);
+  });
+
+  it("fails to set a crank reward above the cap", async () => {
+    try {
+      await ctx.program.methods
+        .updateConfig(null, null, null, null, null, null, 501, null, null, null, null, null, null, null)
+        .accounts({
+          admin: ctx.admin.publicKey,
+          config: ctx.configPda,
+        })
+        .signers([ctx.admin])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidCrankReward error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidCrankReward");
+    }
+  });
+});
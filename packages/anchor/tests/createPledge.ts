@@ -3,19 +3,24 @@ import { expect } from "chai";
 import { SystemProgram } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   setupTestContext,
   initializeConfig,
   createTestUser,
   derivePledgePda,
+  getNextPledgeIndex,
+  deriveUserCounterPda,
+  createPledge,
   deriveVaultPda,
   getCurrentTimestamp,
   getTokenBalance,
+  getDeadlineBucket,
   TestContext,
   UserContext,
   TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
   HUNDRED_USDC,
 } from "./utils/helpers";
 
@@ -34,13 +39,12 @@ describe("create_pledge", () => {
     const testUser = await createTestUser(ctx, HUNDRED_USDC);
     const stakeAmount = TEN_USDC;
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600); // 1 hour from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       testUser.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, testUser.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
@@ -61,16 +65,17 @@ describe("create_pledge", () => {
 
     try {
       await ctx.program.methods
-        .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+        .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
         .accounts({
           user: testUser.keypair.publicKey,
+          payer: testUser.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
           userTokenAccount: testUser.tokenAccount,
           mint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([testUser.keypair])
@@ -94,6 +99,8 @@ describe("create_pledge", () => {
     expect(pledge.status).to.deep.equal({ active: {} });
     expect(pledge.completionPercentage).to.be.null;
     expect(pledge.reportedAt).to.be.null;
+    expect(pledge.metadata).to.deep.equal(NO_METADATA);
+    expect(pledge.metadataVersion).to.equal(0);
 
     // Verify tokens transferred
     const userBalanceAfter = await getTokenBalance(
@@ -107,31 +114,57 @@ describe("create_pledge", () => {
     expect(Number(vaultBalance)).to.equal(stakeAmount);
   });
 
+  it("seeds back-to-back pledges with the user's counter index", async () => {
+    const counterUser = await createTestUser(ctx, HUNDRED_USDC);
+
+    // Back to back (often the same second) - seeds come from the counter, not the timestamp
+    const first = await createPledge(ctx, counterUser, TEN_USDC);
+    const second = await createPledge(ctx, counterUser, TEN_USDC);
+
+    expect(first.index.toNumber()).to.equal(0);
+    expect(second.index.toNumber()).to.equal(1);
+    expect(second.pledgePda.toBase58()).to.not.equal(first.pledgePda.toBase58());
+
+    const pledge = await ctx.program.account.pledge.fetch(second.pledgePda);
+    expect(pledge.index.toNumber()).to.equal(1);
+
+    // created_at comes from the validator clock
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    expect(Math.abs(pledge.createdAt.toNumber() - currentTimestamp)).to.be.lessThan(60);
+
+    const [userCounterPda] = deriveUserCounterPda(
+      ctx.program.programId,
+      counterUser.keypair.publicKey
+    );
+    const userCounter = await ctx.program.account.userCounter.fetch(userCounterPda);
+    expect(userCounter.nextIndex.toNumber()).to.equal(2);
+  });
+
   it("fails with zero stake amount", async () => {
     const user2 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user2.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user2.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     try {
       await ctx.program.methods
-        .createPledge(new anchor.BN(0), deadline, createdAt)
+        .createPledge(new anchor.BN(0), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
         .accounts({
           user: user2.keypair.publicKey,
+          payer: user2.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
           userTokenAccount: user2.tokenAccount,
           mint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([user2.keypair])
@@ -146,28 +179,28 @@ describe("create_pledge", () => {
   it("fails with deadline in the past", async () => {
     const user3 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp - 100); // In the past
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user3.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user3.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     try {
       await ctx.program.methods
-        .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+        .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
         .accounts({
           user: user3.keypair.publicKey,
+          payer: user3.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
           userTokenAccount: user3.tokenAccount,
           mint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([user3.keypair])
@@ -182,7 +215,7 @@ describe("create_pledge", () => {
   it("fails when program is paused", async () => {
     // First, pause the program
     await ctx.program.methods
-      .updateConfig(null, null, null, null, null, null, true)
+      .updateConfig(null, null, null, null, true, null, null, null, null, null, null, null, null, null)
       .accounts({
         admin: ctx.admin.publicKey,
         config: ctx.configPda,
@@ -192,28 +225,28 @@ describe("create_pledge", () => {
 
     const user4 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user4.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user4.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     try {
       await ctx.program.methods
-        .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+        .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
         .accounts({
           user: user4.keypair.publicKey,
+          payer: user4.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
           userTokenAccount: user4.tokenAccount,
           mint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
         .signers([user4.keypair])
@@ -226,7 +259,7 @@ describe("create_pledge", () => {
 
     // Unpause for other tests
     await ctx.program.methods
-      .updateConfig(null, null, null, null, null, null, false)
+      .updateConfig(null, null, null, null, false, null, null, null, null, null, null, null, null, null)
       .accounts({
         admin: ctx.admin.publicKey,
         config: ctx.configPda,
@@ -0,0 +1,235 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  getNextPledgeIndex,
+  deriveVaultPda,
+  getCurrentTimestamp,
+  getFeeVault,
+  getDeadlineBucket,
+  TestContext,
+  TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  HUNDRED_USDC,
+  SECONDS_PER_BUCKET,
+  DEADLINE_BUCKET_SHARDS,
+  UserContext,
+} from "./utils/helpers";
+
+describe("deadline_index", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  async function createPledgeInShard(user: UserContext, deadline: anchor.BN, shard: number) {
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, shard)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline, shard),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    return { pledgePda, vaultPda };
+  }
+
+  it("indexes a pledge by deadline day and moves it when the deadline changes", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    // Days far enough out that no other suite uses these buckets
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 400 * SECONDS_PER_BUCKET);
+    const newDeadline = new anchor.BN(currentTimestamp + 401 * SECONDS_PER_BUCKET);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+    const oldBucket = getDeadlineBucket(ctx, deadline);
+    const newBucket = getDeadlineBucket(ctx, newDeadline);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: oldBucket,
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const created = await ctx.program.account.deadlineBucket.fetch(oldBucket);
+    expect(created.day.toNumber()).to.equal(
+      Math.floor(deadline.toNumber() / SECONDS_PER_BUCKET)
+    );
+    expect(created.rentPayer.toBase58()).to.equal(user.keypair.publicKey.toBase58());
+    expect(created.pledges.map((key) => key.toBase58())).to.deep.equal([
+      pledgePda.toBase58(),
+    ]);
+
+    await ctx.program.methods
+      .editPledge(newDeadline, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: oldBucket,
+        newDeadlineBucket: newBucket,
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const emptied = await ctx.program.account.deadlineBucket.fetch(oldBucket);
+    expect(emptied.pledges).to.have.length(0);
+
+    const moved = await ctx.program.account.deadlineBucket.fetch(newBucket);
+    expect(moved.pledges.map((key) => key.toBase58())).to.deep.equal([
+      pledgePda.toBase58(),
+    ]);
+
+    // Empty bucket returns its rent to the payer
+    const rentBefore = await ctx.provider.connection.getBalance(user.keypair.publicKey);
+
+    await ctx.program.methods
+      .closeDeadlineBucket()
+      .accounts({
+        caller: ctx.admin.publicKey,
+        deadlineBucket: oldBucket,
+        rentPayer: user.keypair.publicKey,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const rentAfter = await ctx.provider.connection.getBalance(user.keypair.publicKey);
+    expect(rentAfter).to.be.greaterThan(rentBefore);
+    expect(await ctx.provider.connection.getAccountInfo(oldBucket)).to.be.null;
+
+    // Bucket still holding a pledge can't be closed
+    try {
+      await ctx.program.methods
+        .closeDeadlineBucket()
+        .accounts({
+          caller: ctx.admin.publicKey,
+          deadlineBucket: newBucket,
+          rentPayer: user.keypair.publicKey,
+        })
+        .signers([ctx.admin])
+        .rpc();
+
+      expect.fail("Should have thrown DeadlineBucketNotEmpty error");
+    } catch (err) {
+      expect(err.message).to.include("DeadlineBucketNotEmpty");
+    }
+  });
+
+  it("indexes a pledge in the shard of the day it picked and keeps the shard on edit", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 402 * SECONDS_PER_BUCKET);
+    const newDeadline = new anchor.BN(currentTimestamp + 403 * SECONDS_PER_BUCKET);
+
+    const { pledgePda, vaultPda } = await createPledgeInShard(user, deadline, 5);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.deadlineShard).to.equal(5);
+    const bucket = await ctx.program.account.deadlineBucket.fetch(getDeadlineBucket(ctx, deadline, 5));
+    expect(bucket.shard).to.equal(5);
+    expect(bucket.pledges.map((key) => key.toBase58())).to.deep.equal([pledgePda.toBase58()]);
+    expect(await ctx.provider.connection.getAccountInfo(getDeadlineBucket(ctx, deadline))).to.be.null;
+
+    await ctx.program.methods
+      .editPledge(newDeadline, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline, 5),
+        newDeadlineBucket: getDeadlineBucket(ctx, newDeadline, 5),
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const moved = await ctx.program.account.deadlineBucket.fetch(getDeadlineBucket(ctx, newDeadline, 5));
+    expect(moved.pledges.map((key) => key.toBase58())).to.deep.equal([pledgePda.toBase58()]);
+  });
+
+  it("fails to index a pledge in a shard out of range", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 404 * SECONDS_PER_BUCKET);
+
+    try {
+      await createPledgeInShard(user, deadline, DEADLINE_BUCKET_SHARDS);
+      expect.fail("Should have thrown InvalidDeadlineShard error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidDeadlineShard");
+    }
+  });
+
+  it("grows a bucket by one entry per pledge instead of reserving them all up front", async () => {
+    const first = await createTestUser(ctx, HUNDRED_USDC);
+    const second = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 410 * SECONDS_PER_BUCKET);
+    const bucket = getDeadlineBucket(ctx, deadline, 9);
+
+    // discriminator + day + shard + rent_payer + vec length + bump, then 32 bytes per pledge
+    const bucketSize = (entries: number) => 54 + entries * 32;
+
+    await createPledgeInShard(first, deadline, 9);
+    expect((await ctx.provider.connection.getAccountInfo(bucket)).data.length).to.equal(bucketSize(1));
+
+    const secondBefore = await ctx.provider.connection.getBalance(second.keypair.publicKey);
+    await createPledgeInShard(second, deadline, 9);
+    const grown = await ctx.provider.connection.getAccountInfo(bucket);
+    expect(grown.data.length).to.equal(bucketSize(2));
+    expect(grown.lamports).to.equal(
+      await ctx.provider.connection.getMinimumBalanceForRentExemption(bucketSize(2))
+    );
+
+    // The second pledger pays for its pledge, vault, counter and one entry - not a full bucket
+    const entryRent =
+      (await ctx.provider.connection.getMinimumBalanceForRentExemption(bucketSize(2))) -
+      (await ctx.provider.connection.getMinimumBalanceForRentExemption(bucketSize(1)));
+    const pledgeRent =
+      (await ctx.provider.connection.getMinimumBalanceForRentExemption(ctx.program.account.pledge.size)) +
+      (await ctx.provider.connection.getMinimumBalanceForRentExemption(ctx.program.account.userCounter.size)) +
+      (await ctx.provider.connection.getMinimumBalanceForRentExemption(165));
+    const secondAfter = await ctx.provider.connection.getBalance(second.keypair.publicKey);
+    expect(secondBefore - secondAfter).to.be.at.most(pledgeRent + entryRent + 10_000);
+
+    const indexed = await ctx.program.account.deadlineBucket.fetch(bucket);
+    expect(indexed.rentPayer.toBase58()).to.equal(first.keypair.publicKey.toBase58());
+    expect(indexed.pledges).to.have.length(2);
+  });
+});
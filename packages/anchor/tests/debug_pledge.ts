@@ -5,7 +5,6 @@ import {
   createAssociatedTokenAccount,
   mintTo,
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { Pledge } from "../target/types/pledge";
 
@@ -52,7 +51,15 @@ async function main() {
   console.log("Initializing config...");
   try {
     await program.methods
-      .initialize(treasury.publicKey, charity.publicKey, 7000, 100, 1000, new anchor.BN(86400))
+      .initialize(
+        [
+          { recipient: treasury.publicKey, bps: 7000, isCharity: false },
+          { recipient: charity.publicKey, bps: 3000, isCharity: true },
+        ],
+        100,
+        1000,
+        new anchor.BN(86400)
+      )
       .accounts({
         admin: admin.publicKey,
         config: configPda,
@@ -71,16 +78,22 @@ async function main() {
   const timestamp = await provider.connection.getBlockTime(slot);
   const createdAt = new anchor.BN(timestamp || Math.floor(Date.now() / 1000));
   const deadline = createdAt.add(new anchor.BN(3600));
+  const metadata = { contentHash: Array(32).fill(0), category: 0, isPublic: false };
 
   // Derive pledge and vault PDAs
+  // Fresh user - first pledge index is 0
   const [pledgePda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("pledge"), user.publicKey.toBuffer(), createdAt.toArrayLike(Buffer, "le", 8)],
+    [Buffer.from("pledge"), user.publicKey.toBuffer(), new anchor.BN(0).toArrayLike(Buffer, "le", 8)],
     program.programId
   );
   const [vaultPda] = PublicKey.findProgramAddressSync(
     [Buffer.from("vault"), pledgePda.toBuffer()],
     program.programId
   );
+  const [deadlineBucketPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("deadline_bucket"), new anchor.BN(Math.floor(deadline.toNumber() / 86400)).toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
   console.log("Pledge PDA:", pledgePda.toBase58());
   console.log("Vault PDA:", vaultPda.toBase58());
 
@@ -88,16 +101,17 @@ async function main() {
   console.log("Creating pledge...");
   try {
     const tx = await program.methods
-      .createPledge(new anchor.BN(10_000_000), deadline, createdAt)
+      .createPledge(new anchor.BN(10_000_000), deadline, null, metadata, false, null, { attested: {} }, false, null, 0)
       .accounts({
         user: user.publicKey,
+        payer: user.publicKey,
         config: configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: deadlineBucketPda,
         userTokenAccount,
         mint: usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user])
@@ -0,0 +1,182 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("default_outcome", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  async function setGraceBounds(minSeconds: number, maxSeconds: number) {
+    await ctx.program.methods
+      .updateConfig(
+        null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        { minSeconds: new anchor.BN(minSeconds), maxSeconds: new anchor.BN(maxSeconds) },
+        null,
+        null,
+        null
+      )
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  async function processExpired(
+    user: UserContext,
+    pledgePda: anchor.web3.PublicKey,
+    vaultPda: anchor.web3.PublicKey,
+    completionPercentage: number
+  ) {
+    await ctx.program.methods
+      .processExpired(completionPercentage)
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+    await setGraceBounds(1, 60);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  after(async () => {
+    // Later suites expect every pledge to use the global grace period
+    await setGraceBounds(0, 0);
+  });
+
+  it("forfeits an unreported pledge regardless of the crank's percentage", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1, false, 1, { forfeit: {} });
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.gracePeriodSeconds.toNumber()).to.equal(1);
+    expect(pledge.defaultOutcome).to.deep.equal({ forfeit: {} });
+
+    await sleep(4000);
+
+    const balanceBefore = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+    await processExpired(user, pledgePda, vaultPda, 100);
+    const balanceAfter = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+
+    expect(Number(balanceAfter - balanceBefore)).to.equal(0);
+    const settled = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(settled.status).to.deep.equal({ forfeited: {} });
+  });
+
+  it("settles an unreported pledge at its chosen percentage", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1, false, 1, {
+      percentage: { 0: 50 },
+    });
+
+    await sleep(4000);
+
+    const balanceBefore = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+    await processExpired(user, pledgePda, vaultPda, 0);
+    const balanceAfter = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+
+    // 50% of 10 USDC minus the 1% partial fee
+    expect(Number(balanceAfter - balanceBefore)).to.equal(4_950_000);
+    const settled = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(settled.completionPercentage).to.equal(50);
+  });
+
+  it("keeps an unreported pledge open until its own grace period ends", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1, false, 60);
+
+    await sleep(4000);
+
+    try {
+      await processExpired(user, pledgePda, vaultPda, 0);
+      expect.fail("Should have thrown GracePeriodNotEnded error");
+    } catch (err) {
+      expect(err.message).to.include("GracePeriodNotEnded");
+    }
+  });
+
+  it("fails to create a pledge with a grace period outside the bounds", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC, 3600, false, 3600);
+      expect.fail("Should have thrown InvalidGracePeriod error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidGracePeriod");
+    }
+  });
+
+  it("fails to create a pledge with a default percentage above 100", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC, 3600, false, null, { percentage: { 0: 101 } });
+      expect.fail("Should have thrown InvalidCompletionPercentage error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidCompletionPercentage");
+    }
+  });
+
+  it("fails to create a pledge with a default percentage above the admin cap", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const config = await ctx.program.account.programConfig.fetch(ctx.configPda);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC, 3600, false, null, {
+        percentage: { 0: config.maxDefaultPercentage + 1 },
+      });
+      expect.fail("Should have thrown InvalidDefaultOutcome error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidDefaultOutcome");
+    }
+  });
+
+  it("fails to set grace bounds with min above max", async () => {
+    try {
+      await setGraceBounds(60, 1);
+      expect.fail("Should have thrown InvalidGracePeriodBounds error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidGracePeriodBounds");
+    }
+  });
+});
@@ -0,0 +1,255 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import { createMint } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getTreasuryTokenAccount,
+  getCharityTokenAccount,
+  getFeeVault,
+  getFeeLedger,
+  getAccruedFees,
+  deriveFeeVaultPda,
+  deriveFeeLedgerPda,
+  airdrop,
+  getPledgeDeadlineBucket,
+  TestContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  USDC_DECIMALS,
+} from "./utils/helpers";
+
+describe("distribute_fees", () => {
+  let ctx: TestContext;
+  let caller: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    caller = Keypair.generate();
+    await airdrop(ctx.provider.connection, caller.publicKey, 1 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  it("pays accrued fees to each revenue share and clears the ledger", async () => {
+    // Edit a pledge so the fee vault holds a penalty
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+    const feeVault = getFeeVault(ctx);
+
+    await ctx.program.methods
+      .editPledge(null, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const { treasury: accruedTreasury, charity: accruedCharity } = await getAccruedFees(ctx);
+    expect(accruedTreasury > 0n).to.be.true;
+    expect(accruedCharity > 0n).to.be.true;
+
+    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
+    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
+    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVaultBefore = await getTokenBalance(ctx.provider.connection, feeVault);
+
+    // Recipient token accounts follow the config's revenue share order
+    await ctx.program.methods
+      .distributeFees()
+      .accounts({
+        caller: caller.publicKey,
+        feeVault,
+        feeLedger: getFeeLedger(ctx),
+      })
+      .remainingAccounts([
+        { pubkey: treasuryTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: charityTokenAccount, isWritable: true, isSigner: false },
+      ])
+      .signers([caller])
+      .rpc();
+
+    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
+    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVaultAfter = await getTokenBalance(ctx.provider.connection, feeVault);
+
+    expect(treasuryAfter - treasuryBefore).to.equal(accruedTreasury);
+    expect(charityAfter - charityBefore).to.equal(accruedCharity);
+    expect(feeVaultBefore - feeVaultAfter).to.equal(accruedTreasury + accruedCharity);
+
+    const { treasury, charity } = await getAccruedFees(ctx);
+    expect(treasury).to.equal(0n);
+    expect(charity).to.equal(0n);
+  });
+
+  it("rejects recipient accounts out of revenue share order", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    await ctx.program.methods
+      .editPledge(null, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    try {
+      await ctx.program.methods
+        .distributeFees()
+        .accounts({
+          caller: caller.publicKey,
+          feeVault: getFeeVault(ctx),
+          feeLedger: getFeeLedger(ctx),
+        })
+        .remainingAccounts([
+          { pubkey: await getCharityTokenAccount(ctx), isWritable: true, isSigner: false },
+          { pubkey: await getTreasuryTokenAccount(ctx), isWritable: true, isSigner: false },
+        ])
+        .signers([caller])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidRecipientAccount error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRecipientAccount");
+    }
+
+    // Leave the ledger empty for the next test
+    await ctx.program.methods
+      .distributeFees()
+      .accounts({
+        caller: caller.publicKey,
+        feeVault: getFeeVault(ctx),
+        feeLedger: getFeeLedger(ctx),
+      })
+      .remainingAccounts([
+        { pubkey: await getTreasuryTokenAccount(ctx), isWritable: true, isSigner: false },
+        { pubkey: await getCharityTokenAccount(ctx), isWritable: true, isSigner: false },
+      ])
+      .signers([caller])
+      .rpc();
+  });
+
+  it("fails when there is nothing to distribute", async () => {
+    try {
+      await ctx.program.methods
+        .distributeFees()
+        .accounts({
+          caller: caller.publicKey,
+          feeVault: getFeeVault(ctx),
+          feeLedger: getFeeLedger(ctx),
+        })
+        .remainingAccounts([
+          { pubkey: await getTreasuryTokenAccount(ctx), isWritable: true, isSigner: false },
+          { pubkey: await getCharityTokenAccount(ctx), isWritable: true, isSigner: false },
+        ])
+        .signers([caller])
+        .rpc();
+      expect.fail("Should have thrown NoFeesToDistribute error");
+    } catch (err) {
+      expect(err.message).to.include("NoFeesToDistribute");
+    }
+  });
+
+  it("keeps each mint's fees in its own ledger", async () => {
+    // Fees accrue in USDC, then a second mint gets its own vault and ledger
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+    await ctx.program.methods
+      .editPledge(null, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const otherMint = await createMint(
+      ctx.provider.connection,
+      ctx.admin,
+      ctx.admin.publicKey,
+      null,
+      USDC_DECIMALS
+    );
+    await ctx.program.methods
+      .initializeFeeVault()
+      .accounts({
+        admin: ctx.admin.publicKey,
+        mint: otherMint,
+      })
+      .signers([ctx.admin])
+      .rpc();
+    const [otherFeeVault] = deriveFeeVaultPda(ctx.program.programId, otherMint);
+    const [otherFeeLedger] = deriveFeeLedgerPda(ctx.program.programId, otherMint);
+
+    const otherLedger = await ctx.program.account.feeLedger.fetch(otherFeeLedger);
+    expect(otherLedger.mint.toBase58()).to.equal(otherMint.toBase58());
+    expect(otherLedger.accrued.every((accrued) => accrued.isZero())).to.be.true;
+
+    // The USDC balances can't be paid out of the other mint's vault
+    try {
+      await ctx.program.methods
+        .distributeFees()
+        .accounts({
+          caller: caller.publicKey,
+          feeVault: otherFeeVault,
+          feeLedger: getFeeLedger(ctx),
+        })
+        .remainingAccounts([
+          { pubkey: await getTreasuryTokenAccount(ctx), isWritable: true, isSigner: false },
+          { pubkey: await getCharityTokenAccount(ctx), isWritable: true, isSigner: false },
+        ])
+        .signers([caller])
+        .rpc();
+      expect.fail("Should have thrown InvalidFeeLedger error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidFeeLedger");
+    }
+
+    // Nor does the other mint have anything of its own to distribute
+    try {
+      await ctx.program.methods
+        .distributeFees()
+        .accounts({
+          caller: caller.publicKey,
+          feeVault: otherFeeVault,
+          feeLedger: otherFeeLedger,
+        })
+        .remainingAccounts([
+          { pubkey: await getTreasuryTokenAccount(ctx), isWritable: true, isSigner: false },
+          { pubkey: await getCharityTokenAccount(ctx), isWritable: true, isSigner: false },
+        ])
+        .signers([caller])
+        .rpc();
+      expect.fail("Should have thrown NoFeesToDistribute error");
+    } catch (err) {
+      expect(err.message).to.include("NoFeesToDistribute");
+    }
+
+    const { treasury, charity } = await getAccruedFees(ctx);
+    expect(treasury > 0n).to.be.true;
+    expect(charity > 0n).to.be.true;
+  });
+});
@@ -0,0 +1,129 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+} from "./utils/helpers";
+
+describe("report_early_completion", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  async function setAllowEarlyReport(allow: boolean) {
+    await ctx.program.methods
+      .updateConfig(null, null, null, null, null, null, null, null, allow, null, null, null, null, null)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  async function reportEarly(
+    user: UserContext,
+    pledgePda: anchor.web3.PublicKey,
+    vaultPda: anchor.web3.PublicKey
+  ) {
+    await ctx.program.methods
+      .reportEarlyCompletion()
+      .accounts({
+        user: user.keypair.publicKey,
+        settlement: {
+          user: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([user.keypair])
+      .rpc();
+  }
+
+  it("settles an opted-in pledge before its deadline with a full refund", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3600, true);
+
+    const balanceBefore = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+    await reportEarly(user, pledgePda, vaultPda);
+    const balanceAfter = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+
+    expect(Number(balanceAfter - balanceBefore)).to.equal(TEN_USDC);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.earlyReport).to.equal(true);
+    // No longer Active - edit_pledge and the regular report flow reject it
+    expect(pledge.status).to.deep.equal({ completed: {} });
+    expect(pledge.completionPercentage).to.equal(100);
+    expect(pledge.reportedAt).to.not.be.null;
+  });
+
+  it("fails when neither the pledge nor the config allows early reports", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await reportEarly(user, pledgePda, vaultPda);
+      expect.fail("Should have thrown EarlyReportNotAllowed error");
+    } catch (err) {
+      expect(err.message).to.include("EarlyReportNotAllowed");
+    }
+  });
+
+  it("accepts early reports for every pledge when enabled in config", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    await setAllowEarlyReport(true);
+    try {
+      await reportEarly(user, pledgePda, vaultPda);
+    } finally {
+      await setAllowEarlyReport(false);
+    }
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ completed: {} });
+  });
+
+  it("fails when called by someone other than the pledge owner", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const other = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3600, true);
+
+    try {
+      await ctx.program.methods
+        .reportEarlyCompletion()
+        .accounts({
+          user: other.keypair.publicKey,
+          settlement: {
+            user: user.keypair.publicKey,
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            userTokenAccount: user.tokenAccount,
+            feeVault: getFeeVault(ctx),
+          },
+        })
+        .signers([other.keypair])
+        .rpc();
+      expect.fail("Should have thrown NotPledgeOwner error");
+    } catch (err) {
+      expect(err.message).to.include("NotPledgeOwner");
+    }
+  });
+});
@@ -1,23 +1,30 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { Keypair, SystemProgram } from "@solana/web3.js";
+import { createHash } from "crypto";
 import {
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   setupTestContext,
   initializeConfig,
   createTestUser,
   derivePledgePda,
+  getNextPledgeIndex,
   deriveVaultPda,
   getCurrentTimestamp,
   getTokenBalance,
-  getTreasuryTokenAccount,
-  getCharityTokenAccount,
+  getFeeVault,
+  getAccruedFees,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
   TestContext,
   TEN_USDC,
+  createPledge,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
   HUNDRED_USDC,
+  airdrop,
   sleep,
 } from "./utils/helpers";
 
@@ -34,28 +41,28 @@ describe("edit_pledge", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600); // 1 hour from now
     const newDeadline = new anchor.BN(currentTimestamp + 7200); // 2 hours from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -64,21 +71,21 @@ describe("edit_pledge", () => {
     const pledgeBefore = await ctx.program.account.pledge.fetch(pledgePda);
     const vaultBalanceBefore = await getTokenBalance(ctx.provider.connection, vaultPda);
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     // Edit pledge with new deadline
     await ctx.program.methods
-      .editPledge(newDeadline)
+      .editPledge(newDeadline, null)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
-        treasuryTokenAccount,
-        charityTokenAccount,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: getDeadlineBucket(ctx, newDeadline),
+        feeVault,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([user.keypair])
@@ -95,8 +102,7 @@ describe("edit_pledge", () => {
     const vaultBalanceAfter = await getTokenBalance(ctx.provider.connection, vaultPda);
     expect(Number(vaultBalanceBefore - vaultBalanceAfter)).to.equal(1_000_000);
 
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
 
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(700_000);
     expect(Number(charityAfter - charityBefore)).to.equal(300_000);
@@ -107,27 +113,27 @@ describe("edit_pledge", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -135,19 +141,20 @@ describe("edit_pledge", () => {
 
     const pledgeBefore = await ctx.program.account.pledge.fetch(pledgePda);
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     // Edit pledge without new deadline (null)
     await ctx.program.methods
-      .editPledge(null)
+      .editPledge(null, null)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
-        treasuryTokenAccount,
-        charityTokenAccount,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .signers([user.keypair])
@@ -161,31 +168,61 @@ describe("edit_pledge", () => {
     expect(pledgeAfter.stakeAmount.toNumber()).to.equal(stakeAmount - 1_000_000);
   });
 
+  it("updates goal metadata and bumps its version", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    const contentHash = Array.from(
+      createHash("sha256").update("Run a marathon\nTrain four times a week").digest()
+    );
+    const metadata = { contentHash, category: 3, isPublic: true };
+
+    await ctx.program.methods
+      .editPledge(null, metadata)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.metadata.contentHash).to.deep.equal(contentHash);
+    expect(pledge.metadata.category).to.equal(3);
+    expect(pledge.metadata.isPublic).to.equal(true);
+    expect(pledge.metadataVersion).to.equal(1);
+  });
+
   it("fails if deadline has already passed", async () => {
     const user = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2); // 2 seconds from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -194,21 +231,22 @@ describe("edit_pledge", () => {
     // Wait for deadline to pass
     await sleep(3000);
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     const newDeadline = new anchor.BN(currentTimestamp + 7200);
 
     try {
       await ctx.program.methods
-        .editPledge(newDeadline)
+        .editPledge(newDeadline, null)
         .accounts({
           user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          newDeadlineBucket: getDeadlineBucket(ctx, newDeadline),
+          feeVault,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([user.keypair])
@@ -225,47 +263,48 @@ describe("edit_pledge", () => {
     const attacker = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       owner.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, owner.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: owner.keypair.publicKey,
+        payer: owner.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: owner.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([owner.keypair])
       .rpc();
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     const newDeadline = new anchor.BN(currentTimestamp + 7200);
 
     try {
       await ctx.program.methods
-        .editPledge(newDeadline)
+        .editPledge(newDeadline, null)
         .accounts({
           user: attacker.keypair.publicKey,
+          payer: attacker.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          newDeadlineBucket: getDeadlineBucket(ctx, newDeadline),
+          feeVault,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([attacker.keypair])
@@ -281,47 +320,48 @@ describe("edit_pledge", () => {
     const user = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
       .rpc();
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     const pastDeadline = new anchor.BN(currentTimestamp - 100);
 
     try {
       await ctx.program.methods
-        .editPledge(pastDeadline)
+        .editPledge(pastDeadline, null)
         .accounts({
           user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
           config: ctx.configPda,
           pledge: pledgePda,
           vault: vaultPda,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          newDeadlineBucket: getDeadlineBucket(ctx, pastDeadline),
+          feeVault,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
         .signers([user.keypair])
@@ -332,4 +372,34 @@ describe("edit_pledge", () => {
       expect(err.message).to.include("InvalidDeadline");
     }
   });
+
+  it("lets a sponsor pay for the new deadline bucket", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+    const sponsor = Keypair.generate();
+    await airdrop(ctx.provider.connection, sponsor.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+
+    // A day no other test indexes, so the bucket is created by this edit
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const newDeadline = new anchor.BN(currentTimestamp + 250 * 86400);
+    const userLamportsBefore = await ctx.provider.connection.getBalance(user.keypair.publicKey);
+
+    await ctx.program.methods
+      .editPledge(newDeadline, null)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: sponsor.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+        newDeadlineBucket: getDeadlineBucket(ctx, newDeadline),
+        feeVault: getFeeVault(ctx),
+      })
+      .signers([user.keypair, sponsor])
+      .rpc();
+
+    const bucket = await ctx.program.account.deadlineBucket.fetch(getDeadlineBucket(ctx, newDeadline));
+    expect(bucket.rentPayer.toBase58()).to.equal(sponsor.publicKey.toBase58());
+    expect(await ctx.provider.connection.getBalance(user.keypair.publicKey)).to.equal(userLamportsBefore);
+  });
 });
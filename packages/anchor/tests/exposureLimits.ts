@@ -0,0 +1,187 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  deriveUserCounterPda,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("exposure_limits", () => {
+  let ctx: TestContext;
+
+  const NO_LIMITS = {
+    minStake: new anchor.BN(0),
+    maxStake: new anchor.BN(0),
+    minDurationSeconds: new anchor.BN(0),
+    maxDurationSeconds: new anchor.BN(0),
+    maxActivePledges: 0,
+    maxActiveStake: new anchor.BN(0),
+  };
+
+  async function setLimits(limits: Partial<typeof NO_LIMITS>) {
+    await ctx.program.methods
+      .updateConfig(null, null, null, null, null, null, null, { ...NO_LIMITS, ...limits }, null, null, null, null, null, null)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  async function getUserCounter(user: UserContext) {
+    const [userCounterPda] = deriveUserCounterPda(
+      ctx.program.programId,
+      user.keypair.publicKey
+    );
+    return ctx.program.account.userCounter.fetch(userCounterPda);
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  afterEach(async () => {
+    // Config is shared with the other suites
+    await setLimits({});
+  });
+
+  it("fails with a stake below the minimum", async () => {
+    await setLimits({ minStake: new anchor.BN(TEN_USDC) });
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC - 1);
+      expect.fail("Should have thrown StakeBelowMinimum error");
+    } catch (err) {
+      expect(err.message).to.include("StakeBelowMinimum");
+    }
+  });
+
+  it("fails with a stake above the maximum", async () => {
+    await setLimits({ maxStake: new anchor.BN(TEN_USDC) });
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC + 1);
+      expect.fail("Should have thrown StakeAboveMaximum error");
+    } catch (err) {
+      expect(err.message).to.include("StakeAboveMaximum");
+    }
+  });
+
+  it("fails with a duration outside the bounds", async () => {
+    await setLimits({
+      minDurationSeconds: new anchor.BN(3600),
+      maxDurationSeconds: new anchor.BN(7 * 86400),
+    });
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC, 60);
+      expect.fail("Should have thrown DurationTooShort error");
+    } catch (err) {
+      expect(err.message).to.include("DurationTooShort");
+    }
+
+    try {
+      await createPledge(ctx, user, TEN_USDC, 8 * 86400);
+      expect.fail("Should have thrown DurationTooLong error");
+    } catch (err) {
+      expect(err.message).to.include("DurationTooLong");
+    }
+  });
+
+  it("caps a user's active pledges and active stake", async () => {
+    await setLimits({
+      maxActivePledges: 2,
+      maxActiveStake: new anchor.BN(3 * TEN_USDC),
+    });
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    await createPledge(ctx, user, TEN_USDC);
+
+    // 10 + 25 USDC would exceed the 30 USDC active stake cap
+    try {
+      await createPledge(ctx, user, 2.5 * TEN_USDC);
+      expect.fail("Should have thrown ActiveStakeLimitExceeded error");
+    } catch (err) {
+      expect(err.message).to.include("ActiveStakeLimitExceeded");
+    }
+
+    await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC);
+      expect.fail("Should have thrown TooManyActivePledges error");
+    } catch (err) {
+      expect(err.message).to.include("TooManyActivePledges");
+    }
+
+    const userCounter = await getUserCounter(user);
+    expect(userCounter.activePledges).to.equal(2);
+    expect(userCounter.activeStake.toNumber()).to.equal(2 * TEN_USDC);
+  });
+
+  it("releases a pledge from the user's totals on settlement", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 2);
+
+    const opened = await getUserCounter(user);
+    expect(opened.activePledges).to.equal(1);
+    expect(opened.activeStake.toNumber()).to.equal(TEN_USDC);
+
+    await sleep(3000);
+
+    await ctx.program.methods
+      .reportCompletion(100)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: ctx.admin.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    const settled = await getUserCounter(user);
+    expect(settled.activePledges).to.equal(0);
+    expect(settled.activeStake.toNumber()).to.equal(0);
+  });
+
+  it("fails to set a minimum above its maximum", async () => {
+    try {
+      await setLimits({
+        minStake: new anchor.BN(HUNDRED_USDC),
+        maxStake: new anchor.BN(TEN_USDC),
+      });
+      expect.fail("Should have thrown InvalidLimits error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidLimits");
+    }
+  });
+});
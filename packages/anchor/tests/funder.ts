@@ -0,0 +1,220 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  deriveVaultPda,
+  getNextPledgeIndex,
+  getCurrentTimestamp,
+  getTokenBalance,
+  getFeeVault,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("funded_pledge", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  async function createFundedPledge(
+    user: UserContext,
+    funder: UserContext,
+    refundToUser: boolean,
+    funderTokenAccount: PublicKey | null = funder.tokenAccount
+  ): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey }> {
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 2);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, refundToUser, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: null,
+        funder: funder.keypair.publicKey,
+        funderTokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair, funder.keypair])
+      .rpc();
+
+    return { pledgePda, vaultPda };
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    refundTokenAccount: PublicKey
+  ) {
+    await sleep(3000);
+
+    // The goal-setter reports, not the funder
+    await ctx.program.methods
+      .reportCompletion(100)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: refundTokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  it("takes the stake from the funder and refunds the funder", async () => {
+    const user = await createTestUser(ctx, 0);
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+
+    const { pledgePda, vaultPda } = await createFundedPledge(user, funder, false);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.user.toBase58()).to.equal(user.keypair.publicKey.toBase58());
+    expect(pledge.funder.toBase58()).to.equal(funder.keypair.publicKey.toBase58());
+    expect(pledge.refundToUser).to.equal(false);
+    expect(Number(await getTokenBalance(ctx.provider.connection, funder.tokenAccount))).to.equal(
+      HUNDRED_USDC - TEN_USDC
+    );
+
+    await reportAndProcess(user, pledgePda, vaultPda, funder.tokenAccount);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, funder.tokenAccount))).to.equal(
+      HUNDRED_USDC
+    );
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(0);
+  });
+
+  it("funds a goal-setter with no token account for the mint", async () => {
+    const keypair = Keypair.generate();
+    await airdrop(ctx.provider.connection, keypair.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    const user = { keypair, tokenAccount: null } as UserContext;
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+
+    const { pledgePda, vaultPda } = await createFundedPledge(user, funder, false);
+    await reportAndProcess(user, pledgePda, vaultPda, funder.tokenAccount);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, funder.tokenAccount))).to.equal(
+      HUNDRED_USDC
+    );
+  });
+
+  it("fails to stake from the user without their token account", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3600);
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+
+    try {
+      await ctx.program.methods
+        .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+        .accounts({
+          user: user.keypair.publicKey,
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+          vault: deriveVaultPda(ctx.program.programId, pledgePda)[0],
+          deadlineBucket: getDeadlineBucket(ctx, deadline),
+          userTokenAccount: null,
+          mint: ctx.usdcMint,
+        })
+        .signers([user.keypair])
+        .rpc();
+      expect.fail("Should have thrown InvalidTokenAccountOwner error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidTokenAccountOwner");
+    }
+  });
+
+  it("refunds the goal-setter when the funder opts in", async () => {
+    const user = await createTestUser(ctx, 0);
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+
+    const { pledgePda, vaultPda } = await createFundedPledge(user, funder, true);
+    await reportAndProcess(user, pledgePda, vaultPda, user.tokenAccount);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(TEN_USDC);
+  });
+
+  it("fails to refund a funded pledge to the goal-setter without the funder's opt-in", async () => {
+    const user = await createTestUser(ctx, 0);
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+
+    const { pledgePda, vaultPda } = await createFundedPledge(user, funder, false);
+
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, user.tokenAccount);
+      expect.fail("Should have thrown ConstraintTokenOwner error");
+    } catch (err) {
+      expect(err.message).to.include("ConstraintTokenOwner");
+    }
+  });
+
+  it("fails when the funder is the goal-setter", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createFundedPledge(user, user, false);
+      expect.fail("Should have thrown InvalidFunder error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidFunder");
+    }
+  });
+
+  it("fails when the funder signs without a funding token account", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createFundedPledge(user, funder, false, null);
+      expect.fail("Should have thrown InvalidFunder error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidFunder");
+    }
+  });
+});
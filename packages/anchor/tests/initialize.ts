@@ -12,6 +12,7 @@ import {
   DEFAULT_GRACE_PERIOD,
   CONFIG_SEED,
   airdrop,
+  getDefaultRevenueShares,
 } from "./utils/helpers";
 
 describe("initialize", () => {
@@ -36,9 +37,7 @@ describe("initialize", () => {
     if (!configExists) {
       await ctx.program.methods
         .initialize(
-          ctx.treasury.publicKey,
-          ctx.charity.publicKey,
-          DEFAULT_TREASURY_SPLIT_BPS,
+          getDefaultRevenueShares(ctx),
           DEFAULT_PARTIAL_FEE_BPS,
           DEFAULT_EDIT_PENALTY_BPS,
           new anchor.BN(DEFAULT_GRACE_PERIOD)
@@ -56,9 +55,13 @@ describe("initialize", () => {
     const config = await ctx.program.account.programConfig.fetch(ctx.configPda);
 
     expect(config.admin.toBase58()).to.equal(ctx.admin.publicKey.toBase58());
-    expect(config.treasury.toBase58()).to.equal(ctx.treasury.publicKey.toBase58());
-    expect(config.charity.toBase58()).to.equal(ctx.charity.publicKey.toBase58());
-    expect(config.treasurySplitBps).to.equal(DEFAULT_TREASURY_SPLIT_BPS);
+    expect(config.revenueShares.length).to.equal(2);
+    expect(config.revenueShares[0].recipient.toBase58()).to.equal(ctx.treasury.publicKey.toBase58());
+    expect(config.revenueShares[0].bps).to.equal(DEFAULT_TREASURY_SPLIT_BPS);
+    expect(config.revenueShares[0].isCharity).to.equal(false);
+    expect(config.revenueShares[1].recipient.toBase58()).to.equal(ctx.charity.publicKey.toBase58());
+    expect(config.revenueShares[1].bps).to.equal(10000 - DEFAULT_TREASURY_SPLIT_BPS);
+    expect(config.revenueShares[1].isCharity).to.equal(true);
     expect(config.partialFeeBps).to.equal(DEFAULT_PARTIAL_FEE_BPS);
     expect(config.editPenaltyBps).to.equal(DEFAULT_EDIT_PENALTY_BPS);
     expect(config.gracePeriodSeconds.toNumber()).to.equal(DEFAULT_GRACE_PERIOD);
@@ -69,9 +72,7 @@ describe("initialize", () => {
     try {
       await ctx.program.methods
         .initialize(
-          ctx.treasury.publicKey,
-          ctx.charity.publicKey,
-          DEFAULT_TREASURY_SPLIT_BPS,
+          getDefaultRevenueShares(ctx),
           DEFAULT_PARTIAL_FEE_BPS,
           DEFAULT_EDIT_PENALTY_BPS,
           new anchor.BN(DEFAULT_GRACE_PERIOD)
@@ -91,6 +92,41 @@ describe("initialize", () => {
     }
   });
 
+  it("rejects revenue shares that don't sum to 10000 bps", async () => {
+    try {
+      await ctx.program.methods
+        .updateConfig(
+          [
+            { recipient: ctx.treasury.publicKey, bps: 7000, isCharity: false },
+            { recipient: ctx.charity.publicKey, bps: 2000, isCharity: true },
+          ],
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null
+        )
+        .accounts({
+          admin: ctx.admin.publicKey,
+          config: ctx.configPda,
+        })
+        .signers([ctx.admin])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidRevenueShares error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRevenueShares");
+    }
+  });
+
   it("fails with invalid treasury split (> 10000 bps)", async () => {
     // Need a fresh context since config is already initialized
     const provider = anchor.AnchorProvider.env();
@@ -118,9 +154,7 @@ describe("initialize", () => {
 export async function runInitialize(ctx: TestContext): Promise<void> {
   await ctx.program.methods
     .initialize(
-      ctx.treasury.publicKey,
-      ctx.charity.publicKey,
-      DEFAULT_TREASURY_SPLIT_BPS,
+      getDefaultRevenueShares(ctx),
       DEFAULT_PARTIAL_FEE_BPS,
       DEFAULT_EDIT_PENALTY_BPS,
       new anchor.BN(DEFAULT_GRACE_PERIOD)
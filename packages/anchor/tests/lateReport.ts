@@ -0,0 +1,176 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("late_report", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  const GRACE_PERIOD = 2;
+  const LATE_WINDOW = 6;
+  const MAX_LATE_PENALTY_BPS = 5000;
+
+  async function setLateReport(gracePeriod: number | null, windowSeconds: number, maxPenaltyBps: number) {
+    await ctx.program.methods
+      .updateConfig(
+        null,
+        null,
+        null,
+        gracePeriod !== null ? new anchor.BN(gracePeriod) : null,
+        null,
+        null,
+        null,
+        null,
+        null,
+        { windowSeconds: new anchor.BN(windowSeconds), maxPenaltyBps },
+        null,
+        null,
+        null,
+        null
+      )
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  async function report(user: UserContext, pledgePda: anchor.web3.PublicKey, completionPercentage: number) {
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+    await setLateReport(GRACE_PERIOD, LATE_WINDOW, MAX_LATE_PENALTY_BPS);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  after(async () => {
+    // Later suites expect the default hard cutoff at the end of the grace period
+    await setLateReport(null, 0, 0);
+  });
+
+  it("accepts a late report and takes the escalating penalty from the refund", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1);
+
+    // Past deadline + grace, inside the late window
+    await sleep((1 + GRACE_PERIOD + LATE_WINDOW / 2) * 1000);
+    await report(user, pledgePda, 100);
+
+    const reported = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(reported.status).to.deep.equal({ reported: {} });
+    expect(reported.latePenaltyBps).to.be.greaterThan(0);
+    expect(reported.latePenaltyBps).to.be.at.most(MAX_LATE_PENALTY_BPS);
+
+    const balanceBefore = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+    const balanceAfter = await getTokenBalance(ctx.provider.connection, user.tokenAccount);
+
+    // 100% completion has no partial fee - only the late penalty is withheld
+    const penalty = Math.floor((TEN_USDC * reported.latePenaltyBps) / 10000);
+    expect(Number(balanceAfter - balanceBefore)).to.equal(TEN_USDC - penalty);
+  });
+
+  it("reports within the grace period without a penalty", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 1);
+
+    await sleep(1500);
+    await report(user, pledgePda, 100);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.latePenaltyBps).to.equal(0);
+  });
+
+  it("fails to process as expired while the late window is open", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1);
+
+    await sleep((1 + GRACE_PERIOD + 1) * 1000);
+
+    try {
+      await ctx.program.methods
+        .processExpired(0)
+        .accounts({
+          crank: crank.publicKey,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault: getFeeVault(ctx),
+          },
+        })
+        .signers([crank])
+        .rpc();
+      expect.fail("Should have thrown GracePeriodNotEnded error");
+    } catch (err) {
+      expect(err.message).to.include("GracePeriodNotEnded");
+    }
+  });
+
+  it("fails to report after the late window closes", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 1);
+
+    await sleep((1 + GRACE_PERIOD + LATE_WINDOW + 2) * 1000);
+
+    try {
+      await report(user, pledgePda, 100);
+      expect.fail("Should have thrown GracePeriodEnded error");
+    } catch (err) {
+      expect(err.message).to.include("GracePeriodEnded");
+    }
+  });
+
+  it("fails to set a late penalty above 100%", async () => {
+    try {
+      await setLateReport(null, LATE_WINDOW, 10001);
+      expect.fail("Should have thrown InvalidLateReportPolicy error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidLateReportPolicy");
+    }
+  });
+});
@@ -0,0 +1,67 @@
+import { expect } from "chai";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  TestContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+} from "./utils/helpers";
+
+// Baseline (version 0) layouts are covered by the fixture tests in utils/migration.rs
+describe("migration", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  it("creates accounts at the current layout version", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.version).to.equal(1);
+
+    const config = await ctx.program.account.programConfig.fetch(ctx.configPda);
+    expect(config.version).to.equal(1);
+  });
+
+  it("fails to migrate a pledge that is already current", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await ctx.program.methods
+        .migratePledge()
+        .accounts({
+          payer: user.keypair.publicKey,
+          pledge: pledgePda,
+        })
+        .signers([user.keypair])
+        .rpc();
+
+      expect.fail("Should have thrown AlreadyMigrated error");
+    } catch (err) {
+      expect(err.message).to.include("AlreadyMigrated");
+    }
+  });
+
+  it("fails to migrate the config when it is already current", async () => {
+    try {
+      await ctx.program.methods
+        .migrateConfig()
+        .accounts({
+          admin: ctx.admin.publicKey,
+        })
+        .signers([ctx.admin])
+        .rpc();
+
+      expect.fail("Should have thrown AlreadyMigrated error");
+    } catch (err) {
+      expect(err.message).to.include("AlreadyMigrated");
+    }
+  });
+});
@@ -10,7 +10,28 @@
  * - report_completion: User reports completion within grace period
  * - process_completion: Crank processes reported pledge
  * - process_expired: Crank processes unreported expired pledge
+ * - process_batch: Crank settles many pledges in one transaction
+ * - crank reward: Settler's cut of fees + forfeitures
+ * - deadline index: Per-day buckets of unsettled pledges for cranks
+ * - migrate_pledge / migrate_config: Upgrade accounts to the current layout
+ * - exposure limits: Per-pledge and per-user guardrails
+ * - set_user_limits: Self-imposed caps and self-exclusion
+ * - report_early_completion: 100% report and settlement before the deadline
+ * - late reports: Penalized reporting after the grace period
+ * - default outcome: Per-pledge grace period and unreported settlement policy
+ * - funded pledges: Third-party stakes with funder-directed refunds
+ * - challenges: Group pools where completers split forfeited stakes, and head-to-head duels
+ * - team pledges: Contributor stakes with captain or quorum reporting
+ * - sponsorships: Sponsor bonuses paid on completion, returned on failure
+ * - yield: Stakes lent through a lending adapter, yield split at settlement
+ * - session keys: Expiring delegated keys that report completion
+ * - rent payer: Backend-sponsored rent for users without SOL
+ * - refund account: Non-associated source accounts and a recorded refund destination
+ * - recovery: Recovery address taking over pledges of an inactive wallet
  * - update_config: Admin updates config parameters
+ * - distribute_fees: Pays accrued fees from the fee vault
+ * - register_charity / update_charity / distribute_charity_fees: Charity registry
+ * - forfeiture beneficiary: Custom recipient for a pledge's forfeitures
  */
 
 // Import all test suites
@@ -22,6 +43,27 @@ import "./reportCompletion";
 import "./processCompletion";
 import "./processExpired";
 import "./editPledge";
+import "./distributeFees";
+import "./charityRegistry";
+import "./beneficiary";
+import "./processBatch";
+import "./crankReward";
+import "./deadlineIndex";
+import "./migration";
+import "./exposureLimits";
+import "./userLimits";
+import "./earlyReport";
+import "./lateReport";
+import "./defaultOutcome";
+import "./funder";
+import "./challenge";
+import "./team";
+import "./sponsorship";
+import "./yield";
+import "./session";
+import "./rentPayer";
+import "./refundAccount";
+import "./recovery";
 
 // Note: Tests are designed to run sequentially since some tests
 // depend on program state from previous tests.
@@ -33,6 +75,27 @@ import "./editPledge";
 // 4. processCompletion - Processes reported pledges
 // 5. processExpired - Processes expired pledges
 // 6. editPledge - Edits pledges (uses initialized config)
+// 7. distributeFees - Pays out fees accrued by the suites above
+// 8. charityRegistry - Registers a charity and routes pledge fees to it
+// 9. beneficiary - Pays forfeitures to a custom beneficiary
+// 10. processBatch - Settles several pledges in one transaction
+// 11. crankReward - Pays settlers a capped cut of fees + forfeitures
+// 12. deadlineIndex - Indexes pledges by deadline day
+// 13. migration - Layout versions and in-place upgrades
+// 14. exposureLimits - Stake, duration and per-user active pledge limits
+// 15. userLimits - User-controlled caps with delayed raises
+// 16. earlyReport - Early completion reports settle immediately
+// 17. lateReport - Late reports with an escalating penalty
+// 18. defaultOutcome - Per-pledge grace periods and default outcomes
+// 19. funder - Pledges staked by a funder on a goal-setter's behalf
+// 20. challenge - Group challenges pooling forfeitures for completers, and duels
+// 21. team - Team pledges with several contributors and one goal
+// 22. sponsorship - Sponsor bonuses on top of the stake
+// 23. yield - Yield-bearing vaults through the mock lending program
+// 24. session - Session keys reporting for the owner
+// 25. rentPayer - Separate rent payer on pledge creation
+// 26. refundAccount - Any source token account and a recorded refund destination
+// 27. recovery - Wallet recovery with an inactivity window and owner challenge
 //
 // Each test file creates its own test context where needed to avoid
 // conflicts between tests.
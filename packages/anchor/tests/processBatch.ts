@@ -0,0 +1,264 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  derivePledgePda,
+  getNextPledgeIndex,
+  deriveVaultPda,
+  getCurrentTimestamp,
+  getTokenBalance,
+  getFeeVault,
+  getFeeLedger,
+  getAccruedFees,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  deriveUserCounterPda,
+  deriveRecoveryPda,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("process_batch", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    // Short grace period so pledges expire quickly
+    await ctx.program.methods
+      .updateConfig(null, null, null, new anchor.BN(2), null, null, null, null, null, null, null, null, null, null)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  async function batchAccounts(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    rentPayer: PublicKey = user.keypair.publicKey
+  ) {
+    const deadlineBucket = await getPledgeDeadlineBucket(ctx, pledgePda);
+    const [userCounter] = deriveUserCounterPda(ctx.program.programId, user.keypair.publicKey);
+    const [recovery] = deriveRecoveryPda(ctx.program.programId, user.keypair.publicKey);
+    return [
+      { pubkey: pledgePda, isWritable: true, isSigner: false },
+      { pubkey: vaultPda, isWritable: true, isSigner: false },
+      { pubkey: user.keypair.publicKey, isWritable: true, isSigner: false },
+      { pubkey: user.tokenAccount, isWritable: true, isSigner: false },
+      { pubkey: deadlineBucket, isWritable: true, isSigner: false },
+      { pubkey: userCounter, isWritable: true, isSigner: false },
+      { pubkey: recovery, isWritable: true, isSigner: false },
+      { pubkey: rentPayer, isWritable: true, isSigner: false },
+    ];
+  }
+
+  it("settles expired pledges and skips ineligible ones", async () => {
+    const forfeitUser = await createTestUser(ctx, HUNDRED_USDC);
+    const partialUser = await createTestUser(ctx, HUNDRED_USDC);
+    const activeUser = await createTestUser(ctx, HUNDRED_USDC);
+
+    const forfeit = await createPledge(ctx, forfeitUser, TEN_USDC, 1);
+    const partial = await createPledge(ctx, partialUser, TEN_USDC, 1);
+    const active = await createPledge(ctx, activeUser, TEN_USDC, 3600);
+
+    // Wait for deadline + grace period to pass
+    await sleep(5000);
+
+    const partialBalanceBefore = await getTokenBalance(
+      ctx.provider.connection,
+      partialUser.tokenAccount
+    );
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
+
+    await ctx.program.methods
+      .processBatch(Buffer.from([0, 50, 0]))
+      .accounts({
+        crank: crank.publicKey,
+        feeVault: getFeeVault(ctx),
+        feeLedger: getFeeLedger(ctx),
+      })
+      .remainingAccounts([
+        ...(await batchAccounts(forfeitUser, forfeit.pledgePda, forfeit.vaultPda)),
+        ...(await batchAccounts(partialUser, partial.pledgePda, partial.vaultPda)),
+        ...(await batchAccounts(activeUser, active.pledgePda, active.vaultPda)),
+      ])
+      .signers([crank])
+      .rpc();
+
+    // 0%: full 10 USDC forfeited
+    const forfeitPledge = await ctx.program.account.pledge.fetch(forfeit.pledgePda);
+    expect(forfeitPledge.status).to.deep.equal({ forfeited: {} });
+    expect(forfeitPledge.completionPercentage).to.equal(0);
+
+    // 50%: same amounts as process_expired(50)
+    const partialPledge = await ctx.program.account.pledge.fetch(partial.pledgePda);
+    expect(partialPledge.status).to.deep.equal({ completed: {} });
+    expect(partialPledge.completionPercentage).to.equal(50);
+
+    const partialBalanceAfter = await getTokenBalance(
+      ctx.provider.connection,
+      partialUser.tokenAccount
+    );
+    expect(Number(partialBalanceAfter - partialBalanceBefore)).to.equal(4_950_000);
+
+    // Treasury = 7,000,000 + 3,535,000, Charity = 3,000,000 + 1,515,000
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
+    expect(Number(treasuryAfter - treasuryBefore)).to.equal(10_535_000);
+    expect(Number(charityAfter - charityBefore)).to.equal(4_515_000);
+
+    // Settled pledges leave the deadline index
+    const bucket = await ctx.program.account.deadlineBucket.fetch(
+      await getPledgeDeadlineBucket(ctx, forfeit.pledgePda)
+    );
+    expect(bucket.pledges.map((key) => key.toBase58())).to.not.include(
+      forfeit.pledgePda.toBase58()
+    );
+
+    // Deadline not passed - skipped, not aborted
+    const activePledge = await ctx.program.account.pledge.fetch(active.pledgePda);
+    expect(activePledge.status).to.deep.equal({ active: {} });
+    const activeVault = await ctx.provider.connection.getAccountInfo(active.vaultPda);
+    expect(activeVault).to.not.be.null;
+  });
+
+  it("skips pledges with a custom beneficiary", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const friend = await createTestUser(ctx, 0);
+
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 1);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, { wallet: friend.keypair.publicKey, bps: 2000 }, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await sleep(5000);
+
+    await ctx.program.methods
+      .processBatch(Buffer.from([0]))
+      .accounts({
+        crank: crank.publicKey,
+        feeVault: getFeeVault(ctx),
+        feeLedger: getFeeLedger(ctx),
+      })
+      .remainingAccounts(await batchAccounts(user, pledgePda, vaultPda))
+      .signers([crank])
+      .rpc();
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ active: {} });
+  });
+
+  it("returns a sponsored pledge's vault rent to its payer", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 1);
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    // The crank sponsors the pledge's rent
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: crank.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair, crank])
+      .rpc();
+
+    await sleep(5000);
+
+    const settle = async (rentPayer: PublicKey) =>
+      ctx.program.methods
+        .processBatch(Buffer.from([0]))
+        .accounts({
+          crank: crank.publicKey,
+          feeVault: getFeeVault(ctx),
+          feeLedger: getFeeLedger(ctx),
+        })
+        .remainingAccounts(await batchAccounts(user, pledgePda, vaultPda, rentPayer))
+        .signers([crank])
+        .rpc();
+
+    // Skipped when the group names the user instead of the rent payer
+    await settle(user.keypair.publicKey);
+    let pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ active: {} });
+
+    const vaultRent = await ctx.provider.connection.getBalance(vaultPda);
+    const crankBefore = await ctx.provider.connection.getBalance(crank.publicKey);
+    await settle(crank.publicKey);
+
+    pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ forfeited: {} });
+    expect(await ctx.provider.connection.getBalance(crank.publicKey)).to.equal(crankBefore + vaultRent);
+  });
+
+  it("fails when completion percentages don't match the account groups", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 1);
+
+    await sleep(5000);
+
+    try {
+      await ctx.program.methods
+        .processBatch(Buffer.from([0, 0]))
+        .accounts({
+          crank: crank.publicKey,
+          feeVault: getFeeVault(ctx),
+          feeLedger: getFeeLedger(ctx),
+        })
+        .remainingAccounts(await batchAccounts(user, pledgePda, vaultPda))
+        .signers([crank])
+        .rpc();
+      expect.fail("Should have thrown InvalidBatch error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidBatch");
+    }
+  });
+});
@@ -3,21 +3,25 @@ import { expect } from "chai";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   setupTestContext,
   initializeConfig,
   createTestUser,
   derivePledgePda,
+  getNextPledgeIndex,
   deriveVaultPda,
   getCurrentTimestamp,
   getTokenBalance,
-  getTreasuryTokenAccount,
-  getCharityTokenAccount,
+  getFeeVault,
+  getAccruedFees,
   airdrop,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
   TestContext,
   TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
   HUNDRED_USDC,
   sleep,
 } from "./utils/helpers";
@@ -40,23 +44,24 @@ describe("process_completion", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     // Create pledge
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
       })
@@ -80,10 +85,8 @@ describe("process_completion", () => {
       user.tokenAccount
     );
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     // Process completion
     try {
@@ -91,12 +94,14 @@ describe("process_completion", () => {
         .processCompletion()
         .accounts({
           crank: crank.publicKey,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+          },
         })
         .signers([crank])
         .rpc();
@@ -120,8 +125,7 @@ describe("process_completion", () => {
     expect(Number(userBalanceAfter - userBalanceBefore)).to.equal(stakeAmount);
 
     // Verify no fees collected
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(0);
     expect(Number(charityAfter - charityBefore)).to.equal(0);
 
@@ -135,22 +139,23 @@ describe("process_completion", () => {
     const stakeAmount = TEN_USDC; // 10 USDC
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
       })
@@ -173,22 +178,22 @@ describe("process_completion", () => {
       user.tokenAccount
     );
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     try {
       await ctx.program.methods
         .processCompletion()
         .accounts({
           crank: crank.publicKey,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+          },
         })
         .signers([crank])
         .rpc();
@@ -220,8 +225,7 @@ describe("process_completion", () => {
     const userRefund = Number(userBalanceAfter - userBalanceBefore);
     expect(userRefund).to.equal(4_950_000); // 4.95 USDC
 
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
 
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(3_535_000); // 70% of 5,050,000
     expect(Number(charityAfter - charityBefore)).to.equal(1_515_000); // 30% of 5,050,000
@@ -235,22 +239,23 @@ describe("process_completion", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
       })
@@ -273,22 +278,22 @@ describe("process_completion", () => {
       user.tokenAccount
     );
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     try {
       await ctx.program.methods
         .processCompletion()
         .accounts({
           crank: crank.publicKey,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+          },
         })
         .signers([crank])
         .rpc();
@@ -314,8 +319,7 @@ describe("process_completion", () => {
     );
     expect(Number(userBalanceAfter - userBalanceBefore)).to.equal(0); // No refund
 
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
 
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(7_000_000); // 70%
     expect(Number(charityAfter - charityBefore)).to.equal(3_000_000); // 30%
@@ -327,42 +331,44 @@ describe("process_completion", () => {
   it("fails to process non-reported pledge", async () => {
     const user = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
       })
       .signers([user.keypair])
       .rpc();
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     try {
       await ctx.program.methods
         .processCompletion()
         .accounts({
           crank: crank.publicKey,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
+          settlement: {
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+          },
         })
         .signers([crank])
         .rpc();
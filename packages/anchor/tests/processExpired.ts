@@ -3,21 +3,25 @@ import { expect } from "chai";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   setupTestContext,
   initializeConfig,
   createTestUser,
   derivePledgePda,
+  getNextPledgeIndex,
   deriveVaultPda,
   getCurrentTimestamp,
   getTokenBalance,
-  getTreasuryTokenAccount,
-  getCharityTokenAccount,
+  getFeeVault,
+  getAccruedFees,
   airdrop,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
   TestContext,
   TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
   HUNDRED_USDC,
   sleep,
 } from "./utils/helpers";
@@ -32,7 +36,7 @@ describe("process_expired", () => {
 
     // Update config with short grace period for testing (2 seconds instead of 1 day)
     await ctx.program.methods
-      .updateConfig(null, null, null, null, null, new anchor.BN(2), null)
+      .updateConfig(null, null, null, new anchor.BN(2), null, null, null, null, null, null, null, null, null, null)
       .accounts({
         admin: ctx.admin.publicKey,
         config: ctx.configPda,
@@ -49,27 +53,27 @@ describe("process_expired", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 1); // 1 second from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -83,24 +87,24 @@ describe("process_expired", () => {
       user.tokenAccount
     );
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     // Crank processes with 50% completion from DB data
     await ctx.program.methods
       .processExpired(50)
       .accounts({
         crank: crank.publicKey,
-        config: ctx.configPda,
-        pledge: pledgePda,
-        vault: vaultPda,
-        user: user.keypair.publicKey,
-        userTokenAccount: user.tokenAccount,
-        treasuryTokenAccount,
-        charityTokenAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        settlement: {
+          config: ctx.configPda,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
       })
       .signers([crank])
       .rpc();
@@ -116,8 +120,7 @@ describe("process_expired", () => {
     );
     expect(Number(userBalanceAfter - userBalanceBefore)).to.equal(4_950_000);
 
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
 
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(3_535_000);
     expect(Number(charityAfter - charityBefore)).to.equal(1_515_000);
@@ -132,27 +135,27 @@ describe("process_expired", () => {
     const stakeAmount = TEN_USDC;
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 1);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+      .createPledge(new anchor.BN(stakeAmount), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -165,24 +168,24 @@ describe("process_expired", () => {
       user.tokenAccount
     );
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
-    const treasuryBefore = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityBefore = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const feeVault = getFeeVault(ctx);
+    const { treasury: treasuryBefore, charity: charityBefore } = await getAccruedFees(ctx);
 
     // Crank processes with 0% completion (user didn't do anything)
     await ctx.program.methods
       .processExpired(0)
       .accounts({
         crank: crank.publicKey,
-        config: ctx.configPda,
-        pledge: pledgePda,
-        vault: vaultPda,
-        user: user.keypair.publicKey,
-        userTokenAccount: user.tokenAccount,
-        treasuryTokenAccount,
-        charityTokenAccount,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        settlement: {
+          config: ctx.configPda,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
       })
       .signers([crank])
       .rpc();
@@ -193,8 +196,7 @@ describe("process_expired", () => {
     );
     expect(Number(userBalanceAfter - userBalanceBefore)).to.equal(0);
 
-    const treasuryAfter = await getTokenBalance(ctx.provider.connection, treasuryTokenAccount);
-    const charityAfter = await getTokenBalance(ctx.provider.connection, charityTokenAccount);
+    const { treasury: treasuryAfter, charity: charityAfter } = await getAccruedFees(ctx);
 
     expect(Number(treasuryAfter - treasuryBefore)).to.equal(7_000_000);
     expect(Number(charityAfter - charityBefore)).to.equal(3_000_000);
@@ -207,48 +209,49 @@ describe("process_expired", () => {
     const user = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600); // Far in future
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
       .rpc();
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     try {
       await ctx.program.methods
         .processExpired(0)
         .accounts({
           crank: crank.publicKey,
-          config: ctx.configPda,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          settlement: {
+            config: ctx.configPda,
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
         })
         .signers([crank])
         .rpc();
@@ -263,27 +266,27 @@ describe("process_expired", () => {
     const user = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 1);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -304,8 +307,7 @@ describe("process_expired", () => {
 
     await sleep(3000);
 
-    const treasuryTokenAccount = await getTreasuryTokenAccount(ctx);
-    const charityTokenAccount = await getCharityTokenAccount(ctx);
+    const feeVault = getFeeVault(ctx);
 
     // Try to process as expired (but pledge is already reported)
     try {
@@ -313,14 +315,16 @@ describe("process_expired", () => {
         .processExpired(0)
         .accounts({
           crank: crank.publicKey,
-          config: ctx.configPda,
-          pledge: pledgePda,
-          vault: vaultPda,
-          user: user.keypair.publicKey,
-          userTokenAccount: user.tokenAccount,
-          treasuryTokenAccount,
-          charityTokenAccount,
-          tokenProgram: TOKEN_PROGRAM_ID,
+          settlement: {
+            config: ctx.configPda,
+            pledge: pledgePda,
+            vault: vaultPda,
+            deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+            user: user.keypair.publicKey,
+            userTokenAccount: user.tokenAccount,
+            feeVault,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          },
         })
         .signers([crank])
         .rpc();
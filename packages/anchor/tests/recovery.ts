@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  deriveRecoveryPda,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("recovery", () => {
+  let ctx: TestContext;
+  let recoveryWallet: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    recoveryWallet = Keypair.generate();
+    await airdrop(ctx.provider.connection, recoveryWallet.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  async function setRecovery(user: UserContext, recoveryAddress: PublicKey): Promise<PublicKey> {
+    await ctx.program.methods
+      .setRecovery(recoveryAddress)
+      .accounts({
+        user: user.keypair.publicKey,
+      })
+      .signers([user.keypair])
+      .rpc();
+    return deriveRecoveryPda(ctx.program.programId, user.keypair.publicKey)[0];
+  }
+
+  it("registers a recovery address on the user's profile", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const recoveryPda = await setRecovery(user, recoveryWallet.publicKey);
+
+    const recovery = await ctx.program.account.recovery.fetch(recoveryPda);
+    expect(recovery.user.toBase58()).to.equal(user.keypair.publicKey.toBase58());
+    expect(recovery.recoveryAddress.toBase58()).to.equal(recoveryWallet.publicKey.toBase58());
+    expect(recovery.lastActive.toNumber()).to.be.greaterThan(0);
+    expect(recovery.initiatedAt).to.be.null;
+
+    // Setting it again replaces the address
+    const replacement = Keypair.generate().publicKey;
+    await setRecovery(user, replacement);
+    const replaced = await ctx.program.account.recovery.fetch(recoveryPda);
+    expect(replaced.recoveryAddress.toBase58()).to.equal(replacement.toBase58());
+  });
+
+  it("counts the owner's pledge instructions as activity", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const recoveryPda = await setRecovery(user, recoveryWallet.publicKey);
+    const before = await ctx.program.account.recovery.fetch(recoveryPda);
+
+    await sleep(2000);
+    await createPledge(ctx, user, TEN_USDC);
+
+    const after = await ctx.program.account.recovery.fetch(recoveryPda);
+    expect(after.lastActive.toNumber()).to.be.greaterThan(before.lastActive.toNumber());
+  });
+
+  it("fails to register the user's own wallet", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await setRecovery(user, user.keypair.publicKey);
+      expect.fail("Should have thrown InvalidRecovery error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRecovery");
+    }
+  });
+
+  it("fails to start a recovery while the owner is active", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const recoveryPda = await setRecovery(user, recoveryWallet.publicKey);
+
+    try {
+      await ctx.program.methods
+        .initiateRecovery()
+        .accounts({
+          recoveryAddress: recoveryWallet.publicKey,
+          recovery: recoveryPda,
+        })
+        .signers([recoveryWallet])
+        .rpc();
+      expect.fail("Should have thrown RecoveryNotDue error");
+    } catch (err) {
+      expect(err.message).to.include("RecoveryNotDue");
+    }
+  });
+
+  it("fails to start a recovery from another wallet", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const recoveryPda = await setRecovery(user, recoveryWallet.publicKey);
+    const stranger = Keypair.generate();
+    await airdrop(ctx.provider.connection, stranger.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+
+    try {
+      await ctx.program.methods
+        .initiateRecovery()
+        .accounts({
+          recoveryAddress: stranger.publicKey,
+          recovery: recoveryPda,
+        })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have thrown InvalidRecovery error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRecovery");
+    }
+  });
+
+  it("fails to cancel when no recovery is pending", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    await setRecovery(user, recoveryWallet.publicKey);
+
+    try {
+      await ctx.program.methods
+        .cancelRecovery()
+        .accounts({
+          user: user.keypair.publicKey,
+        })
+        .signers([user.keypair])
+        .rpc();
+      expect.fail("Should have thrown RecoveryNotPending error");
+    } catch (err) {
+      expect(err.message).to.include("RecoveryNotPending");
+    }
+  });
+
+  it("fails to take over a pledge before a recovery is final", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const recoveryPda = await setRecovery(user, recoveryWallet.publicKey);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await ctx.program.methods
+        .claimRecovery()
+        .accounts({
+          recoveryAddress: recoveryWallet.publicKey,
+          recovery: recoveryPda,
+          pledge: pledgePda,
+        })
+        .signers([recoveryWallet])
+        .rpc();
+      expect.fail("Should have thrown RecoveryNotFinal error");
+    } catch (err) {
+      expect(err.message).to.include("RecoveryNotFinal");
+    }
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.recovery).to.be.null;
+  });
+});
@@ -0,0 +1,169 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAccount, getAccount, mintTo } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  derivePledgePda,
+  deriveVaultPda,
+  getNextPledgeIndex,
+  getCurrentTimestamp,
+  getFeeVault,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("refund_account", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  /** Token account for the USDC mint at a fresh (non-associated) address */
+  async function createPlainTokenAccount(owner: PublicKey, amount: number = 0): Promise<PublicKey> {
+    const tokenAccount = await createAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      ctx.usdcMint,
+      owner,
+      Keypair.generate()
+    );
+    if (amount > 0) {
+      await mintTo(ctx.provider.connection, ctx.admin, ctx.usdcMint, tokenAccount, ctx.admin, amount);
+    }
+    return tokenAccount;
+  }
+
+  async function createPledgeFrom(
+    user: UserContext,
+    refundAccount: PublicKey | null
+  ): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey }> {
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 2);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        refundAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    return { pledgePda, vaultPda };
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    userTokenAccount: PublicKey
+  ) {
+    await sleep(3000);
+    await ctx.program.methods
+      .reportCompletion(100)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  it("stakes from a token account that is not the user's associated account", async () => {
+    const keypair = Keypair.generate();
+    await airdrop(ctx.provider.connection, keypair.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+    const user = { keypair, tokenAccount: await createPlainTokenAccount(keypair.publicKey, HUNDRED_USDC) };
+
+    const { vaultPda } = await createPledgeFrom(user, null);
+
+    const vault = await getAccount(ctx.provider.connection, vaultPda);
+    expect(Number(vault.amount)).to.equal(TEN_USDC);
+    const source = await getAccount(ctx.provider.connection, user.tokenAccount);
+    expect(Number(source.amount)).to.equal(HUNDRED_USDC - TEN_USDC);
+  });
+
+  it("pays the refund into the recorded refund account", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const refundAccount = await createPlainTokenAccount(user.keypair.publicKey);
+
+    const { pledgePda, vaultPda } = await createPledgeFrom(user, refundAccount);
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.refundAccount.toBase58()).to.equal(refundAccount.toBase58());
+
+    await reportAndProcess(user, pledgePda, vaultPda, refundAccount);
+
+    const refunded = await getAccount(ctx.provider.connection, refundAccount);
+    expect(Number(refunded.amount)).to.be.greaterThan(0);
+  });
+
+  it("fails to settle into a token account other than the recorded one", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const refundAccount = await createPlainTokenAccount(user.keypair.publicKey);
+    const { pledgePda, vaultPda } = await createPledgeFrom(user, refundAccount);
+
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, user.tokenAccount);
+      expect.fail("Should have thrown InvalidRefundAccount error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRefundAccount");
+    }
+  });
+
+  it("fails to record a refund account owned by another wallet", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const other = await createPlainTokenAccount(Keypair.generate().publicKey);
+
+    try {
+      await createPledgeFrom(user, other);
+      expect.fail("Should have thrown InvalidRefundAccount error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRefundAccount");
+    }
+  });
+});
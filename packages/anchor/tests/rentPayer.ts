@@ -0,0 +1,152 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  derivePledgePda,
+  deriveVaultPda,
+  getNextPledgeIndex,
+  getCurrentTimestamp,
+  getFeeVault,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("rent_payer", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+  let backend: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+    backend = Keypair.generate();
+    await airdrop(ctx.provider.connection, backend.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  /** User holding USDC but no SOL at all */
+  async function createUsdcOnlyUser(): Promise<UserContext> {
+    const keypair = Keypair.generate();
+    const tokenAccount = await createAssociatedTokenAccount(
+      ctx.provider.connection,
+      ctx.admin,
+      ctx.usdcMint,
+      keypair.publicKey
+    );
+    await mintTo(ctx.provider.connection, ctx.admin, ctx.usdcMint, tokenAccount, ctx.admin, HUNDRED_USDC);
+    return { keypair, tokenAccount };
+  }
+
+  async function createSponsoredPledge(user: UserContext): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey }> {
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 2);
+
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: backend.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: user.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair, backend])
+      .rpc();
+
+    return { pledgePda, vaultPda };
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    rentPayer: PublicKey | null
+  ) {
+    await sleep(3000);
+    await ctx.program.methods
+      .reportCompletion(100)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+          rentPayer,
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  it("creates a pledge for a user without SOL and returns the rent to the payer", async () => {
+    const user = await createUsdcOnlyUser();
+    const { pledgePda, vaultPda } = await createSponsoredPledge(user);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.rentPayer.toBase58()).to.equal(backend.publicKey.toBase58());
+    expect(await ctx.provider.connection.getBalance(user.keypair.publicKey)).to.equal(0);
+
+    const vaultRent = await ctx.provider.connection.getBalance(vaultPda);
+    const backendBefore = await ctx.provider.connection.getBalance(backend.publicKey);
+    await reportAndProcess(user, pledgePda, vaultPda, backend.publicKey);
+
+    expect(await ctx.provider.connection.getBalance(backend.publicKey)).to.equal(backendBefore + vaultRent);
+    expect(await ctx.provider.connection.getBalance(user.keypair.publicKey)).to.equal(0);
+  });
+
+  it("records no rent payer when the user pays", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.rentPayer).to.be.null;
+  });
+
+  it("fails to settle a sponsored pledge without its rent payer", async () => {
+    const user = await createUsdcOnlyUser();
+    const { pledgePda, vaultPda } = await createSponsoredPledge(user);
+
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, null);
+      expect.fail("Should have thrown InvalidRentPayer error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidRentPayer");
+    }
+  });
+});
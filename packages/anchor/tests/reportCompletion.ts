@@ -3,18 +3,21 @@ import { expect } from "chai";
 import { SystemProgram } from "@solana/web3.js";
 import {
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   setupTestContext,
   initializeConfig,
   createTestUser,
   derivePledgePda,
+  getNextPledgeIndex,
   deriveVaultPda,
   getCurrentTimestamp,
+  getDeadlineBucket,
   TestContext,
   UserContext,
   TEN_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
   HUNDRED_USDC,
   sleep,
 } from "./utils/helpers";
@@ -32,27 +35,27 @@ describe("report_completion", () => {
   it("reports 100% completion within grace period", async () => {
     // Create a pledge with a very short deadline (2 seconds)
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2); // 2 seconds from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user.keypair])
@@ -82,27 +85,27 @@ describe("report_completion", () => {
   it("reports partial completion (50%)", async () => {
     const user2 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user2.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user2.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user2.keypair.publicKey,
+        payer: user2.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user2.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user2.keypair])
@@ -128,27 +131,27 @@ describe("report_completion", () => {
   it("reports 0% completion", async () => {
     const user3 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user3.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user3.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user3.keypair.publicKey,
+        payer: user3.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user3.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user3.keypair])
@@ -174,27 +177,27 @@ describe("report_completion", () => {
   it("fails to report before deadline", async () => {
     const user4 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 3600); // 1 hour from now
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user4.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user4.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user4.keypair.publicKey,
+        payer: user4.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user4.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user4.keypair])
@@ -221,27 +224,27 @@ describe("report_completion", () => {
   it("fails with invalid completion percentage (> 100)", async () => {
     const user5 = await createTestUser(ctx, HUNDRED_USDC);
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       user5.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, user5.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: user5.keypair.publicKey,
+        payer: user5.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: user5.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([user5.keypair])
@@ -271,27 +274,27 @@ describe("report_completion", () => {
     const attacker = await createTestUser(ctx, HUNDRED_USDC);
 
     const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-    const createdAt = new anchor.BN(currentTimestamp);
     const deadline = new anchor.BN(currentTimestamp + 2);
 
     const [pledgePda] = derivePledgePda(
       ctx.program.programId,
       owner.keypair.publicKey,
-      createdAt
+      await getNextPledgeIndex(ctx, owner.keypair.publicKey)
     );
     const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
     await ctx.program.methods
-      .createPledge(new anchor.BN(TEN_USDC), deadline, createdAt)
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
       .accounts({
         user: owner.keypair.publicKey,
+        payer: owner.keypair.publicKey,
         config: ctx.configPda,
         pledge: pledgePda,
         vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
         userTokenAccount: owner.tokenAccount,
         mint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([owner.keypair])
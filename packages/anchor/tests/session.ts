@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  deriveSessionPda,
+  getCurrentTimestamp,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("session", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  async function createSession(
+    user: UserContext,
+    sessionKey: PublicKey,
+    expiresIn: number,
+    pledge: PublicKey | null = null
+  ): Promise<PublicKey> {
+    const now = await getCurrentTimestamp(ctx.provider.connection);
+    await ctx.program.methods
+      .createSession(sessionKey, new anchor.BN(now + expiresIn), pledge)
+      .accounts({
+        user: user.keypair.publicKey,
+      })
+      .signers([user.keypair])
+      .rpc();
+    return deriveSessionPda(ctx.program.programId, user.keypair.publicKey)[0];
+  }
+
+  async function reportWithSession(
+    sessionKey: Keypair,
+    pledgePda: PublicKey,
+    session: PublicKey | null,
+    completionPercentage: number = 100
+  ) {
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: sessionKey.publicKey,
+        pledge: pledgePda,
+        session,
+      })
+      .signers([sessionKey])
+      .rpc();
+  }
+
+  it("reports completion with a session key", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sessionKey = Keypair.generate();
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    const session = await createSession(user, sessionKey.publicKey, 3600);
+    const record = await ctx.program.account.sessionAuthority.fetch(session);
+    expect(record.sessionKey.toBase58()).to.equal(sessionKey.publicKey.toBase58());
+    expect(record.pledge).to.be.null;
+
+    await sleep(4000);
+    await reportWithSession(sessionKey, pledgePda, session, 80);
+
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ reported: {} });
+    expect(pledge.completionPercentage).to.equal(80);
+  });
+
+  it("fails to report another pledge with a pledge-scoped session", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sessionKey = Keypair.generate();
+    const { pledgePda: scopedPledge } = await createPledge(ctx, user, TEN_USDC, 3);
+    const { pledgePda: otherPledge } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    const session = await createSession(user, sessionKey.publicKey, 3600, scopedPledge);
+    await sleep(4000);
+
+    try {
+      await reportWithSession(sessionKey, otherPledge, session);
+      expect.fail("Should have thrown InvalidSession error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSession");
+    }
+    await reportWithSession(sessionKey, scopedPledge, session);
+  });
+
+  it("fails to report with an expired session", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sessionKey = Keypair.generate();
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    const session = await createSession(user, sessionKey.publicKey, 2);
+    await sleep(4000);
+
+    try {
+      await reportWithSession(sessionKey, pledgePda, session);
+      expect.fail("Should have thrown SessionExpired error");
+    } catch (err) {
+      expect(err.message).to.include("SessionExpired");
+    }
+  });
+
+  it("fails to report with a session key but no session", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sessionKey = Keypair.generate();
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await createSession(user, sessionKey.publicKey, 3600);
+    await sleep(4000);
+
+    try {
+      await reportWithSession(sessionKey, pledgePda, null);
+      expect.fail("Should have thrown NotPledgeOwner error");
+    } catch (err) {
+      expect(err.message).to.include("NotPledgeOwner");
+    }
+  });
+
+  it("fails to report after the session is revoked", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sessionKey = Keypair.generate();
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    const session = await createSession(user, sessionKey.publicKey, 3600);
+    await ctx.program.methods
+      .revokeSession()
+      .accounts({
+        user: user.keypair.publicKey,
+      })
+      .signers([user.keypair])
+      .rpc();
+    expect(await ctx.provider.connection.getAccountInfo(session)).to.be.null;
+    await sleep(4000);
+
+    try {
+      await reportWithSession(sessionKey, pledgePda, session);
+      expect.fail("Should have thrown AccountNotInitialized error");
+    } catch (err) {
+      expect(err.message).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("fails to create a session longer than 30 days", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    try {
+      await createSession(user, Keypair.generate().publicKey, 31 * 86400);
+      expect.fail("Should have thrown InvalidSession error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSession");
+    }
+  });
+});
@@ -0,0 +1,267 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  derivePledgePda,
+  deriveVaultPda,
+  getNextPledgeIndex,
+  getCurrentTimestamp,
+  getDeadlineBucket,
+  deriveSponsorshipPda,
+  deriveSponsorVaultPda,
+  getTokenBalance,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  ONE_USDC,
+  TEN_USDC,
+  HUNDRED_USDC,
+  NO_METADATA,
+  DEFAULT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("sponsorship", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  async function addSponsorship(sponsor: UserContext, pledgePda: PublicKey, amount: number) {
+    await ctx.program.methods
+      .addSponsorship(new anchor.BN(amount))
+      .accounts({
+        sponsor: sponsor.keypair.publicKey,
+        pledge: pledgePda,
+        mint: ctx.usdcMint,
+        sponsorTokenAccount: sponsor.tokenAccount,
+      })
+      .signers([sponsor.keypair])
+      .rpc();
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    completionPercentage: number,
+    sponsor: UserContext | null,
+    refundTokenAccount: PublicKey = user.tokenAccount,
+    bonusTokenAccount: PublicKey | null = null
+  ) {
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    const [sponsorship] = deriveSponsorshipPda(ctx.program.programId, pledgePda);
+    const [sponsorVault] = deriveSponsorVaultPda(ctx.program.programId, pledgePda);
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: refundTokenAccount,
+          feeVault: getFeeVault(ctx),
+          sponsorship: sponsor ? sponsorship : null,
+          sponsorVault: sponsor ? sponsorVault : null,
+          sponsor: sponsor ? sponsor.keypair.publicKey : null,
+          sponsorTokenAccount: sponsor ? sponsor.tokenAccount : null,
+          bonusTokenAccount,
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  it("pays the full bonus on 100% completion", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await addSponsorship(sponsor, pledgePda, 5 * ONE_USDC);
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.sponsorship.toNumber()).to.equal(5 * ONE_USDC);
+
+    await sleep(4000);
+    await reportAndProcess(user, pledgePda, vaultPda, 100, sponsor);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      HUNDRED_USDC + 5 * ONE_USDC
+    );
+    expect(Number(await getTokenBalance(ctx.provider.connection, sponsor.tokenAccount))).to.equal(
+      HUNDRED_USDC - 5 * ONE_USDC
+    );
+    const [sponsorVault] = deriveSponsorVaultPda(ctx.program.programId, pledgePda);
+    expect(await ctx.provider.connection.getAccountInfo(sponsorVault)).to.be.null;
+  });
+
+  it("pays the bonus pro-rata on partial completion", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await addSponsorship(sponsor, pledgePda, 5 * ONE_USDC);
+    await sleep(4000);
+    await reportAndProcess(user, pledgePda, vaultPda, 60, sponsor);
+
+    // 60% of the bonus earned, 40% back to the sponsor
+    expect(Number(await getTokenBalance(ctx.provider.connection, sponsor.tokenAccount))).to.equal(
+      HUNDRED_USDC - 3 * ONE_USDC
+    );
+  });
+
+  it("pays the bonus to the goal-setter, not the funder, on a funded pledge", async () => {
+    const user = await createTestUser(ctx, 0);
+    const funder = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+
+    const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
+    const deadline = new anchor.BN(currentTimestamp + 3);
+    const [pledgePda] = derivePledgePda(
+      ctx.program.programId,
+      user.keypair.publicKey,
+      await getNextPledgeIndex(ctx, user.keypair.publicKey)
+    );
+    const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
+    await ctx.program.methods
+      .createPledge(new anchor.BN(TEN_USDC), deadline, null, NO_METADATA, false, null, DEFAULT_OUTCOME, false, null, 0)
+      .accounts({
+        user: user.keypair.publicKey,
+        payer: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        deadlineBucket: getDeadlineBucket(ctx, deadline),
+        userTokenAccount: null,
+        funder: funder.keypair.publicKey,
+        funderTokenAccount: funder.tokenAccount,
+        mint: ctx.usdcMint,
+      })
+      .signers([user.keypair, funder.keypair])
+      .rpc();
+
+    await addSponsorship(sponsor, pledgePda, 5 * ONE_USDC);
+    await sleep(4000);
+
+    // The bonus can't follow the refund to the funder
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, 100, sponsor, funder.tokenAccount);
+      expect.fail("Should have thrown InvalidBonusAccount error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidBonusAccount");
+    }
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: funder.tokenAccount,
+          feeVault: getFeeVault(ctx),
+          sponsorship: deriveSponsorshipPda(ctx.program.programId, pledgePda)[0],
+          sponsorVault: deriveSponsorVaultPda(ctx.program.programId, pledgePda)[0],
+          sponsor: sponsor.keypair.publicKey,
+          sponsorTokenAccount: sponsor.tokenAccount,
+          bonusTokenAccount: user.tokenAccount,
+        },
+      })
+      .signers([crank])
+      .rpc();
+
+    // Stake back to the funder, bonus to the goal-setter
+    expect(Number(await getTokenBalance(ctx.provider.connection, funder.tokenAccount))).to.equal(
+      HUNDRED_USDC
+    );
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      5 * ONE_USDC
+    );
+  });
+
+  it("returns the bonus to the sponsor on failure", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await addSponsorship(sponsor, pledgePda, 5 * ONE_USDC);
+    await sleep(4000);
+    await reportAndProcess(user, pledgePda, vaultPda, 0, sponsor);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, sponsor.tokenAccount))).to.equal(
+      HUNDRED_USDC
+    );
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      HUNDRED_USDC - TEN_USDC
+    );
+  });
+
+  it("fails to settle a sponsored pledge without the sponsor accounts", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await addSponsorship(sponsor, pledgePda, ONE_USDC);
+    await sleep(4000);
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, 100, null);
+      expect.fail("Should have thrown InvalidSponsor error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSponsor");
+    }
+  });
+
+  it("fails to sponsor your own pledge", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await addSponsorship(user, pledgePda, ONE_USDC);
+      expect.fail("Should have thrown InvalidSponsor error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSponsor");
+    }
+  });
+
+  it("fails to add a second sponsor", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const sponsor = await createTestUser(ctx, HUNDRED_USDC);
+    const other = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda } = await createPledge(ctx, user, TEN_USDC);
+
+    await addSponsorship(sponsor, pledgePda, ONE_USDC);
+    await addSponsorship(sponsor, pledgePda, ONE_USDC);
+    const [sponsorshipPda] = deriveSponsorshipPda(ctx.program.programId, pledgePda);
+    const sponsorship = await ctx.program.account.sponsorship.fetch(sponsorshipPda);
+    expect(sponsorship.amount.toNumber()).to.equal(2 * ONE_USDC);
+
+    try {
+      await addSponsorship(other, pledgePda, ONE_USDC);
+      expect.fail("Should have thrown InvalidSponsor error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidSponsor");
+    }
+  });
+});
@@ -0,0 +1,286 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  deriveContributionPda,
+  getTokenBalance,
+  getFeeVault,
+  getDeadlineBucket,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  DEFAULT_OUTCOME,
+  sleep,
+} from "./utils/helpers";
+
+describe("team_pledge", () => {
+  let ctx: TestContext;
+  let crank: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+  });
+
+  async function join(
+    contributor: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    amount: number
+  ) {
+    await ctx.program.methods
+      .joinTeamPledge(new anchor.BN(amount))
+      .accounts({
+        contributor: contributor.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        contributorTokenAccount: contributor.tokenAccount,
+      })
+      .signers([contributor.keypair])
+      .rpc();
+  }
+
+  async function vote(
+    contributor: UserContext,
+    pledgePda: PublicKey,
+    completionPercentage: number
+  ) {
+    await ctx.program.methods
+      .voteTeamCompletion(completionPercentage)
+      .accounts({
+        contributor: contributor.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([contributor.keypair])
+      .rpc();
+  }
+
+  async function processCompletion(
+    captain: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey
+  ) {
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: captain.keypair.publicKey,
+          userTokenAccount: captain.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  async function claim(
+    contributor: UserContext,
+    captain: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey
+  ) {
+    await ctx.program.methods
+      .claimTeamRefund()
+      .accounts({
+        contributor: contributor.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        user: captain.keypair.publicKey,
+        contributorTokenAccount: contributor.tokenAccount,
+      })
+      .signers([contributor.keypair])
+      .rpc();
+  }
+
+  it("refunds the captain on settlement and the contributor on claim", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const contributor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3, false, null, DEFAULT_OUTCOME, { captain: {} }
+    );
+
+    await join(contributor, pledgePda, vaultPda, 2 * TEN_USDC);
+
+    let pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.stakeAmount.toNumber()).to.equal(3 * TEN_USDC);
+    expect(pledge.team.captainStake.toNumber()).to.equal(TEN_USDC);
+    expect(pledge.team.contributors).to.equal(1);
+
+    await sleep(4000);
+    await ctx.program.methods
+      .reportCompletion(100)
+      .accounts({
+        user: captain.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([captain.keypair])
+      .rpc();
+    await processCompletion(captain, pledgePda, vaultPda);
+
+    // Captain's share is refunded now, the contributor's waits in the vault
+    expect(Number(await getTokenBalance(ctx.provider.connection, captain.tokenAccount))).to.equal(HUNDRED_USDC);
+    expect(Number(await getTokenBalance(ctx.provider.connection, vaultPda))).to.equal(2 * TEN_USDC);
+    pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.team.refundPool.toNumber()).to.equal(2 * TEN_USDC);
+
+    await claim(contributor, captain, pledgePda, vaultPda);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, contributor.tokenAccount))).to.equal(
+      HUNDRED_USDC
+    );
+    expect(await ctx.provider.connection.getAccountInfo(vaultPda)).to.be.null;
+    const [contributionPda] = deriveContributionPda(
+      ctx.program.programId,
+      pledgePda,
+      contributor.keypair.publicKey
+    );
+    expect(await ctx.provider.connection.getAccountInfo(contributionPda)).to.be.null;
+  });
+
+  it("reports the lowest vote once the quorum is reached", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const first = await createTestUser(ctx, HUNDRED_USDC);
+    const second = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3, false, null, DEFAULT_OUTCOME, { quorum: { 0: 2 } }
+    );
+
+    await join(first, pledgePda, vaultPda, TEN_USDC);
+    await join(second, pledgePda, vaultPda, TEN_USDC);
+    await sleep(4000);
+
+    // Quorum teams don't take the captain's report
+    try {
+      await ctx.program.methods
+        .reportCompletion(100)
+        .accounts({
+          user: captain.keypair.publicKey,
+          pledge: pledgePda,
+        })
+        .signers([captain.keypair])
+        .rpc();
+      expect.fail("Should have thrown InvalidTeamReport error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidTeamReport");
+    }
+
+    await vote(first, pledgePda, 100);
+    let pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ active: {} });
+
+    await vote(second, pledgePda, 50);
+    pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.status).to.deep.equal({ reported: {} });
+    expect(pledge.completionPercentage).to.equal(50);
+  });
+
+  it("fails to vote twice", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const contributor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3, false, null, DEFAULT_OUTCOME, { quorum: { 0: 2 } }
+    );
+
+    await join(contributor, pledgePda, vaultPda, TEN_USDC);
+    await sleep(4000);
+    await vote(contributor, pledgePda, 100);
+
+    try {
+      await vote(contributor, pledgePda, 100);
+      expect.fail("Should have thrown AlreadyVoted error");
+    } catch (err) {
+      expect(err.message).to.include("AlreadyVoted");
+    }
+  });
+
+  it("fails to join as the captain", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3600, false, null, DEFAULT_OUTCOME, { captain: {} }
+    );
+
+    try {
+      await join(captain, pledgePda, vaultPda, TEN_USDC);
+      expect.fail("Should have thrown InvalidContributor error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidContributor");
+    }
+  });
+
+  it("fails to join a solo pledge", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const contributor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await join(contributor, pledgePda, vaultPda, TEN_USDC);
+      expect.fail("Should have thrown InvalidTeam error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidTeam");
+    }
+  });
+
+  it("fails to join during the contributor's self-exclusion", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const contributor = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3600, false, null, DEFAULT_OUTCOME, { captain: {} }
+    );
+    await ctx.program.methods
+      .setUserLimits(null, null, null, new anchor.BN(86400))
+      .accounts({
+        user: contributor.keypair.publicKey,
+      })
+      .signers([contributor.keypair])
+      .rpc();
+
+    try {
+      await join(contributor, pledgePda, vaultPda, TEN_USDC);
+      expect.fail("Should have thrown SelfExcluded error");
+    } catch (err) {
+      expect(err.message).to.include("SelfExcluded");
+    }
+  });
+
+  it("fails to edit a team pledge", async () => {
+    const captain = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(
+      ctx, captain, TEN_USDC, 3600, false, null, DEFAULT_OUTCOME, { captain: {} }
+    );
+    const pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    const newDeadline = pledge.deadline.addn(3600);
+
+    try {
+      await ctx.program.methods
+        .editPledge(newDeadline, null)
+        .accounts({
+          user: captain.keypair.publicKey,
+          payer: captain.keypair.publicKey,
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: getDeadlineBucket(ctx, pledge.deadline),
+          newDeadlineBucket: getDeadlineBucket(ctx, newDeadline),
+          feeVault: getFeeVault(ctx),
+        })
+        .signers([captain.keypair])
+        .rpc();
+      expect.fail("Should have thrown TeamPledgeLocked error");
+    } catch (err) {
+      expect(err.message).to.include("TeamPledgeLocked");
+    }
+  });
+});
@@ -0,0 +1,127 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  TestContext,
+  UserContext,
+  TEN_USDC,
+  HUNDRED_USDC,
+  USER_LIMITS_SEED,
+} from "./utils/helpers";
+
+describe("user_limits", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+  });
+
+  function deriveUserLimitsPda(user: PublicKey): PublicKey {
+    const [userLimitsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(USER_LIMITS_SEED), user.toBuffer()],
+      ctx.program.programId
+    );
+    return userLimitsPda;
+  }
+
+  async function setUserLimits(
+    user: UserContext,
+    maxMonthlyStake: number | null,
+    maxSingleStake: number | null,
+    maxMonthlyForfeiture: number | null,
+    excludeForSeconds: number | null
+  ) {
+    const amount = (value: number | null) => (value === null ? null : new anchor.BN(value));
+    await ctx.program.methods
+      .setUserLimits(
+        amount(maxMonthlyStake),
+        amount(maxSingleStake),
+        amount(maxMonthlyForfeiture),
+        amount(excludeForSeconds)
+      )
+      .accounts({
+        user: user.keypair.publicKey,
+      })
+      .signers([user.keypair])
+      .rpc();
+  }
+
+  it("applies a lowered cap immediately and delays raising it", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+
+    await setUserLimits(user, null, TEN_USDC, null, null);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC + 1);
+      expect.fail("Should have thrown SingleStakeLimitExceeded error");
+    } catch (err) {
+      expect(err.message).to.include("SingleStakeLimitExceeded");
+    }
+
+    // Raising the cap is queued - the old cap still applies
+    await setUserLimits(user, null, 2 * TEN_USDC, null, null);
+
+    const limits = await ctx.program.account.userLimits.fetch(
+      deriveUserLimitsPda(user.keypair.publicKey)
+    );
+    expect(limits.caps.maxSingleStake.toNumber()).to.equal(TEN_USDC);
+    expect(limits.pendingCaps.maxSingleStake.toNumber()).to.equal(2 * TEN_USDC);
+    expect(limits.pendingEffectiveAt.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC + 1);
+      expect.fail("Should have thrown SingleStakeLimitExceeded error");
+    } catch (err) {
+      expect(err.message).to.include("SingleStakeLimitExceeded");
+    }
+
+    await createPledge(ctx, user, TEN_USDC);
+  });
+
+  it("caps the stake created within the month", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    await setUserLimits(user, 2 * TEN_USDC, null, null, null);
+
+    await createPledge(ctx, user, TEN_USDC);
+    await createPledge(ctx, user, TEN_USDC);
+
+    try {
+      await createPledge(ctx, user, 1);
+      expect.fail("Should have thrown MonthlyStakeLimitExceeded error");
+    } catch (err) {
+      expect(err.message).to.include("MonthlyStakeLimitExceeded");
+    }
+  });
+
+  it("caps the stake at risk of forfeiture within the month", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    await setUserLimits(user, null, null, 1.5 * TEN_USDC, null);
+
+    await createPledge(ctx, user, TEN_USDC);
+
+    // 10 USDC already at risk + 10 USDC more would exceed 15 USDC
+    try {
+      await createPledge(ctx, user, TEN_USDC);
+      expect.fail("Should have thrown ForfeitureLimitExceeded error");
+    } catch (err) {
+      expect(err.message).to.include("ForfeitureLimitExceeded");
+    }
+  });
+
+  it("blocks new pledges during self-exclusion", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    await setUserLimits(user, null, null, null, 86400);
+
+    try {
+      await createPledge(ctx, user, TEN_USDC);
+      expect.fail("Should have thrown SelfExcluded error");
+    } catch (err) {
+      expect(err.message).to.include("SelfExcluded");
+    }
+  });
+});
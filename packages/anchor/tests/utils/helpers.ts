@@ -12,7 +12,6 @@ import {
   mintTo,
   getAssociatedTokenAddress,
   TOKEN_PROGRAM_ID,
-  ASSOCIATED_TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import { Pledge } from "../../target/types/pledge";
 
@@ -21,11 +20,31 @@ export const DEFAULT_TREASURY_SPLIT_BPS = 7000; // 70%
 export const DEFAULT_PARTIAL_FEE_BPS = 100; // 1%
 export const DEFAULT_EDIT_PENALTY_BPS = 1000; // 10%
 export const DEFAULT_GRACE_PERIOD = 86400; // 1 day in seconds
+export const DEFAULT_MAX_BENEFICIARY_BPS = 5000; // 50% of the charity share
+export const DEFAULT_CRANK_REWARD_BPS = 50; // 0.5% of fees + forfeitures
 
 // PDA seeds
 export const CONFIG_SEED = "config";
 export const PLEDGE_SEED = "pledge";
 export const VAULT_SEED = "vault";
+export const FEE_VAULT_SEED = "fee_vault";
+export const FEE_LEDGER_SEED = "fee_ledger";
+export const CHARITY_SEED = "charity";
+export const CHARITY_LEDGER_SEED = "charity_ledger";
+export const DEADLINE_BUCKET_SEED = "deadline_bucket";
+export const USER_COUNTER_SEED = "user_counter";
+export const USER_LIMITS_SEED = "user_limits";
+export const CHALLENGE_SEED = "challenge";
+export const CHALLENGE_VAULT_SEED = "challenge_vault";
+export const CONTRIBUTION_SEED = "contribution";
+export const SPONSORSHIP_SEED = "sponsorship";
+export const SPONSOR_VAULT_SEED = "sponsor_vault";
+export const SESSION_SEED = "session";
+export const RECOVERY_SEED = "recovery";
+
+// Deadline index
+export const SECONDS_PER_BUCKET = 86400; // One bucket per day
+export const DEADLINE_BUCKET_SHARDS = 16; // Buckets per day
 
 // Shared test keypairs (deterministic for consistent testing)
 // These are used across all tests to ensure config PDA matches
@@ -40,6 +59,17 @@ export const ONE_USDC = 1_000_000;
 export const TEN_USDC = 10_000_000;
 export const HUNDRED_USDC = 100_000_000;
 
+// Goal metadata for pledges that don't commit to any
+export const NO_METADATA = {
+  contentHash: Array(32).fill(0),
+  category: 0,
+  isPublic: false,
+};
+
+// Unreported pledges settle at the crank's attested percentage
+export const DEFAULT_OUTCOME = { attested: {} };
+export const FORFEIT_OUTCOME = { forfeit: {} }; // Required for challenge pledges
+
 export interface TestContext {
   program: Program<Pledge>;
   provider: anchor.AnchorProvider;
@@ -149,30 +179,60 @@ export async function initializeConfig(ctx: TestContext): Promise<void> {
   }
 
   // Check if config already exists
+  let configExists = false;
   try {
     const existingConfig = await ctx.program.account.programConfig.fetch(ctx.configPda);
-    if (existingConfig) {
-      // Config already initialized - skip
-      return;
-    }
+    configExists = !!existingConfig;
   } catch {
     // Config doesn't exist - proceed with initialization
   }
 
-  await ctx.program.methods
-    .initialize(
-      ctx.treasury.publicKey,
-      ctx.charity.publicKey,
-      DEFAULT_TREASURY_SPLIT_BPS,
-      DEFAULT_PARTIAL_FEE_BPS,
-      DEFAULT_EDIT_PENALTY_BPS,
-      new anchor.BN(DEFAULT_GRACE_PERIOD)
-    )
-    .accounts({
-      admin: ctx.admin.publicKey,
-    })
-    .signers([ctx.admin])
-    .rpc();
+  if (!configExists) {
+    await ctx.program.methods
+      .initialize(
+        getDefaultRevenueShares(ctx),
+        DEFAULT_PARTIAL_FEE_BPS,
+        DEFAULT_EDIT_PENALTY_BPS,
+        new anchor.BN(DEFAULT_GRACE_PERIOD)
+      )
+      .accounts({
+        admin: ctx.admin.publicKey,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  // Fee vault is per mint, so it may be missing even when config exists
+  const feeVault = getFeeVault(ctx);
+  const feeVaultInfo = await ctx.provider.connection.getAccountInfo(feeVault);
+  if (!feeVaultInfo) {
+    await ctx.program.methods
+      .initializeFeeVault()
+      .accounts({
+        admin: ctx.admin.publicKey,
+        mint: ctx.usdcMint,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+}
+
+/**
+ * Default revenue shares: treasury (70%) and charity slot (30%)
+ */
+export function getDefaultRevenueShares(ctx: TestContext) {
+  return [
+    {
+      recipient: ctx.treasury.publicKey,
+      bps: DEFAULT_TREASURY_SPLIT_BPS,
+      isCharity: false,
+    },
+    {
+      recipient: ctx.charity.publicKey,
+      bps: 10000 - DEFAULT_TREASURY_SPLIT_BPS,
+      isCharity: true,
+    },
+  ];
 }
 
 /**
@@ -209,23 +269,48 @@ export async function createTestUser(
 }
 
 /**
- * Derive pledge PDA from user and created_at timestamp
+ * Derive pledge PDA from user and per-user pledge index
  */
 export function derivePledgePda(
   programId: PublicKey,
   user: PublicKey,
-  createdAt: anchor.BN
+  index: anchor.BN
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [
       Buffer.from(PLEDGE_SEED),
       user.toBuffer(),
-      createdAt.toArrayLike(Buffer, "le", 8),
+      index.toArrayLike(Buffer, "le", 8),
     ],
     programId
   );
 }
 
+/**
+ * Derive user counter PDA from user
+ */
+export function deriveUserCounterPda(
+  programId: PublicKey,
+  user: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(USER_COUNTER_SEED), user.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Get the index the user's next pledge will be seeded with
+ */
+export async function getNextPledgeIndex(
+  ctx: TestContext,
+  user: PublicKey
+): Promise<anchor.BN> {
+  const [userCounterPda] = deriveUserCounterPda(ctx.program.programId, user);
+  const userCounter = await ctx.program.account.userCounter.fetchNullable(userCounterPda);
+  return userCounter ? userCounter.nextIndex : new anchor.BN(0);
+}
+
 /**
  * Derive vault PDA from pledge
  */
@@ -239,6 +324,103 @@ export function deriveVaultPda(
   );
 }
 
+/**
+ * Derive challenge PDA from creator and creator-chosen challenge id
+ */
+export function deriveChallengePda(
+  programId: PublicKey,
+  creator: PublicKey,
+  challengeId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(CHALLENGE_SEED),
+      creator.toBuffer(),
+      challengeId.toArrayLike(Buffer, "le", 8),
+    ],
+    programId
+  );
+}
+
+/**
+ * Derive challenge pool vault PDA from challenge
+ */
+export function deriveChallengeVaultPda(
+  programId: PublicKey,
+  challenge: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CHALLENGE_VAULT_SEED), challenge.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a contributor's record on a team pledge
+ */
+export function deriveContributionPda(
+  programId: PublicKey,
+  pledge: PublicKey,
+  contributor: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CONTRIBUTION_SEED), pledge.toBuffer(), contributor.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a pledge's sponsorship record
+ */
+export function deriveSponsorshipPda(
+  programId: PublicKey,
+  pledge: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SPONSORSHIP_SEED), pledge.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a pledge's sponsor bonus vault
+ */
+export function deriveSponsorVaultPda(
+  programId: PublicKey,
+  pledge: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SPONSOR_VAULT_SEED), pledge.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a user's session key record
+ */
+export function deriveSessionPda(
+  programId: PublicKey,
+  user: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SESSION_SEED), user.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive a user's recovery profile
+ */
+export function deriveRecoveryPda(
+  programId: PublicKey,
+  user: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(RECOVERY_SEED), user.toBuffer()],
+    programId
+  );
+}
+
 /**
  * Get current timestamp from the cluster
  */
@@ -311,32 +493,49 @@ export async function createPledge(
   ctx: TestContext,
   user: UserContext,
   stakeAmount: number,
-  deadlineOffset: number = 3600 // 1 hour from now
-): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey; createdAt: anchor.BN }> {
+  deadlineOffset: number = 3600, // 1 hour from now
+  earlyReport: boolean = false,
+  gracePeriodSeconds: number | null = null,
+  defaultOutcome: object = DEFAULT_OUTCOME,
+  team: object | null = null
+): Promise<{ pledgePda: PublicKey; vaultPda: PublicKey; index: anchor.BN }> {
   const currentTimestamp = await getCurrentTimestamp(ctx.provider.connection);
-  const createdAt = new anchor.BN(currentTimestamp);
+  const index = await getNextPledgeIndex(ctx, user.keypair.publicKey);
   const deadline = new anchor.BN(currentTimestamp + deadlineOffset);
 
   const [pledgePda] = derivePledgePda(
     ctx.program.programId,
     user.keypair.publicKey,
-    createdAt
+    index
   );
   const [vaultPda] = deriveVaultPda(ctx.program.programId, pledgePda);
 
   await ctx.program.methods
-    .createPledge(new anchor.BN(stakeAmount), deadline, createdAt)
+    .createPledge(
+      new anchor.BN(stakeAmount),
+      deadline,
+      null,
+      NO_METADATA,
+      earlyReport,
+      gracePeriodSeconds !== null ? new anchor.BN(gracePeriodSeconds) : null,
+      defaultOutcome,
+      false,
+      team,
+      0
+    )
     .accounts({
       user: user.keypair.publicKey,
+      payer: user.keypair.publicKey,
       pledge: pledgePda,
       vault: vaultPda,
+      deadlineBucket: getDeadlineBucket(ctx, deadline),
       userTokenAccount: user.tokenAccount,
       mint: ctx.usdcMint,
     })
     .signers([user.keypair])
     .rpc();
 
-  return { pledgePda, vaultPda, createdAt };
+  return { pledgePda, vaultPda, index };
 }
 
 /**
@@ -356,3 +555,107 @@ export async function getCharityTokenAccount(
 ): Promise<PublicKey> {
   return getAssociatedTokenAddress(ctx.usdcMint, ctx.charity.publicKey);
 }
+
+/**
+ * Derive fee vault PDA for a mint
+ */
+export function deriveFeeVaultPda(
+  programId: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(FEE_VAULT_SEED), mint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Get the fee vault for the test USDC mint
+ */
+export function getFeeVault(ctx: TestContext): PublicKey {
+  const [feeVault] = deriveFeeVaultPda(ctx.program.programId, ctx.usdcMint);
+  return feeVault;
+}
+
+/**
+ * Derive fee ledger PDA for a mint
+ */
+export function deriveFeeLedgerPda(
+  programId: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(FEE_LEDGER_SEED), mint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Get the fee ledger for the test USDC mint
+ */
+export function getFeeLedger(ctx: TestContext): PublicKey {
+  const [feeLedger] = deriveFeeLedgerPda(ctx.program.programId, ctx.usdcMint);
+  return feeLedger;
+}
+
+/**
+ * Get undistributed treasury/charity fees from the USDC fee ledger
+ * (revenue shares in the order set by getDefaultRevenueShares)
+ */
+export async function getAccruedFees(
+  ctx: TestContext
+): Promise<{ treasury: bigint; charity: bigint }> {
+  const feeLedger = await ctx.program.account.feeLedger.fetch(getFeeLedger(ctx));
+  return {
+    treasury: BigInt(feeLedger.accrued[0].toString()),
+    charity: BigInt(feeLedger.accrued[1].toString()),
+  };
+}
+
+/**
+ * Derive a registered charity's fee ledger PDA for a mint
+ */
+export function deriveCharityLedgerPda(
+  programId: PublicKey,
+  charity: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CHARITY_LEDGER_SEED), charity.toBuffer(), mint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Derive deadline bucket PDA for the day a deadline falls on and a shard of that day
+ */
+export function deriveDeadlineBucketPda(
+  programId: PublicKey,
+  deadline: anchor.BN,
+  shard: number = 0
+): [PublicKey, number] {
+  const day = new anchor.BN(Math.floor(deadline.toNumber() / SECONDS_PER_BUCKET));
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DEADLINE_BUCKET_SEED), day.toArrayLike(Buffer, "le", 8), Buffer.from([shard])],
+    programId
+  );
+}
+
+/**
+ * Get the deadline bucket for a deadline (shard 0 unless given)
+ */
+export function getDeadlineBucket(ctx: TestContext, deadline: anchor.BN, shard: number = 0): PublicKey {
+  const [deadlineBucket] = deriveDeadlineBucketPda(ctx.program.programId, deadline, shard);
+  return deadlineBucket;
+}
+
+/**
+ * Get the deadline bucket for a pledge's current deadline
+ */
+export async function getPledgeDeadlineBucket(
+  ctx: TestContext,
+  pledge: PublicKey
+): Promise<PublicKey> {
+  const pledgeAccount = await ctx.program.account.pledge.fetch(pledge);
+  return getDeadlineBucket(ctx, pledgeAccount.deadline, pledgeAccount.deadlineShard);
+}
@@ -0,0 +1,286 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { MockLending } from "../target/types/mock_lending";
+import {
+  setupTestContext,
+  initializeConfig,
+  createTestUser,
+  createPledge,
+  getTokenBalance,
+  getFeeVault,
+  getPledgeDeadlineBucket,
+  airdrop,
+  TestContext,
+  UserContext,
+  ONE_USDC,
+  TEN_USDC,
+  HUNDRED_USDC,
+  sleep,
+} from "./utils/helpers";
+
+describe("yield", () => {
+  let ctx: TestContext;
+  let lending: Program<MockLending>;
+  let crank: Keypair;
+  let reserve: PublicKey;
+  let reserveVault: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await initializeConfig(ctx);
+    lending = anchor.workspace.MockLending as Program<MockLending>;
+
+    crank = Keypair.generate();
+    await airdrop(ctx.provider.connection, crank.publicKey, 5 * anchor.web3.LAMPORTS_PER_SOL);
+
+    [reserve] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve"), ctx.usdcMint.toBuffer()],
+      lending.programId
+    );
+    [reserveVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve_vault"), ctx.usdcMint.toBuffer()],
+      lending.programId
+    );
+    if (!(await ctx.provider.connection.getAccountInfo(reserve))) {
+      await lending.methods
+        .initReserve()
+        .accounts({ payer: ctx.admin.publicKey, mint: ctx.usdcMint })
+        .signers([ctx.admin])
+        .rpc();
+    }
+
+    await setYieldPolicy(lending.programId, 5000);
+  });
+
+  after(async () => {
+    // Config is shared across test files - switch yield mode back off
+    await setYieldPolicy(null, 0);
+  });
+
+  async function setYieldPolicy(adapter: PublicKey | null, userBps: number) {
+    await ctx.program.methods
+      .updateConfig(null, null, null, null, null, null, null, null, null, null, null, null, { adapter, userBps }, null)
+      .accounts({
+        admin: ctx.admin.publicKey,
+        config: ctx.configPda,
+      })
+      .signers([ctx.admin])
+      .rpc();
+  }
+
+  function derivePosition(pledgePda: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), reserve.toBuffer(), pledgePda.toBuffer()],
+      lending.programId
+    )[0];
+  }
+
+  async function depositYield(user: UserContext, pledgePda: PublicKey, vaultPda: PublicKey) {
+    await ctx.program.methods
+      .depositYield()
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        adapterProgram: lending.programId,
+        reserve,
+        reserveVault,
+        position: derivePosition(pledgePda),
+      })
+      .signers([user.keypair])
+      .rpc();
+  }
+
+  async function withdrawYield(caller: Keypair, user: UserContext, pledgePda: PublicKey, vaultPda: PublicKey) {
+    await ctx.program.methods
+      .withdrawYield()
+      .accounts({
+        caller: caller.publicKey,
+        pledge: pledgePda,
+        vault: vaultPda,
+        user: user.keypair.publicKey,
+        adapterProgram: lending.programId,
+        reserve,
+        reserveVault,
+        position: derivePosition(pledgePda),
+      })
+      .signers([caller])
+      .rpc();
+  }
+
+  async function accrue(pledgePda: PublicKey, amount: number) {
+    const donor = await createTestUser(ctx, amount);
+    await lending.methods
+      .accrue(new anchor.BN(amount))
+      .accounts({
+        donor: donor.keypair.publicKey,
+        donorTokenAccount: donor.tokenAccount,
+        reserve,
+        reserveVault,
+        position: derivePosition(pledgePda),
+      })
+      .signers([donor.keypair])
+      .rpc();
+  }
+
+  async function reportAndProcess(
+    user: UserContext,
+    pledgePda: PublicKey,
+    vaultPda: PublicKey,
+    completionPercentage: number
+  ) {
+    await ctx.program.methods
+      .reportCompletion(completionPercentage)
+      .accounts({
+        user: user.keypair.publicKey,
+        pledge: pledgePda,
+      })
+      .signers([user.keypair])
+      .rpc();
+
+    await ctx.program.methods
+      .processCompletion()
+      .accounts({
+        crank: crank.publicKey,
+        settlement: {
+          pledge: pledgePda,
+          vault: vaultPda,
+          deadlineBucket: await getPledgeDeadlineBucket(ctx, pledgePda),
+          user: user.keypair.publicKey,
+          userTokenAccount: user.tokenAccount,
+          feeVault: getFeeVault(ctx),
+        },
+      })
+      .signers([crank])
+      .rpc();
+  }
+
+  it("lends the stake and pays the user's yield share with the refund", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await depositYield(user, pledgePda, vaultPda);
+    expect(Number(await getTokenBalance(ctx.provider.connection, vaultPda))).to.equal(0);
+    let pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.yieldPosition.principal.toNumber()).to.equal(TEN_USDC);
+    expect(pledge.yieldPosition.userBps).to.equal(5000);
+
+    await accrue(pledgePda, ONE_USDC);
+    await sleep(4000);
+
+    // Anyone may withdraw once the deadline has passed
+    await withdrawYield(crank, user, pledgePda, vaultPda);
+    expect(Number(await getTokenBalance(ctx.provider.connection, vaultPda))).to.equal(TEN_USDC + ONE_USDC);
+    pledge = await ctx.program.account.pledge.fetch(pledgePda);
+    expect(pledge.yieldPosition.withdrawn).to.be.true;
+    expect(pledge.yieldPosition.yieldAmount.toNumber()).to.equal(ONE_USDC);
+
+    const feeVaultBefore = Number(await getTokenBalance(ctx.provider.connection, getFeeVault(ctx)));
+    await reportAndProcess(user, pledgePda, vaultPda, 100);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      HUNDRED_USDC + ONE_USDC / 2
+    );
+    expect(Number(await getTokenBalance(ctx.provider.connection, getFeeVault(ctx)))).to.equal(
+      feeVaultBefore + ONE_USDC / 2
+    );
+  });
+
+  it("forfeits the principal exactly and still pays the yield share", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await depositYield(user, pledgePda, vaultPda);
+    await accrue(pledgePda, ONE_USDC);
+    await sleep(4000);
+    await withdrawYield(user.keypair, user, pledgePda, vaultPda);
+    await reportAndProcess(user, pledgePda, vaultPda, 0);
+
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      HUNDRED_USDC - TEN_USDC + ONE_USDC / 2
+    );
+  });
+
+  it("fails to settle while the stake is lent out", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await depositYield(user, pledgePda, vaultPda);
+    await sleep(4000);
+    try {
+      await reportAndProcess(user, pledgePda, vaultPda, 100);
+      expect.fail("Should have thrown YieldPositionOpen error");
+    } catch (err) {
+      expect(err.message).to.include("YieldPositionOpen");
+    }
+  });
+
+  it("fails to withdraw someone else's stake before the deadline", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    await depositYield(user, pledgePda, vaultPda);
+    try {
+      await withdrawYield(crank, user, pledgePda, vaultPda);
+      expect.fail("Should have thrown NotPledgeOwner error");
+    } catch (err) {
+      expect(err.message).to.include("NotPledgeOwner");
+    }
+
+    // The owner may pull the stake back early
+    await withdrawYield(user.keypair, user, pledgePda, vaultPda);
+    expect(Number(await getTokenBalance(ctx.provider.connection, vaultPda))).to.equal(TEN_USDC);
+  });
+
+  it("rejects a withdrawal short of the principal and keeps the refund math on the stake", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC, 3);
+
+    await depositYield(user, pledgePda, vaultPda);
+    const loser = await createTestUser(ctx, 0);
+    await lending.methods
+      .slash(new anchor.BN(1))
+      .accounts({
+        recipientTokenAccount: loser.tokenAccount,
+        reserve,
+        reserveVault,
+        position: derivePosition(pledgePda),
+      })
+      .rpc();
+
+    try {
+      await withdrawYield(user.keypair, user, pledgePda, vaultPda);
+      expect.fail("Should have thrown YieldShortfall error");
+    } catch (err) {
+      expect(err.message).to.include("YieldShortfall");
+    }
+
+    // Once the adapter makes the position whole the pledge settles exactly as if nothing was lost
+    await accrue(pledgePda, 1);
+    await sleep(4000);
+    await withdrawYield(user.keypair, user, pledgePda, vaultPda);
+    await reportAndProcess(user, pledgePda, vaultPda, 50);
+
+    // 50% of the stake less the 1% partial fee
+    expect(Number(await getTokenBalance(ctx.provider.connection, user.tokenAccount))).to.equal(
+      HUNDRED_USDC - TEN_USDC + (TEN_USDC / 2) * 0.99
+    );
+  });
+
+  it("fails to deposit when yield mode is off", async () => {
+    const user = await createTestUser(ctx, HUNDRED_USDC);
+    const { pledgePda, vaultPda } = await createPledge(ctx, user, TEN_USDC);
+
+    await setYieldPolicy(null, 0);
+    try {
+      await depositYield(user, pledgePda, vaultPda);
+      expect.fail("Should have thrown InvalidYieldAdapter error");
+    } catch (err) {
+      expect(err.message).to.include("InvalidYieldAdapter");
+    } finally {
+      await setYieldPolicy(lending.programId, 5000);
+    }
+  });
+});
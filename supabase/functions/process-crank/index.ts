@@ -13,6 +13,8 @@ import {
 import { Program, AnchorProvider } from 'https://esm.sh/@coral-xyz/anchor@0.28.0';
 
 const GRACE_PERIOD_SECONDS = 24 * 60 * 60; // 1 day
+const SECONDS_PER_BUCKET = 24 * 60 * 60; // One deadline bucket per day (per shard)
+const PLEDGE_PROGRAM_ID = new PublicKey('PLDG12YsnCxRHa9CkWDnzkA9vsbEFpThXHR9zgnDTDp');
 
 serve(async (req) => {
   try {
@@ -38,6 +40,10 @@ serve(async (req) => {
     const now = Math.floor(Date.now() / 1000);
     const cutoffTime = new Date((now - GRACE_PERIOD_SECONDS) * 1000).toISOString();
 
+    // TODO: Read due pledges from on-chain DeadlineBucket accounts (one per deadline
+    // day and shard, seeds ["deadline_bucket", day as i64 LE, shard as u8]) up to the
+    // cutoff day instead of relying on the DB, then closeDeadlineBucket once a bucket is empty.
+
     // Query for expired pledges (past deadline + grace period, still Active)
     const { data: expiredPledges, error } = await supabase
       .from('pledges')
@@ -52,6 +58,11 @@ serve(async (req) => {
 
     console.log(`Processing ${expiredPledges?.length || 0} expired pledges`);
 
+    // TODO: Settle in chunks with processBatch(completionPercentages) - one
+    // batchAccounts() group of 8 per pledge as remaining accounts. Pledges listed in
+    // BatchProcessed.skipped (charity/beneficiary routing, challenge, team, sponsored or
+    // lent out, or held by a pending recovery) still need an individual processExpired call.
+
     const results = [];
 
     for (const pledge of expiredPledges || []) {
@@ -125,3 +136,46 @@ function calculateCompletionPercentage(pledge: any): number {
   if (totalExpected === 0) return 0;
   return Math.round((totalCompleted / totalExpected) * 100);
 }
+
+/** On-chain pledge fields a batch group is derived from */
+interface OnChainPledge {
+  user: PublicKey;
+  deadline: number;
+  deadlineShard: number;
+  vaultBump: number;
+  rentPayer: PublicKey | null;
+}
+
+function findPda(seeds: Uint8Array[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, PLEDGE_PROGRAM_ID)[0];
+}
+
+/**
+ * processBatch remaining accounts for one pledge, in the order the program reads them:
+ * pledge, vault, user, user_token_account, deadline_bucket, user_counter, recovery, rent_payer.
+ * The rent payer slot repeats the user when nobody sponsored the pledge's rent.
+ */
+function batchAccounts(pledgeAddress: PublicKey, pledge: OnChainPledge, refundTokenAccount: PublicKey) {
+  const encoder = new TextEncoder();
+  const day = new Uint8Array(8);
+  new DataView(day.buffer).setBigInt64(0, BigInt(Math.floor(pledge.deadline / SECONDS_PER_BUCKET)), true);
+
+  const vault = PublicKey.createProgramAddressSync(
+    [encoder.encode('vault'), pledgeAddress.toBytes(), Uint8Array.of(pledge.vaultBump)],
+    PLEDGE_PROGRAM_ID
+  );
+  const deadlineBucket = findPda([encoder.encode('deadline_bucket'), day, Uint8Array.of(pledge.deadlineShard)]);
+  const userCounter = findPda([encoder.encode('user_counter'), pledge.user.toBytes()]);
+  const recovery = findPda([encoder.encode('recovery'), pledge.user.toBytes()]);
+
+  return [
+    pledgeAddress,
+    vault,
+    pledge.user,
+    refundTokenAccount,
+    deadlineBucket,
+    userCounter,
+    recovery,
+    pledge.rentPayer ?? pledge.user,
+  ].map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+}